@@ -21,6 +21,7 @@ use gpt_common::{
         least_losing_nodes_reachable::run_least_losing_nodes_reachable,
         monke::run_monke,
     },
+    test_case_generator::{generate, GenConfig, Strategy},
 };
 use itertools::Itertools;
 
@@ -36,6 +37,7 @@ struct Cli {
 enum Command {
     Run(Run),
     AndReduce(AndReduce),
+    Generate(Generate),
 }
 
 /// Read the input GPT file and generate test cases
@@ -87,6 +89,45 @@ struct AndReduce {
     file_path: String,
 }
 
+/// Generate the test cases of every feature with a strategy, the output is the same for the same seed
+#[derive(Parser, Debug)]
+struct Generate {
+    /// Strategy to choose the test values with
+    #[arg(short, long, value_enum, default_value_t = GenStrategy::BoundaryValue)]
+    strategy: GenStrategy,
+
+    /// Seed of the random values
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Drop the test cases violating the relations between variables, like `start <= end`
+    #[arg(long)]
+    honor_relations: bool,
+
+    /// Input GPT file path
+    file_path: String,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum GenStrategy {
+    /// In, on, off and out points of every boundary
+    BoundaryValue,
+    /// Middle of every interval
+    Midpoint,
+    /// Random value of every interval
+    Random,
+}
+
+impl From<GenStrategy> for Strategy {
+    fn from(strategy: GenStrategy) -> Self {
+        match strategy {
+            GenStrategy::BoundaryValue => Self::BoundaryValue,
+            GenStrategy::Midpoint => Self::Midpoint,
+            GenStrategy::Random => Self::Random,
+        }
+    }
+}
+
 impl fmt::Display for Algo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -116,6 +157,7 @@ fn print_typst_format(test_cases: &[NTupleSingleInterval]) {
                         gpt_common::dto::Output::MissingVariable => "*".to_owned(),
                         gpt_common::dto::Output::Bool(x) => format!("{x}"),
                         gpt_common::dto::Output::Interval(x) => format!("{x}"),
+                        gpt_common::dto::Output::Enum(x) => x.clone(),
                     }
                 )
             })
@@ -205,12 +247,32 @@ fn and_reduce(_cli: &Cli, cmd: &AndReduce) -> Result<(), Box<dyn std::error::Err
     Ok(())
 }
 
+fn generate_cmd(_cli: &Cli, cmd: &Generate) -> Result<(), Box<dyn std::error::Error>> {
+    let input = std::fs::read_to_string(&cmd.file_path)
+        .map_err(|e| format!("Error while reading file {}: {}", &cmd.file_path, e))?;
+
+    let config = GenConfig {
+        seed: cmd.seed,
+        strategy: cmd.strategy.into(),
+        honor_relations: cmd.honor_relations,
+    };
+
+    for feature in and_reduce_gpt_input(&input)? {
+        for test_case in generate(&feature, config) {
+            println!("{test_case}");
+        }
+    }
+
+    Ok(())
+}
+
 pub fn main() {
     let args = Cli::parse();
 
     let result = match &args.command {
         Command::Run(cmd) => run(&args, cmd),
         Command::AndReduce(cmd) => and_reduce(&args, cmd),
+        Command::Generate(cmd) => generate_cmd(&args, cmd),
     };
 
     match result {
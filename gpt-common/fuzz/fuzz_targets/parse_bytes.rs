@@ -0,0 +1,16 @@
+#![no_main]
+
+use gpt_common::{
+    and_reduce_gpt_input, ir::ir_to_ntuple::ir_to_ntuple, parser::parse_features_iter,
+};
+use libfuzzer_sys::fuzz_target;
+
+// Arbitrary bytes, the invalid UTF-8 sequences become replacement characters
+fuzz_target!(|data: &[u8]| {
+    let input = String::from_utf8_lossy(data);
+
+    if let Ok(features) = and_reduce_gpt_input(&input) {
+        features.iter().map(ir_to_ntuple).for_each(drop);
+    }
+    parse_features_iter(&input).for_each(drop);
+});
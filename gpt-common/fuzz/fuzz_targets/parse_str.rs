@@ -0,0 +1,15 @@
+#![no_main]
+
+use gpt_common::{
+    and_reduce_gpt_input, ir::ir_to_ntuple::ir_to_ntuple, parser::parse_features_iter,
+};
+use libfuzzer_sys::fuzz_target;
+
+// Any UTF-8 input should result in an `Ok` or an `Err`, but never in a panic or an infinite loop.
+// The type errors are rejected by `and_reduce_gpt_input`, so every accepted feature has to be converted to tuples.
+fuzz_target!(|input: &str| {
+    if let Ok(features) = and_reduce_gpt_input(input) {
+        features.iter().map(ir_to_ntuple).for_each(drop);
+    }
+    parse_features_iter(input).for_each(drop);
+});
@@ -0,0 +1,818 @@
+//! A compact, deterministic binary encoding of the features and the DTOs, for caching the parse results.
+//!
+//! Encoding the same value always produces the same bytes, so they can be hashed to detect changes:
+//! the maps are encoded with their keys sorted, the lengths are fixed-width, and `-0` is encoded as `0`.
+//! The infinities and the boundaries are encoded explicitly with their own tags.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::{
+    dto::{
+        BoolDTO, BoolExpression, EnumDTO, Input, IntervalDTO, NTupleInput, NTupleOutput, Output,
+        Value,
+    },
+    interval::{Boundary, Intersectable, Interval, MultiInterval},
+    ir::{
+        BoolCondition, Condition, EnumCondition, Feature, IntervalCondition, Predicate,
+        RelationCondition, Variable,
+    },
+    parser::ast::{BinaryOp, BoolOp, Literal, Type},
+};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    #[error("Unexpected end of input")]
+    UnexpectedEnd,
+
+    #[error("Invalid tag {tag} for {type_name}")]
+    InvalidTag { type_name: &'static str, tag: u8 },
+
+    #[error("Invalid UTF-8 in a string")]
+    InvalidUtf8,
+
+    #[error("Invalid interval, lo is greater than hi")]
+    InvalidInterval,
+
+    #[error("{0} bytes left after decoding")]
+    TrailingBytes(usize),
+}
+
+pub trait Encode {
+    fn encode(&self, bytes: &mut Vec<u8>);
+}
+
+pub trait Decode: Sized {
+    /// Decodes the value from the start of `bytes`, and advances `bytes` past it.
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError>;
+}
+
+pub fn to_bytes<T: Encode>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    value.encode(&mut bytes);
+    bytes
+}
+
+/// Decodes a value encoded with `to_bytes`, every byte of the input has to be used.
+pub fn from_bytes<T: Decode>(mut bytes: &[u8]) -> Result<T, DecodeError> {
+    let value = T::decode(&mut bytes)?;
+
+    match bytes.len() {
+        0 => Ok(value),
+        left => Err(DecodeError::TrailingBytes(left)),
+    }
+}
+
+const fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8], DecodeError> {
+    if bytes.len() < n {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+
+    let (taken, rest) = bytes.split_at(n);
+    *bytes = rest;
+    Ok(taken)
+}
+
+fn take_array<const N: usize>(bytes: &mut &[u8]) -> Result<[u8; N], DecodeError> {
+    Ok(take(bytes, N)?
+        .try_into()
+        .expect("take returns exactly N bytes"))
+}
+
+const fn invalid_tag<T>(type_name: &'static str, tag: u8) -> Result<T, DecodeError> {
+    Err(DecodeError::InvalidTag { type_name, tag })
+}
+
+impl Encode for u8 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.push(*self);
+    }
+}
+
+impl Decode for u8 {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(take_array::<1>(bytes)?[0])
+    }
+}
+
+impl Encode for bool {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        u8::from(*self).encode(bytes);
+    }
+}
+
+impl Decode for bool {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(bytes)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => invalid_tag("bool", tag),
+        }
+    }
+}
+
+/// The lengths are always 8 bytes, so the encoding is the same on 32 and 64 bit platforms
+impl Encode for usize {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend((*self as u64).to_le_bytes());
+    }
+}
+
+impl Decode for usize {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(u64::from_le_bytes(take_array(bytes)?) as Self)
+    }
+}
+
+/// `-Inf` and `Inf` have their own tags, the finite numbers are tagged and followed by their bits
+impl Encode for f32 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match *self {
+            Self::NEG_INFINITY => 1u8.encode(bytes),
+            Self::INFINITY => 2u8.encode(bytes),
+            num => {
+                0u8.encode(bytes);
+                // Adding zero turns `-0` into `0`, so the equal numbers have the same bytes
+                bytes.extend((num + 0.0).to_bits().to_le_bytes());
+            }
+        }
+    }
+}
+
+impl Decode for f32 {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(bytes)? {
+            0 => Ok(Self::from_bits(u32::from_le_bytes(take_array(bytes)?))),
+            1 => Ok(Self::NEG_INFINITY),
+            2 => Ok(Self::INFINITY),
+            tag => invalid_tag("f32", tag),
+        }
+    }
+}
+
+impl Encode for str {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.len().encode(bytes);
+        bytes.extend(self.as_bytes());
+    }
+}
+
+impl Encode for String {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.as_str().encode(bytes);
+    }
+}
+
+impl Decode for String {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        let len = usize::decode(bytes)?;
+        let string = take(bytes, len)?;
+
+        Self::from_utf8(string.to_vec()).map_err(|_| DecodeError::InvalidUtf8)
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.len().encode(bytes);
+        for item in self {
+            item.encode(bytes);
+        }
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        let len = usize::decode(bytes)?;
+
+        // The length is not trusted for the capacity, a corrupted one could be huge
+        (0..len).map(|_| T::decode(bytes)).collect()
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            None => 0u8.encode(bytes),
+            Some(value) => {
+                1u8.encode(bytes);
+                value.encode(bytes);
+            }
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(bytes)? {
+            0 => Ok(None),
+            1 => Ok(Some(T::decode(bytes)?)),
+            tag => invalid_tag("Option", tag),
+        }
+    }
+}
+
+/// The entries are sorted by their keys, because the iteration order of a `HashMap` changes between runs
+impl<T: Encode> Encode for HashMap<String, T> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        let mut entries = self.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|(key, _)| *key);
+
+        entries.len().encode(bytes);
+        for (key, value) in entries {
+            key.encode(bytes);
+            value.encode(bytes);
+        }
+    }
+}
+
+impl<T: Decode> Decode for HashMap<String, T> {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        let len = usize::decode(bytes)?;
+
+        (0..len)
+            .map(|_| Ok((String::decode(bytes)?, T::decode(bytes)?)))
+            .collect()
+    }
+}
+
+impl Encode for Boundary {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Self::Open => 0u8.encode(bytes),
+            Self::Closed => 1u8.encode(bytes),
+        }
+    }
+}
+
+impl Decode for Boundary {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(bytes)? {
+            0 => Ok(Self::Open),
+            1 => Ok(Self::Closed),
+            tag => invalid_tag("Boundary", tag),
+        }
+    }
+}
+
+impl Encode for Interval {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.lo_boundary.encode(bytes);
+        self.lo.encode(bytes);
+        self.hi.encode(bytes);
+        self.hi_boundary.encode(bytes);
+    }
+}
+
+impl Decode for Interval {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        let lo_boundary = Boundary::decode(bytes)?;
+        let lo = f32::decode(bytes)?;
+        let hi = f32::decode(bytes)?;
+        let hi_boundary = Boundary::decode(bytes)?;
+
+        Self::new(lo_boundary, lo, hi, hi_boundary).map_err(|_| DecodeError::InvalidInterval)
+    }
+}
+
+impl Encode for MultiInterval {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.intervals.encode(bytes);
+    }
+}
+
+impl Decode for MultiInterval {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self::from_intervals(Vec::decode(bytes)?))
+    }
+}
+
+impl Encode for Type {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Self::Bool => 0u8.encode(bytes),
+            Self::Integer => 1u8.encode(bytes),
+            Self::Float { precision } => {
+                2u8.encode(bytes);
+                precision.encode(bytes);
+            }
+            Self::Decimal { places } => {
+                3u8.encode(bytes);
+                places.encode(bytes);
+            }
+            Self::Enum(values) => {
+                4u8.encode(bytes);
+                values.encode(bytes);
+            }
+        }
+    }
+}
+
+impl Decode for Type {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(bytes)? {
+            0 => Ok(Self::Bool),
+            1 => Ok(Self::Integer),
+            2 => Ok(Self::Float {
+                precision: f32::decode(bytes)?,
+            }),
+            3 => Ok(Self::Decimal {
+                places: u8::decode(bytes)?,
+            }),
+            4 => Ok(Self::Enum(Vec::decode(bytes)?)),
+            tag => invalid_tag("Type", tag),
+        }
+    }
+}
+
+impl Encode for Literal {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Self::Bool(bool_val) => {
+                0u8.encode(bytes);
+                bool_val.encode(bytes);
+            }
+            Self::Number(num) => {
+                1u8.encode(bytes);
+                num.encode(bytes);
+            }
+        }
+    }
+}
+
+impl Decode for Literal {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(bytes)? {
+            0 => Ok(Self::Bool(bool::decode(bytes)?)),
+            1 => Ok(Self::Number(f32::decode(bytes)?)),
+            tag => invalid_tag("Literal", tag),
+        }
+    }
+}
+
+impl Encode for BinaryOp {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        let tag: u8 = match self {
+            Self::LessThan => 0,
+            Self::GreaterThan => 1,
+            Self::LessThanEqualTo => 2,
+            Self::GreaterThanEqualTo => 3,
+            Self::Equal => 4,
+            Self::NotEqual => 5,
+        };
+        tag.encode(bytes);
+    }
+}
+
+impl Decode for BinaryOp {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(bytes)? {
+            0 => Ok(Self::LessThan),
+            1 => Ok(Self::GreaterThan),
+            2 => Ok(Self::LessThanEqualTo),
+            3 => Ok(Self::GreaterThanEqualTo),
+            4 => Ok(Self::Equal),
+            5 => Ok(Self::NotEqual),
+            tag => invalid_tag("BinaryOp", tag),
+        }
+    }
+}
+
+impl Encode for Variable {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.var_name.encode(bytes);
+        self.var_type.encode(bytes);
+        self.domain.encode(bytes);
+        self.default.encode(bytes);
+    }
+}
+
+impl Decode for Variable {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            var_name: String::decode(bytes)?,
+            var_type: Type::decode(bytes)?,
+            domain: Option::decode(bytes)?,
+            default: Option::decode(bytes)?,
+        })
+    }
+}
+
+impl Encode for Condition {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Self::Bool(BoolCondition {
+                var_name,
+                should_equal_to,
+            }) => {
+                0u8.encode(bytes);
+                var_name.encode(bytes);
+                should_equal_to.encode(bytes);
+            }
+            Self::Interval(IntervalCondition { var_name, interval }) => {
+                1u8.encode(bytes);
+                var_name.encode(bytes);
+                interval.encode(bytes);
+            }
+            Self::Enum(EnumCondition {
+                var_name,
+                values,
+                should_be_in,
+            }) => {
+                2u8.encode(bytes);
+                var_name.encode(bytes);
+                values.encode(bytes);
+                should_be_in.encode(bytes);
+            }
+            Self::Relation(RelationCondition {
+                left,
+                binary_op,
+                right,
+            }) => {
+                3u8.encode(bytes);
+                left.encode(bytes);
+                binary_op.encode(bytes);
+                right.encode(bytes);
+            }
+        }
+    }
+}
+
+impl Decode for Condition {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(bytes)? {
+            0 => Ok(Self::Bool(BoolCondition {
+                var_name: String::decode(bytes)?,
+                should_equal_to: bool::decode(bytes)?,
+            })),
+            1 => Ok(Self::Interval(IntervalCondition {
+                var_name: String::decode(bytes)?,
+                interval: MultiInterval::decode(bytes)?,
+            })),
+            2 => Ok(Self::Enum(EnumCondition {
+                var_name: String::decode(bytes)?,
+                values: Vec::decode(bytes)?,
+                should_be_in: bool::decode(bytes)?,
+            })),
+            3 => Ok(Self::Relation(RelationCondition {
+                left: String::decode(bytes)?,
+                binary_op: BinaryOp::decode(bytes)?,
+                right: String::decode(bytes)?,
+            })),
+            tag => invalid_tag("Condition", tag),
+        }
+    }
+}
+
+impl Encode for Predicate {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Self::Negated(pred) => {
+                0u8.encode(bytes);
+                pred.encode(bytes);
+            }
+            Self::Expression(cond) => {
+                1u8.encode(bytes);
+                cond.encode(bytes);
+            }
+            Self::Group {
+                left,
+                right,
+                operator,
+            } => {
+                match operator {
+                    BoolOp::And => 2u8.encode(bytes),
+                    BoolOp::Or => 3u8.encode(bytes),
+                }
+                left.encode(bytes);
+                right.encode(bytes);
+            }
+        }
+    }
+}
+
+impl Decode for Predicate {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        let group = |bytes: &mut &[u8], operator| {
+            Ok(Self::Group {
+                left: Box::new(Self::decode(bytes)?),
+                right: Box::new(Self::decode(bytes)?),
+                operator,
+            })
+        };
+
+        match u8::decode(bytes)? {
+            0 => Ok(Self::Negated(Box::new(Self::decode(bytes)?))),
+            1 => Ok(Self::Expression(Condition::decode(bytes)?)),
+            2 => group(bytes, BoolOp::And),
+            3 => group(bytes, BoolOp::Or),
+            tag => invalid_tag("Predicate", tag),
+        }
+    }
+}
+
+impl Encode for Feature {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.variables.encode(bytes);
+        self.predicates.encode(bytes);
+    }
+}
+
+impl Decode for Feature {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            variables: Vec::decode(bytes)?,
+            predicates: Vec::decode(bytes)?,
+            // The spans are only meaningful with the source, which is not encoded
+            spans: None,
+        })
+    }
+}
+
+impl Encode for BoolDTO {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self.expression {
+            BoolExpression::IsTrue => 0u8.encode(bytes),
+            BoolExpression::IsFalse => 1u8.encode(bytes),
+        }
+        self.bool_val.encode(bytes);
+        self.is_constant.encode(bytes);
+    }
+}
+
+impl Decode for BoolDTO {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        let expression = match u8::decode(bytes)? {
+            0 => BoolExpression::IsTrue,
+            1 => BoolExpression::IsFalse,
+            tag => return invalid_tag("BoolExpression", tag),
+        };
+
+        Ok(Self {
+            expression,
+            bool_val: bool::decode(bytes)?,
+            is_constant: bool::decode(bytes)?,
+        })
+    }
+}
+
+impl Encode for IntervalDTO {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.interval.encode(bytes);
+        self.precision.encode(bytes);
+        self.is_constant.encode(bytes);
+        self.is_integer.encode(bytes);
+    }
+}
+
+impl Decode for IntervalDTO {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            interval: MultiInterval::decode(bytes)?,
+            precision: f32::decode(bytes)?,
+            is_constant: bool::decode(bytes)?,
+            is_integer: bool::decode(bytes)?,
+        })
+    }
+}
+
+impl Encode for EnumDTO {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.values.encode(bytes);
+        self.other_values.encode(bytes);
+    }
+}
+
+impl Decode for EnumDTO {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            values: Vec::decode(bytes)?,
+            other_values: Vec::decode(bytes)?,
+        })
+    }
+}
+
+impl Encode for Input {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Self::Bool(dto) => {
+                0u8.encode(bytes);
+                dto.encode(bytes);
+            }
+            Self::Interval(dto) => {
+                1u8.encode(bytes);
+                dto.encode(bytes);
+            }
+            Self::Enum(dto) => {
+                2u8.encode(bytes);
+                dto.encode(bytes);
+            }
+            Self::Any => 3u8.encode(bytes),
+        }
+    }
+}
+
+impl Decode for Input {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(bytes)? {
+            0 => Ok(Self::Bool(BoolDTO::decode(bytes)?)),
+            1 => Ok(Self::Interval(IntervalDTO::decode(bytes)?)),
+            2 => Ok(Self::Enum(EnumDTO::decode(bytes)?)),
+            3 => Ok(Self::Any),
+            tag => invalid_tag("Input", tag),
+        }
+    }
+}
+
+impl Encode for NTupleInput {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.inputs.encode(bytes);
+    }
+}
+
+impl Decode for NTupleInput {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            inputs: HashMap::decode(bytes)?,
+        })
+    }
+}
+
+impl<T: Encode + Intersectable> Encode for Output<T> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Self::MissingVariable => 0u8.encode(bytes),
+            Self::Bool(bool_val) => {
+                1u8.encode(bytes);
+                bool_val.encode(bytes);
+            }
+            Self::Interval(interval) => {
+                2u8.encode(bytes);
+                interval.encode(bytes);
+            }
+            Self::Enum(value) => {
+                3u8.encode(bytes);
+                value.encode(bytes);
+            }
+        }
+    }
+}
+
+impl<T: Decode + Intersectable> Decode for Output<T> {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(bytes)? {
+            0 => Ok(Self::MissingVariable),
+            1 => Ok(Self::Bool(bool::decode(bytes)?)),
+            2 => Ok(Self::Interval(T::decode(bytes)?)),
+            3 => Ok(Self::Enum(String::decode(bytes)?)),
+            tag => invalid_tag("Output", tag),
+        }
+    }
+}
+
+impl Encode for NTupleOutput {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.outputs.encode(bytes);
+    }
+}
+
+impl Decode for NTupleOutput {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            outputs: HashMap::decode(bytes)?,
+        })
+    }
+}
+
+impl Encode for Value {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Self::Number(num) => {
+                0u8.encode(bytes);
+                num.encode(bytes);
+            }
+            Self::Bool(bool_val) => {
+                1u8.encode(bytes);
+                bool_val.encode(bytes);
+            }
+            Self::Enum(value) => {
+                2u8.encode(bytes);
+                value.encode(bytes);
+            }
+        }
+    }
+}
+
+impl Decode for Value {
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(bytes)? {
+            0 => Ok(Self::Number(f32::decode(bytes)?)),
+            1 => Ok(Self::Bool(bool::decode(bytes)?)),
+            2 => Ok(Self::Enum(String::decode(bytes)?)),
+            tag => invalid_tag("Value", tag),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::{from_bytes, to_bytes, DecodeError};
+    use crate::{
+        dto::{NTupleInput, Value},
+        interval::{test::multiint, MultiInterval},
+        ir::{ir_to_ntuple::ir_to_ntuple, Feature},
+        parser::parse_gpt_to_ir,
+    };
+
+    const INPUT: &str = r#"
+        var x: int in [0, 100] = 5
+        var y: num(0.1)
+        var price: decimal(2)
+        var flag: bool = true
+        var status: enum {active, closed}
+
+        if(x < 10 && !(y in (-Inf, 0.5]) || flag == false)
+        if(status not in {closed} && price >= 9.99)
+    "#;
+
+    fn feature() -> Feature {
+        let (_, mut features) = parse_gpt_to_ir(INPUT).unwrap();
+        features.remove(0)
+    }
+
+    #[test]
+    fn test_feature_round_trip() {
+        let feature = feature();
+        let decoded = from_bytes::<Feature>(&to_bytes(&feature)).unwrap();
+
+        assert_eq!(decoded.variables, feature.variables);
+        assert_eq!(decoded.predicates, feature.predicates);
+    }
+
+    #[test]
+    fn test_deterministic() {
+        assert_eq!(to_bytes(&feature()), to_bytes(&feature()));
+
+        // The tuples are in HashMaps, which are iterated in a different order for each instance
+        let encode_ntuples = || {
+            ir_to_ntuple(&feature())
+                .0
+                .iter()
+                .map(to_bytes)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(encode_ntuples(), encode_ntuples());
+    }
+
+    #[test]
+    fn test_ntuple_round_trip() {
+        for ntuple in ir_to_ntuple(&feature()).0 {
+            assert_eq!(from_bytes::<NTupleInput>(&to_bytes(&ntuple)), Ok(ntuple));
+        }
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("(-Inf, Inf)")]
+    #[case("(-Inf, -5) [0, 0] (0.5, 10] [20, Inf)")]
+    fn test_multiinterval_round_trip(#[case] interval: MultiInterval) {
+        assert_eq!(from_bytes(&to_bytes(&interval)), Ok(interval));
+    }
+
+    #[test]
+    fn test_negative_zero() {
+        assert_eq!(
+            to_bytes(&Value::Number(-0.0)),
+            to_bytes(&Value::Number(0.0))
+        );
+        assert_eq!(
+            to_bytes(&multiint("[-0, 1]")),
+            to_bytes(&multiint("[0, 1]"))
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let bytes = to_bytes(&feature());
+
+        assert_eq!(
+            from_bytes::<Feature>(&bytes[..bytes.len() - 1]).err(),
+            Some(DecodeError::UnexpectedEnd)
+        );
+        assert_eq!(
+            from_bytes::<Value>(&[7]),
+            Err(DecodeError::InvalidTag {
+                type_name: "Value",
+                tag: 7
+            })
+        );
+        assert_eq!(
+            from_bytes::<bool>(&[1, 0]),
+            Err(DecodeError::TrailingBytes(1))
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use crate::interval::{Boundary, Interval, IntervalError, MultiInterval};
+use crate::{
+    interval::{Boundary, Interval, MultiInterval},
+    util::grid_value,
+};
 
 pub trait Bva
 where
@@ -63,7 +66,7 @@ impl Bva for Interval {
 
         match Self::new_closed(lo, hi) {
             Ok(in_interval) => MultiInterval::from_interval(in_interval),
-            Err(IntervalError::LoIsGreaterThanHi) => MultiInterval {
+            Err(_) => MultiInterval {
                 intervals: Vec::new(),
             },
         }
@@ -187,7 +190,7 @@ impl Bva for Interval {
 
         match Self::new_closed(lo, hi) {
             Ok(inin) => MultiInterval::from_interval(inin),
-            Err(IntervalError::LoIsGreaterThanHi) => MultiInterval {
+            Err(_) => MultiInterval {
                 intervals: Vec::new(),
             },
         }
@@ -268,12 +271,51 @@ impl Bva for MultiInterval {
     }
 }
 
+/// The largest whole number until which every whole number can be represented by an f32
+const MAX_EXACT_STEPS: f32 = 16_777_216.0;
+
+/// Every multiple of the precision inside the interval, in ascending order, if there are at most `limit` of them.
+///
+/// Small domains can be tested exhaustively, instead of only at their boundaries.
+/// Example: `[0, 5]` with the precision of `1` is `[0, 1, 2, 3, 4, 5]`.
+/// Returns `None` if there are more than `limit` values, or infinitely many, then the boundary values should be used instead.
+/// It's `None` as well if the values are more than `2^24` steps away from `0`, because an f32 can't count the steps exactly there,
+/// so neighbouring values would become duplicates.
+pub fn enumerate(interval: &Interval, precision: f32, limit: usize) -> Option<Vec<f32>> {
+    if precision <= 0.0 || interval.width().is_infinite() {
+        return None;
+    }
+
+    let grid = interval.quantize(precision);
+    if grid.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let lo_steps = (grid.lo / precision).round();
+    let hi_steps = (grid.hi / precision).round();
+    if lo_steps.abs().max(hi_steps.abs()) > MAX_EXACT_STEPS {
+        return None;
+    }
+
+    // Checking the count before collecting, so a huge interval doesn't allocate
+    if hi_steps - lo_steps + 1.0 > limit as f32 {
+        return None;
+    }
+
+    let count = (hi_steps - lo_steps) as usize + 1;
+    Some(
+        (0..count)
+            .map(|step| grid_value(lo_steps + step as f32, precision))
+            .collect(),
+    )
+}
+
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
     use rstest::rstest;
 
-    use super::Bva;
+    use super::{enumerate, Bva};
     use crate::interval::{Interval, MultiInterval};
 
     #[rstest]
@@ -337,6 +379,29 @@ mod test {
         assert_eq!(input.on(precision), expected);
     }
 
+    #[rstest]
+    #[case("[0, 5]", 1.0, 10, Some(vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]))]
+    #[case("(0, 5)", 1.0, 10, Some(vec![1.0, 2.0, 3.0, 4.0]))]
+    #[case("[0, 5]", 1.0, 6, Some(vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]))]
+    #[case("[0, 5]", 1.0, 5, None)]
+    #[case("[0.1, 0.4)", 0.1, 10, Some(vec![0.1, 0.2, 0.3]))]
+    #[case("[-1.5, 1.5]", 1.0, 10, Some(vec![-1.0, 0.0, 1.0]))]
+    #[case("(0.2, 0.8)", 1.0, 10, Some(vec![]))]
+    #[case("[0, Inf)", 1.0, 10, None)]
+    #[case("[0, 1000000000]", 1.0, 10, None)]
+    #[case("[0, 5]", 0.0, 10, None)]
+    #[case("[16777206, 16777216]", 1.0, 20, Some((16_777_206..=16_777_216).map(|num| num as f32).collect()))]
+    #[case("[16777216, 16777226]", 1.0, 20, None)]
+    #[case("[-16777226, -16777216]", 1.0, 20, None)]
+    fn test_enumerate(
+        #[case] interval: Interval,
+        #[case] precision: f32,
+        #[case] limit: usize,
+        #[case] expected: Option<Vec<f32>>,
+    ) {
+        assert_eq!(enumerate(&interval, precision, limit), expected);
+    }
+
     // TODO: Test for interval inin
     // TODO: Test for interval off
     // TODO: Test for interval out
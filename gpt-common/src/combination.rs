@@ -0,0 +1,290 @@
+use crate::{
+    dto::{Input, NTupleInput},
+    ir::{ir_to_ntuple::ir_to_ntuples_with_relations, Feature, RelationCondition},
+    test_case_generator::satisfies_relations,
+    util::UniquesVec,
+};
+
+/// The possible values of each variable which appears in at least one of the tuples, in the order of the feature's variables.
+fn values_per_variable(feature: &Feature, tuples: &[NTupleInput]) -> Vec<(String, Vec<Input>)> {
+    feature
+        .variables
+        .iter()
+        .map(|variable| {
+            let values = tuples
+                .iter()
+                .filter_map(|ntuple| ntuple.inputs.get(&variable.var_name).cloned())
+                // A wildcard isn't a value on its own, the variable can take any of the other values instead
+                .filter(|input| *input != Input::Any)
+                .collect::<Vec<_>>()
+                .uniques();
+
+            (variable.var_name.clone(), values)
+        })
+        .filter(|(_, values)| !values.is_empty())
+        .collect()
+}
+
+/// A row of value indices, `None` means that the value for the variable doesn't matter yet.
+type Row = Vec<Option<usize>>;
+
+/// The pairs of (earlier variable, its value, value of the current variable) which are not covered by any row yet.
+fn uncovered_pairs(value_counts: &[usize], current: usize) -> Vec<(usize, usize, usize)> {
+    (0..current)
+        .flat_map(|earlier| {
+            (0..value_counts[earlier]).flat_map(move |earlier_value| {
+                (0..value_counts[current])
+                    .map(move |current_value| (earlier, earlier_value, current_value))
+            })
+        })
+        .collect()
+}
+
+fn covers(
+    row: &Row,
+    current: usize,
+    (earlier, earlier_value, current_value): (usize, usize, usize),
+) -> bool {
+    row[earlier] == Some(earlier_value) && row[current] == Some(current_value)
+}
+
+/// In-parameter-order (IPO) pairwise combination of the value indices.
+fn in_parameter_order(value_counts: &[usize]) -> Vec<Row> {
+    let variable_count = value_counts.len();
+
+    if variable_count == 0 {
+        return Vec::new();
+    }
+
+    let mut rows: Vec<Row> = (0..value_counts[0])
+        .map(|value| {
+            let mut row = vec![None; variable_count];
+            row[0] = Some(value);
+            row
+        })
+        .collect();
+
+    for current in 1..variable_count {
+        let mut uncovered = uncovered_pairs(value_counts, current);
+
+        // Horizontal growth: extend every row with the value which covers the most uncovered pairs
+        for row in &mut rows {
+            let best_value = (0..value_counts[current])
+                .max_by_key(|value| {
+                    let mut candidate = row.clone();
+                    candidate[current] = Some(*value);
+                    // Reversing, so on a tie the smaller value index wins
+                    (
+                        uncovered
+                            .iter()
+                            .filter(|pair| covers(&candidate, current, **pair))
+                            .count(),
+                        std::cmp::Reverse(*value),
+                    )
+                })
+                .expect("Variables with no values are filtered out");
+
+            row[current] = Some(best_value);
+            uncovered.retain(|pair| !covers(row, current, *pair));
+        }
+
+        // Vertical growth: put the remaining pairs into rows where the earlier variable doesn't matter yet, or into new rows
+        for (earlier, earlier_value, current_value) in uncovered {
+            let existing_row = rows
+                .iter_mut()
+                .find(|row| row[earlier].is_none() && row[current] == Some(current_value));
+
+            match existing_row {
+                Some(row) => row[earlier] = Some(earlier_value),
+                None => {
+                    let mut row = vec![None; variable_count];
+                    row[earlier] = Some(earlier_value);
+                    row[current] = Some(current_value);
+                    rows.push(row);
+                }
+            }
+        }
+    }
+
+    rows
+}
+
+/// Every input of the tuple accepts the value of the row, so the row is one of the test cases of the tuple.
+fn is_inside(row: &NTupleInput, ntuple: &NTupleInput) -> bool {
+    ntuple.inputs.iter().all(|(var_name, input)| {
+        *input == Input::Any
+            || row
+                .inputs
+                .get(var_name)
+                .is_some_and(|row_input| row_input.is_subset_of(input))
+    })
+}
+
+/// Whether the row satisfies the relations of at least one of the tuples it's inside of, like `start <= end`.
+/// A row which isn't inside any of the tuples isn't restricted by their relations.
+fn honors_relations(row: &NTupleInput, ntuples: &[(NTupleInput, Vec<RelationCondition>)]) -> bool {
+    let mut containing = ntuples
+        .iter()
+        .filter(|(ntuple, _)| is_inside(row, ntuple))
+        .peekable();
+
+    containing.peek().is_none()
+        || containing.any(|(_, relations)| satisfies_relations(row, relations))
+}
+
+/// Pairwise (all-pairs) combination of the per-variable values found in the tuples.
+///
+/// Every pair of values of any two variables appears in at least one of the returned tuples,
+/// which results in far fewer tuples than the cartesian product.
+/// Uses the in-parameter-order (IPO) greedy algorithm, the variables are taken in the order they are declared in the feature.
+/// Variables which don't appear in any of the tuples are not in the result either.
+/// The combinations violating the relations of the feature's tuples, like `start <= end`, are dropped,
+/// so some of the pairs might not be covered in that case.
+pub fn pairwise(feature: &Feature, tuples: &[NTupleInput]) -> Vec<NTupleInput> {
+    let values = values_per_variable(feature, tuples);
+    let ntuples_with_relations = ir_to_ntuples_with_relations(feature);
+    let value_counts = values
+        .iter()
+        .map(|(_, values)| values.len())
+        .collect::<Vec<_>>();
+
+    in_parameter_order(&value_counts)
+        .into_iter()
+        .map(|row| NTupleInput {
+            inputs: row
+                .into_iter()
+                .zip(values.iter())
+                .map(|(value_index, (var_name, values))| {
+                    // If the value doesn't matter, the first one is as good as any
+                    (var_name.clone(), values[value_index.unwrap_or(0)].clone())
+                })
+                .collect(),
+        })
+        .filter(|row| honors_relations(row, &ntuples_with_relations))
+        .collect::<Vec<_>>()
+        .uniques()
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use pretty_assertions::assert_eq;
+
+    use super::{in_parameter_order, pairwise};
+    use crate::{
+        dto::{
+            tests::{bool_input, create_ntuple_input, interval_input},
+            Input, NTupleInput,
+        },
+        ir::ir_to_ntuple::ir_to_ntuple,
+        parser::parse_gpt_to_ir,
+    };
+
+    #[test]
+    fn test_in_parameter_order_covers_all_pairs() {
+        let value_counts = [3, 3, 2, 4];
+        let rows = in_parameter_order(&value_counts);
+
+        for (a, b) in (0..value_counts.len()).tuple_combinations() {
+            for (a_value, b_value) in (0..value_counts[a]).cartesian_product(0..value_counts[b]) {
+                assert!(
+                    rows.iter()
+                        .any(|row| row[a] == Some(a_value) && row[b] == Some(b_value)),
+                    "Pair ({a}={a_value}, {b}={b_value}) is not covered by {rows:?}"
+                );
+            }
+        }
+        assert!(rows.len() < value_counts.iter().product());
+    }
+
+    #[test]
+    fn test_pairwise() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var x: int
+            var y: int
+            var flag: bool
+
+            if(x < 0)
+        "#,
+        )
+        .unwrap();
+
+        let xs = ["[0, 10)", "[10, 20)", "[20, 30]"];
+        let ys = ["(-Inf, 0)", "[0, Inf)"];
+        let tuples = vec![
+            create_ntuple_input(vec![
+                ("x", interval_input(xs[0])),
+                ("y", interval_input(ys[0])),
+                ("flag", bool_input(true)),
+            ]),
+            create_ntuple_input(vec![
+                ("x", interval_input(xs[1])),
+                ("y", interval_input(ys[1])),
+                ("flag", bool_input(false)),
+            ]),
+            create_ntuple_input(vec![
+                ("x", interval_input(xs[2])),
+                ("y", Input::Any),
+                ("flag", Input::Any),
+            ]),
+        ];
+
+        let result = pairwise(&features[0], &tuples);
+
+        let values = [
+            (
+                "x",
+                xs.iter().map(|x| interval_input(x)).collect::<Vec<_>>(),
+            ),
+            ("y", ys.iter().map(|y| interval_input(y)).collect()),
+            ("flag", vec![bool_input(true), bool_input(false)]),
+        ];
+        for ((a, a_values), (b, b_values)) in values.iter().tuple_combinations() {
+            for (a_value, b_value) in a_values.iter().cartesian_product(b_values.iter()) {
+                assert!(
+                    result.iter().any(
+                        |ntuple| ntuple.inputs[*a] == *a_value && ntuple.inputs[*b] == *b_value
+                    ),
+                    "Pair ({a}={a_value:?}, {b}={b_value:?}) is not covered"
+                );
+            }
+        }
+        assert_eq!(result.len(), 6);
+    }
+
+    #[test]
+    fn test_pairwise_honors_relations() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var start: int
+            var end: int
+
+            if(start in [0, 4] && end in [0, 4])
+            if(start <= end && start in [5, 10] && end in [0, 10])
+        "#,
+        )
+        .unwrap();
+        let feature = &features[0];
+        let interval = |ntuple: &NTupleInput, var_name: &str| match &ntuple.inputs[var_name] {
+            Input::Interval(dto) => dto.interval.to_string(),
+            input => panic!("{var_name} should be an interval, but it is {input:?}"),
+        };
+
+        let result = pairwise(feature, &ir_to_ntuple(feature).0)
+            .iter()
+            .map(|ntuple| (interval(ntuple, "start"), interval(ntuple, "end")))
+            .sorted()
+            .collect::<Vec<_>>();
+
+        // `start ∈ [5, 10]` and `end ∈ [0, 4]` is only inside the second tuple, but it can't satisfy `start <= end`
+        assert_eq!(
+            result,
+            vec![
+                ("[0, 4]".to_owned(), "[0, 10]".to_owned()),
+                ("[0, 4]".to_owned(), "[0, 4]".to_owned()),
+                ("[5, 10]".to_owned(), "[0, 10]".to_owned()),
+            ]
+        );
+    }
+}
@@ -5,7 +5,10 @@ use std::{
 
 use serde::Serialize;
 
-use crate::interval::{Intersectable, Interval, MultiInterval};
+use crate::{
+    interval::{Boundary, Intersectable, Interval, MultiInterval, Unionable},
+    util::{snap_to_precision, UniquesVec},
+};
 
 #[derive(Clone, PartialEq, Eq, Debug, Copy)]
 pub enum BoolExpression {
@@ -39,12 +42,245 @@ pub struct IntervalDTO {
     pub interval: MultiInterval,
     pub precision: f32,
     pub is_constant: bool,
+    /// The variable can only have whole number values
+    pub is_integer: bool,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct EnumDTO {
+    /// The values satisfying the condition, in the order they were declared
+    pub values: Vec<String>,
+    /// The declared values which don't satisfy the condition, these are used for the negative test cases
+    pub other_values: Vec<String>,
+}
+
+impl EnumDTO {
+    /// The DTO satisfied by only `value`, every other value of the DTO is moved to `other_values`.
+    pub fn pinned(&self, value: &str) -> Self {
+        Self {
+            values: vec![value.to_owned()],
+            other_values: self
+                .values
+                .iter()
+                .chain(&self.other_values)
+                .filter(|other| *other != value)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+/// The reason why two `IntervalDTO`s of the same variable can't be merged
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ConversionError {
+    /// The DTOs have different precisions, so they can't belong to the same variable
+    PrecisionMismatch { this: f32, that: f32 },
+    /// The intersection of the intervals is empty, so no value satisfies both DTOs
+    Contradiction,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BoundaryPointKind {
+    /// The boundary value itself
+    On,
+    /// The closest value to a boundary, which is inside of the interval
+    Inside,
+    /// The closest value to a boundary, which is outside of the interval
+    Outside,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BoundaryTestPoint {
+    pub value: f32,
+    pub kind: BoundaryPointKind,
+}
+
+/// From `2^24` every `f32` is a whole number, but the neighbouring whole numbers can't all be represented
+const MAX_EXACT_INTEGER: f32 = 16_777_216.0;
+
+impl IntervalDTO {
+    /// The interval with the smallest and largest whole numbers as closed boundaries for integer variables.
+    ///
+    /// The boundaries are calculated with integer arithmetic, so the value just below `10` in `x < 10` is exactly `9`.
+    /// The infinite boundaries and the ones beyond [`MAX_EXACT_INTEGER`] are kept as they are, they are already whole numbers.
+    /// Example: `(0.5, 7.5)` becomes `[1, 7]`, and `(0, 10)` becomes `[1, 9]` for an integer variable.
+    pub fn rounded_interval(&self) -> MultiInterval {
+        if !self.is_integer {
+            return self.interval.clone();
+        }
+
+        let rounded_intervals = self
+            .interval
+            .intervals
+            .iter()
+            .filter_map(|interval| {
+                let (lo_boundary, lo) = if interval.lo.abs() >= MAX_EXACT_INTEGER {
+                    (interval.lo_boundary, interval.lo)
+                } else {
+                    let smallest = interval.lo.ceil() as i64;
+                    let is_excluded =
+                        interval.lo_boundary == Boundary::Open && interval.lo.fract() == 0.0;
+                    let smallest = if is_excluded { smallest + 1 } else { smallest };
+
+                    (Boundary::Closed, smallest as f32)
+                };
+                let (hi, hi_boundary) = if interval.hi.abs() >= MAX_EXACT_INTEGER {
+                    (interval.hi, interval.hi_boundary)
+                } else {
+                    let largest = interval.hi.floor() as i64;
+                    let is_excluded =
+                        interval.hi_boundary == Boundary::Open && interval.hi.fract() == 0.0;
+                    let largest = if is_excluded { largest - 1 } else { largest };
+
+                    (largest as f32, Boundary::Closed)
+                };
+
+                // There might be no whole numbers in the interval, like (0.2, 0.8)
+                Interval::new(lo_boundary, lo, hi, hi_boundary).ok()
+            })
+            .collect();
+
+        MultiInterval::from_intervals(rounded_intervals)
+    }
+
+    /// Merges two DTOs of the same variable, when the conditions containing them are conjoined.
+    ///
+    /// The result is the intersection of the intervals, and it's constant if any of the DTOs is constant.
+    /// Example: `[0, 10]` merged with `(5, 20]` is `(5, 10]`, while merging it with `(10, 20]` is a `Contradiction`.
+    pub fn merge(&self, other: &Self) -> Result<Self, ConversionError> {
+        if self.precision != other.precision {
+            return Err(ConversionError::PrecisionMismatch {
+                this: self.precision,
+                that: other.precision,
+            });
+        }
+
+        let interval = self
+            .interval
+            .intersect(&other.interval)
+            .ok_or(ConversionError::Contradiction)?;
+
+        Ok(Self {
+            interval,
+            precision: self.precision,
+            is_constant: self.is_constant || other.is_constant,
+            is_integer: self.is_integer || other.is_integer,
+        })
+    }
+
+    fn contains_point(&self, point: f32) -> bool {
+        self.interval
+            .intervals
+            .iter()
+            .any(|interval| interval.contains_point(point))
+    }
+
+    /// Snaps the value to the precision grid, while keeping it inside (or outside) the interval, just like the original value was.
+    ///
+    /// If the closest grid point would escape, the neighbouring grid points are tried,
+    /// and if none of them are suitable, the value is kept as it is.
+    /// Example: `0.5` in `(0,1)` with the precision of `1` stays `0.5`, because both `0` and `1` are outside.
+    fn snap_point(&self, value: f32) -> f32 {
+        let is_inside = self.contains_point(value);
+        let snapped = snap_to_precision(value, self.precision);
+
+        [
+            snapped,
+            snap_to_precision(snapped - self.precision, self.precision),
+            snap_to_precision(snapped + self.precision, self.precision),
+        ]
+        .into_iter()
+        .filter(|candidate| self.contains_point(*candidate) == is_inside)
+        .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()))
+        .unwrap_or(value)
+    }
+
+    /// The test points around every finite boundary of the interval, using the precision of the DTO.
+    ///
+    /// Every boundary has an On point, which is the boundary value itself.
+    /// A closed boundary's other point is the closest one outside, an open boundary's is the closest one inside.
+    ///
+    /// Example: `[0,10]` with a precision of `0.5` has the Outside points `-0.5` and `10.5`,
+    /// while `(0,10)` has the Inside points `0.5` and `9.5`.
+    pub fn boundary_test_points(&self) -> Vec<BoundaryTestPoint> {
+        let mut points = Vec::new();
+
+        for interval in &self.interval.intervals {
+            let boundaries = [
+                (interval.lo, interval.lo_boundary, -self.precision),
+                (interval.hi, interval.hi_boundary, self.precision),
+            ];
+
+            for (value, boundary, step_outwards) in boundaries {
+                if value.is_infinite() {
+                    continue;
+                }
+
+                let neighbour = match boundary {
+                    Boundary::Closed => BoundaryTestPoint {
+                        value: self.snap_point(value + step_outwards),
+                        kind: BoundaryPointKind::Outside,
+                    },
+                    Boundary::Open => BoundaryTestPoint {
+                        value: self.snap_point(value - step_outwards),
+                        kind: BoundaryPointKind::Inside,
+                    },
+                };
+
+                // In a narrow interval, or between touching intervals the neighbour can be on the other side as well
+                let is_neighbour_valid = self.contains_point(neighbour.value)
+                    == (neighbour.kind == BoundaryPointKind::Inside);
+
+                points.push(BoundaryTestPoint {
+                    value,
+                    kind: BoundaryPointKind::On,
+                });
+                if is_neighbour_valid {
+                    points.push(neighbour);
+                }
+            }
+        }
+
+        points.uniques()
+    }
+}
+
+/// A concrete value which can be assigned to a variable
+#[derive(PartialEq, Clone, Debug)]
+pub enum Value {
+    Number(f32),
+    Bool(bool),
+    Enum(String),
+}
+
+impl Value {
+    /// The number of a `Number` value, `None` for the other kinds
+    pub const fn as_number(&self) -> Option<f32> {
+        match self {
+            Self::Number(num) => Some(*num),
+            Self::Bool(_) | Self::Enum(_) => None,
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(num) => write!(f, "{num}"),
+            Self::Bool(bool_val) => write!(f, "{bool_val}"),
+            Self::Enum(value) => write!(f, "{value}"),
+        }
+    }
 }
 
 #[derive(PartialEq, Clone, Debug)]
 pub enum Input {
     Bool(BoolDTO),
     Interval(IntervalDTO),
+    Enum(EnumDTO),
+    /// The variable is not mentioned by the predicate, so it can take any value.
+    /// This is different from an interval covering the whole domain, which was explicitly stated.
+    Any,
 }
 
 #[derive(PartialEq, Clone, Debug)]
@@ -52,6 +288,275 @@ pub struct NTupleInput {
     pub inputs: HashMap<String, Input>,
 }
 
+impl Input {
+    /// A value satisfying the input, the representative value of the first interval for interval inputs,
+    /// and the first value for enum inputs.
+    ///
+    /// The intervals of integer inputs are rounded first, so the value is a whole number.
+    /// Returns `None` if there is no such value, because the interval is empty,
+    /// or if there is no single representative value, because the input is `Any`.
+    pub fn pick_value(&self, precision: f32) -> Option<Value> {
+        match self {
+            Self::Any => None,
+            Self::Bool(BoolDTO { bool_val, .. }) => Some(Value::Bool(*bool_val)),
+            Self::Enum(EnumDTO { values, .. }) => values.first().cloned().map(Value::Enum),
+            Self::Interval(interval_dto) => interval_dto
+                .rounded_interval()
+                .intervals
+                .iter()
+                .find_map(|interval| interval.representative_value(precision))
+                .map(Value::Number),
+        }
+    }
+
+    /// Every value accepted by `self` is accepted by `other` as well.
+    pub(crate) fn is_subset_of(&self, other: &Self) -> bool {
+        match (self, other) {
+            (_, Self::Any) => true,
+            (Self::Bool(this), Self::Bool(that)) => this == that,
+            (Self::Interval(this), Self::Interval(that)) => {
+                this.is_constant == that.is_constant && this.interval.is_subset_of(&that.interval)
+            }
+            (Self::Enum(this), Self::Enum(that)) => {
+                this.values.iter().all(|value| that.values.contains(value))
+            }
+            (_, _) => false,
+        }
+    }
+}
+
+impl NTupleInput {
+    /// At least one of the variables has an empty interval or no enum values, so there are no values satisfying the tuple.
+    pub fn is_unsatisfiable(&self) -> bool {
+        self.inputs.values().any(|input| match input {
+            Input::Interval(IntervalDTO { interval, .. }) => interval.is_empty(),
+            Input::Enum(EnumDTO { values, .. }) => values.is_empty(),
+            Input::Bool(_) | Input::Any => false,
+        })
+    }
+
+    /// Both tuples constrain the same variables, and every input of `self` is contained in the input of `other`.
+    pub fn is_subsumed_by(&self, other: &Self) -> bool {
+        self.inputs.len() == other.inputs.len()
+            && self.inputs.iter().all(|(var_name, input)| {
+                other
+                    .inputs
+                    .get(var_name)
+                    .is_some_and(|other_input| input.is_subset_of(other_input))
+            })
+    }
+}
+
+/// Removes the duplicate tuples, and the tuples which are subsumed by another tuple.
+///
+/// Example: `x ∈ [0, 5]` is dropped if there is an `x ∈ [0, 10]` tuple as well.
+/// Of the different tuples subsuming each other, like the same interval with different precisions, the first one is kept.
+/// The order of the remaining tuples is kept.
+pub fn dedup_ntuples(tuples: Vec<NTupleInput>) -> Vec<NTupleInput> {
+    let uniques = tuples.uniques();
+
+    uniques
+        .iter()
+        .enumerate()
+        .filter(|(i, ntuple)| {
+            !uniques.iter().enumerate().any(|(j, other)| {
+                *i != j && ntuple.is_subsumed_by(other) && (j < *i || !other.is_subsumed_by(ntuple))
+            })
+        })
+        .map(|(_, ntuple)| ntuple.clone())
+        .collect()
+}
+
+/// Merges two tuples which differ only in the interval of a single variable, if those intervals overlap or border each other.
+/// The rest of the DTO, like the precision, has to be the same.
+fn coalesce_pair(this: &NTupleInput, that: &NTupleInput) -> Option<NTupleInput> {
+    if this.inputs.len() != that.inputs.len() {
+        return None;
+    }
+
+    let mut differing = this
+        .inputs
+        .iter()
+        .filter(|(var_name, input)| that.inputs.get(*var_name) != Some(*input));
+    let Some((var_name, this_input)) = differing.next() else {
+        // Same variables with the same inputs
+        return Some(this.clone());
+    };
+    if differing.next().is_some() {
+        return None;
+    }
+
+    let (Input::Interval(this_dto), Some(Input::Interval(that_dto))) =
+        (this_input, that.inputs.get(var_name))
+    else {
+        return None;
+    };
+    if this_dto.precision != that_dto.precision
+        || this_dto.is_constant != that_dto.is_constant
+        || this_dto.is_integer != that_dto.is_integer
+    {
+        return None;
+    }
+
+    // Some of the intervals merged into one, so they overlap or border each other
+    let interval = this_dto.interval.union(&that_dto.interval);
+    if interval.intervals.len()
+        == this_dto.interval.intervals.len() + that_dto.interval.intervals.len()
+    {
+        return None;
+    }
+
+    let mut inputs = this.inputs.clone();
+    inputs.insert(
+        var_name.clone(),
+        Input::Interval(IntervalDTO {
+            interval,
+            ..this_dto.clone()
+        }),
+    );
+
+    Some(NTupleInput { inputs })
+}
+
+/// Merges the tuples which differ only in one variable, where the intervals overlap or border each other, for a compact summary.
+///
+/// Example: `x ∈ [0, 5), y = true` and `x ∈ [5, 10], y = true` become `x ∈ [0, 10], y = true`.
+/// The merging is repeated until no more tuples can be merged, the merged tuple takes the place of the first one.
+pub fn coalesce_ntuples(tuples: &[NTupleInput]) -> Vec<NTupleInput> {
+    let mut coalesced: Vec<NTupleInput> = Vec::new();
+
+    for tuple in tuples {
+        let mut current = tuple.clone();
+        let mut position = coalesced.len();
+
+        while let Some((i, merged)) = coalesced
+            .iter()
+            .enumerate()
+            .find_map(|(i, other)| coalesce_pair(other, &current).map(|merged| (i, merged)))
+        {
+            coalesced.remove(i);
+            position = position.min(i);
+            current = merged;
+        }
+
+        coalesced.insert(position.min(coalesced.len()), current);
+    }
+
+    coalesced
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(BoolDTO { bool_val, .. }) => write!(f, "{bool_val}"),
+            Self::Interval(IntervalDTO { interval, .. }) if interval.is_empty() => write!(f, "∅"),
+            Self::Interval(IntervalDTO { interval, .. }) => write!(f, "{interval}"),
+            Self::Enum(EnumDTO { values, .. }) => write!(f, "{{{}}}", values.join(", ")),
+            Self::Any => write!(f, "*"),
+        }
+    }
+}
+
+/// Prints the inputs ordered by the variable names, like `flag = true, x ∈ [0, 10)`
+impl Display for NTupleInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut var_names = self.inputs.keys().collect::<Vec<_>>();
+        var_names.sort_unstable();
+
+        for (i, var_name) in var_names.into_iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            let input = &self.inputs[var_name];
+            match input {
+                Input::Bool(_) | Input::Any => write!(f, "{var_name} = {input}")?,
+                Input::Interval(_) | Input::Enum(_) => write!(f, "{var_name} ∈ {input}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Intersectable for Input {
+    fn intersects_with(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Any, _) | (_, Self::Any) => true,
+            (Self::Bool(this), Self::Bool(that)) => this.bool_val == that.bool_val,
+            (Self::Interval(this), Self::Interval(that)) => {
+                this.interval.intersects_with(&that.interval)
+            }
+            (Self::Enum(this), Self::Enum(that)) => {
+                this.values.iter().any(|value| that.values.contains(value))
+            }
+            (_, _) => false,
+        }
+    }
+
+    fn intersect(&self, other: &Self) -> Option<Self> {
+        match (self, other) {
+            (Self::Any, that) => Some(that.clone()),
+            (this, Self::Any) => Some(this.clone()),
+            (Self::Bool(this), Self::Bool(that)) if this.bool_val == that.bool_val => {
+                Some(Self::Bool(BoolDTO {
+                    is_constant: this.is_constant || that.is_constant,
+                    ..*this
+                }))
+            }
+            // The inputs of the same variable have the same precision, so the merge can only fail with a contradiction
+            (Self::Interval(this), Self::Interval(that)) => {
+                this.merge(that).ok().map(Self::Interval)
+            }
+            (Self::Enum(this), Self::Enum(that)) => {
+                let (values, mut other_values): (Vec<_>, Vec<_>) = this
+                    .values
+                    .iter()
+                    .cloned()
+                    .partition(|value| that.values.contains(value));
+                if values.is_empty() {
+                    return None;
+                }
+                other_values.extend(this.other_values.iter().cloned());
+
+                Some(Self::Enum(EnumDTO {
+                    values,
+                    other_values,
+                }))
+            }
+            (_, _) => None,
+        }
+    }
+}
+
+impl Intersectable for NTupleInput {
+    fn intersects_with(&self, other: &Self) -> bool {
+        self.inputs.iter().all(|(var_name, input)| {
+            other
+                .inputs
+                .get(var_name)
+                .is_none_or(|other_input| input.intersects_with(other_input))
+        })
+    }
+
+    /// Intersects the inputs variable-by-variable, the variables which are only in one of the tuples are kept as they are.
+    /// Returns `None` if any of the common variables has an empty intersection, or their bool values conflict.
+    fn intersect(&self, other: &Self) -> Option<Self> {
+        let mut inputs = other.inputs.clone();
+
+        for (var_name, input) in &self.inputs {
+            let intersection = match other.inputs.get(var_name) {
+                Some(other_input) => input.intersect(other_input)?,
+                None => input.clone(),
+            };
+
+            inputs.insert(var_name.clone(), intersection);
+        }
+
+        Some(Self { inputs })
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum Output<T>
 where
@@ -60,6 +565,7 @@ where
     MissingVariable,
     Bool(bool),
     Interval(T),
+    Enum(String),
 }
 
 impl<T> Intersectable for Output<T>
@@ -70,6 +576,7 @@ where
         match (self, other) {
             (Self::Bool(this), Self::Bool(that)) => this == that,
             (Self::Interval(this), Self::Interval(that)) => this.intersects_with(that),
+            (Self::Enum(this), Self::Enum(that)) => this == that,
             (_, _) => false,
         }
     }
@@ -80,6 +587,7 @@ where
             (Self::Interval(this), Self::Interval(that)) => {
                 this.intersect(that).map(Self::Interval)
             }
+            (Self::Enum(this), Self::Enum(that)) if this == that => Some(Self::Enum(this.clone())),
             (Self::MissingVariable, Self::MissingVariable) => Some(Self::MissingVariable),
             (_, _) => None,
         }
@@ -98,11 +606,12 @@ where
             Self::MissingVariable => serializer.serialize_none(),
             Self::Bool(bool) => serializer.serialize_bool(*bool),
             Self::Interval(interval) => interval.serialize(serializer),
+            Self::Enum(value) => serializer.serialize_str(value),
         }
     }
 }
 
-#[derive(PartialEq, Clone)]
+#[derive(PartialEq, Eq, Clone)]
 pub struct NTupleOutput {
     pub outputs: HashMap<String, Output<MultiInterval>>,
 }
@@ -173,8 +682,16 @@ pub(crate) mod tests {
 
     use rstest::rstest;
 
-    use super::{Input, NTupleInput, NTupleOutput, NTupleSingleInterval, Output};
-    use crate::interval::{test::int, Intersectable, Interval, MultiInterval};
+    use super::{
+        coalesce_ntuples, dedup_ntuples, BoolDTO, BoolExpression,
+        BoundaryPointKind::{self, Inside, On, Outside},
+        BoundaryTestPoint, ConversionError, Input, IntervalDTO, NTupleInput, NTupleOutput,
+        NTupleSingleInterval, Output, Value,
+    };
+    use crate::interval::{
+        test::{int, multiint},
+        Intersectable, Interval, MultiInterval,
+    };
 
     pub fn create_ntuple_input(inputs: Vec<(&str, Input)>) -> NTupleInput {
         NTupleInput {
@@ -203,6 +720,31 @@ pub(crate) mod tests {
             .collect::<HashMap<_, _>>()
     }
 
+    pub fn interval_input(interval: &str) -> Input {
+        precise_interval_input(interval, 1.0, false)
+    }
+
+    pub fn precise_interval_input(interval: &str, precision: f32, is_integer: bool) -> Input {
+        Input::Interval(IntervalDTO {
+            interval: multiint(interval),
+            precision,
+            is_constant: false,
+            is_integer,
+        })
+    }
+
+    pub fn bool_input(bool_val: bool) -> Input {
+        Input::Bool(BoolDTO {
+            expression: if bool_val {
+                BoolExpression::IsTrue
+            } else {
+                BoolExpression::IsFalse
+            },
+            bool_val,
+            is_constant: false,
+        })
+    }
+
     #[rstest]
     #[case::same(vec![
         ("x", Output::Interval(int("[10, 20]"))),
@@ -250,4 +792,337 @@ pub(crate) mod tests {
         assert!(!create_ntuple_single_interval(left)
             .intersects_with(&create_ntuple_single_interval(right)));
     }
+
+    #[rstest]
+    #[case(bool_input(true), 1.0, Some(Value::Bool(true)))]
+    #[case(bool_input(false), 1.0, Some(Value::Bool(false)))]
+    #[case(interval_input("[0, 10]"), 1.0, Some(Value::Number(0.0)))]
+    #[case(interval_input("(0, 10]"), 0.1, Some(Value::Number(0.1)))]
+    #[case(interval_input("(-Inf, 5) [10, 20]"), 1.0, Some(Value::Number(4.0)))]
+    #[case(interval_input(""), 1.0, None)]
+    #[case(Input::Interval(IntervalDTO {
+        interval: multiint("(0.5, 7.5)"),
+        precision: 1.0,
+        is_constant: false,
+        is_integer: true,
+    }), 1.0, Some(Value::Number(1.0)))]
+    fn test_input_pick_value(
+        #[case] input: Input,
+        #[case] precision: f32,
+        #[case] expected: Option<Value>,
+    ) {
+        assert_eq!(input.pick_value(precision), expected);
+    }
+
+    #[rstest]
+    #[case::same(
+        vec![("x", interval_input("[10, 20]")), ("y", bool_input(true))],
+        vec![("x", interval_input("[10, 20]")), ("y", bool_input(true))],
+        Some(vec![("x", interval_input("[10, 20]")), ("y", bool_input(true))]),
+    )]
+    #[case::overlapping(
+        vec![("x", interval_input("[0, 15]"))],
+        vec![("x", interval_input("(10, 20] [30, 40]"))],
+        Some(vec![("x", interval_input("(10, 15]"))]),
+    )]
+    #[case::different_variables(
+        vec![("x", interval_input("[0, 100]")), ("y", bool_input(true))],
+        vec![("x", interval_input("[10, 20]")), ("z", bool_input(false))],
+        Some(vec![("x", interval_input("[10, 20]")), ("y", bool_input(true)), ("z", bool_input(false))]),
+    )]
+    #[case::empty_left(
+        vec![],
+        vec![("x", interval_input("[10, 20]"))],
+        Some(vec![("x", interval_input("[10, 20]"))]),
+    )]
+    #[case::disjoint_intervals(
+        vec![("x", interval_input("[0, 10)")), ("y", bool_input(true))],
+        vec![("x", interval_input("[10, 20]")), ("y", bool_input(true))],
+        None,
+    )]
+    #[case::conflicting_bools(
+        vec![("x", interval_input("[0, 10]")), ("y", bool_input(true))],
+        vec![("x", interval_input("[0, 10]")), ("y", bool_input(false))],
+        None,
+    )]
+    #[case::bool_and_interval(
+        vec![("x", interval_input("[0, 10]"))],
+        vec![("x", bool_input(false))],
+        None,
+    )]
+    #[case::any(
+        vec![("x", Input::Any), ("y", bool_input(true))],
+        vec![("x", interval_input("[0, 10]")), ("y", Input::Any)],
+        Some(vec![("x", interval_input("[0, 10]")), ("y", bool_input(true))]),
+    )]
+    fn test_ntuple_input_intersect(
+        #[case] left: Vec<(&str, Input)>,
+        #[case] right: Vec<(&str, Input)>,
+        #[case] expected: Option<Vec<(&str, Input)>>,
+    ) {
+        let left = create_ntuple_input(left);
+        let right = create_ntuple_input(right);
+        let expected = expected.map(create_ntuple_input);
+
+        assert_eq!(left.intersects_with(&right), expected.is_some());
+        assert_eq!(left.intersect(&right), expected);
+        assert_eq!(right.intersect(&left), expected);
+    }
+
+    #[rstest]
+    #[case::empty(vec![], vec![])]
+    #[case::duplicates(
+        vec![
+            vec![("x", interval_input("[0, 10]"))],
+            vec![("y", bool_input(true))],
+            vec![("x", interval_input("[0, 10]"))],
+        ],
+        vec![vec![("x", interval_input("[0, 10]"))], vec![("y", bool_input(true))]],
+    )]
+    #[case::subsumed(
+        vec![
+            vec![("x", interval_input("[2, 5]")), ("y", bool_input(true))],
+            vec![("x", interval_input("[0, 10]")), ("y", bool_input(true))],
+        ],
+        vec![vec![("x", interval_input("[0, 10]")), ("y", bool_input(true))]],
+    )]
+    #[case::different_bools(
+        vec![
+            vec![("x", interval_input("[2, 5]")), ("y", bool_input(false))],
+            vec![("x", interval_input("[0, 10]")), ("y", bool_input(true))],
+        ],
+        vec![
+            vec![("x", interval_input("[2, 5]")), ("y", bool_input(false))],
+            vec![("x", interval_input("[0, 10]")), ("y", bool_input(true))],
+        ],
+    )]
+    #[case::different_variables(
+        vec![
+            vec![("x", interval_input("[2, 5]"))],
+            vec![("x", interval_input("[0, 10]")), ("y", bool_input(true))],
+        ],
+        vec![
+            vec![("x", interval_input("[2, 5]"))],
+            vec![("x", interval_input("[0, 10]")), ("y", bool_input(true))],
+        ],
+    )]
+    #[case::partially_overlapping(
+        vec![vec![("x", interval_input("[0, 10]"))], vec![("x", interval_input("[5, 20]"))]],
+        vec![vec![("x", interval_input("[0, 10]"))], vec![("x", interval_input("[5, 20]"))]],
+    )]
+    #[case::mutually_subsuming(
+        vec![
+            vec![("x", interval_input("[0, 10]"))],
+            vec![("x", Input::Interval(IntervalDTO {
+                interval: multiint("[0, 10]"),
+                precision: 0.5,
+                is_constant: false,
+                is_integer: false,
+            }))],
+        ],
+        vec![vec![("x", interval_input("[0, 10]"))]],
+    )]
+    fn test_dedup_ntuples(
+        #[case] tuples: Vec<Vec<(&str, Input)>>,
+        #[case] expected: Vec<Vec<(&str, Input)>>,
+    ) {
+        let tuples = tuples.into_iter().map(create_ntuple_input).collect();
+        let expected = expected
+            .into_iter()
+            .map(create_ntuple_input)
+            .collect::<Vec<_>>();
+
+        assert_eq!(dedup_ntuples(tuples), expected);
+    }
+
+    #[rstest]
+    #[case::empty(vec![], vec![])]
+    #[case::adjacent(
+        vec![
+            vec![("x", interval_input("[0, 5)")), ("y", bool_input(true))],
+            vec![("x", interval_input("[5, 10]")), ("y", bool_input(true))],
+        ],
+        vec![vec![("x", interval_input("[0, 10]")), ("y", bool_input(true))]],
+    )]
+    #[case::overlapping(
+        vec![vec![("x", interval_input("[0, 10]"))], vec![("x", interval_input("[5, 20]"))]],
+        vec![vec![("x", interval_input("[0, 20]"))]],
+    )]
+    #[case::chain(
+        vec![
+            vec![("x", interval_input("[0, 1)"))],
+            vec![("x", interval_input("[2, 3]"))],
+            vec![("x", interval_input("[1, 2)"))],
+        ],
+        vec![vec![("x", interval_input("[0, 3]"))]],
+    )]
+    #[case::gap(
+        vec![vec![("x", interval_input("[0, 5)"))], vec![("x", interval_input("(5, 10]"))]],
+        vec![vec![("x", interval_input("[0, 5)"))], vec![("x", interval_input("(5, 10]"))]],
+    )]
+    #[case::two_variables_differ(
+        vec![
+            vec![("x", interval_input("[0, 5)")), ("y", interval_input("[0, 1]"))],
+            vec![("x", interval_input("[5, 10]")), ("y", interval_input("[1, 2]"))],
+        ],
+        vec![
+            vec![("x", interval_input("[0, 5)")), ("y", interval_input("[0, 1]"))],
+            vec![("x", interval_input("[5, 10]")), ("y", interval_input("[1, 2]"))],
+        ],
+    )]
+    #[case::different_bools(
+        vec![
+            vec![("x", interval_input("[0, 5]")), ("y", bool_input(true))],
+            vec![("x", interval_input("[0, 5]")), ("y", bool_input(false))],
+        ],
+        vec![
+            vec![("x", interval_input("[0, 5]")), ("y", bool_input(true))],
+            vec![("x", interval_input("[0, 5]")), ("y", bool_input(false))],
+        ],
+    )]
+    #[case::duplicates(
+        vec![vec![("x", interval_input("[0, 5]"))], vec![("x", interval_input("[0, 5]"))]],
+        vec![vec![("x", interval_input("[0, 5]"))]],
+    )]
+    fn test_coalesce_ntuples(
+        #[case] tuples: Vec<Vec<(&str, Input)>>,
+        #[case] expected: Vec<Vec<(&str, Input)>>,
+    ) {
+        let tuples = tuples
+            .into_iter()
+            .map(create_ntuple_input)
+            .collect::<Vec<_>>();
+        let expected = expected
+            .into_iter()
+            .map(create_ntuple_input)
+            .collect::<Vec<_>>();
+
+        assert_eq!(coalesce_ntuples(&tuples), expected);
+    }
+
+    #[rstest]
+    #[case::empty(vec![], "")]
+    #[case::bool(vec![("flag", bool_input(true))], "flag = true")]
+    #[case::interval(vec![("x", interval_input("[0, 10)"))], "x ∈ [0, 10)")]
+    #[case::multi_interval(vec![("x", interval_input("(-Inf, 0) (0, Inf)"))], "x ∈ (-Inf, 0) (0, Inf)")]
+    #[case::empty_interval(vec![("x", interval_input(""))], "x ∈ ∅")]
+    #[case::any(vec![("x", Input::Any)], "x = *")]
+    #[case::ordered_by_name(
+        vec![("x", interval_input("[0, 10)")), ("flag", bool_input(false)), ("a", interval_input("[1, 1]"))],
+        "a ∈ [1, 1], flag = false, x ∈ [0, 10)",
+    )]
+    fn test_ntuple_input_display(#[case] inputs: Vec<(&str, Input)>, #[case] expected: &str) {
+        assert_eq!(create_ntuple_input(inputs).to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("[0, 10]", 0.5, vec![(0.0, On), (-0.5, Outside), (10.0, On), (10.5, Outside)])]
+    #[case("(0, 10)", 0.5, vec![(0.0, On), (0.5, Inside), (10.0, On), (9.5, Inside)])]
+    #[case("[0, 10)", 1.0, vec![(0.0, On), (-1.0, Outside), (10.0, On), (9.0, Inside)])]
+    #[case("(-Inf, 10]", 0.01, vec![(10.0, On), (10.01, Outside)])]
+    #[case("(-Inf, Inf)", 0.01, vec![])]
+    #[case("[5, 5]", 1.0, vec![(5.0, On), (4.0, Outside), (6.0, Outside)])]
+    #[case("(0, 1)", 1.0, vec![(0.0, On), (1.0, On)])]
+    #[case("[0.1, 0.3]", 0.1, vec![(0.1, On), (0.0, Outside), (0.3, On), (0.4, Outside)])]
+    #[case("[0, 10) (10, 20]", 1.0, vec![(0.0, On), (-1.0, Outside), (10.0, On), (9.0, Inside), (11.0, Inside), (20.0, On), (21.0, Outside)])]
+    fn test_boundary_test_points(
+        #[case] interval: &str,
+        #[case] precision: f32,
+        #[case] expected: Vec<(f32, BoundaryPointKind)>,
+    ) {
+        let dto = IntervalDTO {
+            interval: multiint(interval),
+            precision,
+            is_constant: false,
+            is_integer: false,
+        };
+        let expected = expected
+            .into_iter()
+            .map(|(value, kind)| BoundaryTestPoint { value, kind })
+            .collect::<Vec<_>>();
+
+        assert_eq!(dto.boundary_test_points(), expected);
+    }
+
+    #[rstest]
+    #[case("(0, 1)", 1.0, 0.5, 0.5)]
+    #[case("(0, 1)", 0.1, 0.5, 0.5)]
+    #[case("[0, 10]", 1.0, 4.4, 4.0)]
+    #[case("(0.6, 1.2)", 0.5, 0.7, 1.0)]
+    #[case("[0, 10]", 1.0, 10.4, 11.0)]
+    #[case("[0, 10]", 0.01, 10.010001, 10.01)]
+    fn test_snap_point(
+        #[case] interval: &str,
+        #[case] precision: f32,
+        #[case] value: f32,
+        #[case] expected: f32,
+    ) {
+        let dto = IntervalDTO {
+            interval: multiint(interval),
+            precision,
+            is_constant: false,
+            is_integer: false,
+        };
+
+        assert_eq!(dto.snap_point(value), expected);
+    }
+
+    #[rstest]
+    #[case("(0.5, 7.5)", true, "[1, 7]")]
+    #[case("(0, 7]", true, "[1, 7]")]
+    #[case("(0, 10)", true, "[1, 9]")]
+    #[case("(-Inf, 10)", true, "(-Inf, 9]")]
+    #[case("(0, 1)", true, "")]
+    #[case("(0, 1]", true, "[1, 1]")]
+    #[case("(-Inf, 7.5)", true, "(-Inf, 7]")]
+    #[case("(-2.5, Inf)", true, "[-2, Inf)")]
+    #[case("(0.2, 0.8) [1.5, 3.5]", true, "[2, 3]")]
+    #[case("(0.5, 7.5)", false, "(0.5, 7.5)")]
+    #[case("(100000000000000000000, Inf)", true, "(100000000000000000000, Inf)")]
+    #[case(
+        "(-Inf, -100000000000000000000)",
+        true,
+        "(-Inf, -100000000000000000000)"
+    )]
+    #[case("(16777216, 16777220)", true, "(16777216, 16777220)")]
+    #[case("(16777214, 16777216)", true, "[16777215, 16777216)")]
+    fn test_rounded_interval(
+        #[case] interval: MultiInterval,
+        #[case] is_integer: bool,
+        #[case] expected: MultiInterval,
+    ) {
+        let dto = IntervalDTO {
+            interval,
+            precision: 1.0,
+            is_constant: false,
+            is_integer,
+        };
+
+        assert_eq!(dto.rounded_interval(), expected);
+    }
+
+    #[rstest]
+    #[case(("[0, 10]", 1.0, false), ("(5, 20]", 1.0, false), Ok(("(5, 10]", 1.0, false)))]
+    #[case(("[0, 10]", 0.1, false), ("[5, 5]", 0.1, true), Ok(("[5, 5]", 0.1, true)))]
+    #[case(("(-Inf, 0) (10, Inf)", 1.0, false), ("[-5, 15]", 1.0, false), Ok(("[-5, 0) (10, 15]", 1.0, false)))]
+    #[case(("[0, 10]", 1.0, false), ("(10, 20]", 1.0, false), Err(ConversionError::Contradiction))]
+    #[case(
+        ("[0, 10]", 1.0, false),
+        ("[0, 10]", 0.1, false),
+        Err(ConversionError::PrecisionMismatch { this: 1.0, that: 0.1 })
+    )]
+    fn test_interval_dto_merge(
+        #[case] this: (&str, f32, bool),
+        #[case] that: (&str, f32, bool),
+        #[case] expected: Result<(&str, f32, bool), ConversionError>,
+    ) {
+        let dto = |(interval, precision, is_constant): (&str, f32, bool)| IntervalDTO {
+            interval: multiint(interval),
+            precision,
+            is_constant,
+            is_integer: false,
+        };
+
+        assert_eq!(dto(this).merge(&dto(that)), expected.map(dto));
+    }
 }
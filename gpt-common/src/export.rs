@@ -0,0 +1,346 @@
+use serde_json::{Map, Number, Value};
+
+use crate::{
+    dto::{self, Input, NTupleInput},
+    interval::{Boundary, Interval},
+    ir::{BoolCondition, Condition, Feature, IntervalCondition, Predicate, RelationCondition},
+    parser::ast::Type,
+};
+
+/// Converts an interval endpoint to JSON. Infinities become the strings `"-Inf"` and `"Inf"`,
+/// because JSON has no representation for them.
+fn endpoint_to_json(value: f32) -> Value {
+    if value.is_infinite() {
+        return Value::String(if value > 0.0 { "Inf" } else { "-Inf" }.to_owned());
+    }
+
+    // Going through the shortest string representation, so 0.1_f32 becomes 0.1 and not 0.10000000149011612
+    value
+        .to_string()
+        .parse::<f64>()
+        .ok()
+        .and_then(Number::from_f64)
+        .map_or(Value::Null, Value::Number)
+}
+
+fn interval_to_json(interval: &Interval) -> Value {
+    let mut object = Map::new();
+    object.insert("min".to_owned(), endpoint_to_json(interval.lo));
+    object.insert("max".to_owned(), endpoint_to_json(interval.hi));
+    object.insert(
+        "minInclusive".to_owned(),
+        Value::Bool(interval.lo_boundary == Boundary::Closed),
+    );
+    object.insert(
+        "maxInclusive".to_owned(),
+        Value::Bool(interval.hi_boundary == Boundary::Closed),
+    );
+
+    Value::Object(object)
+}
+
+fn input_to_json(input: &Input) -> Value {
+    match input {
+        Input::Bool(bool_dto) => Value::Bool(bool_dto.bool_val),
+        Input::Any => Value::String("*".to_owned()),
+        Input::Enum(enum_dto) => match &enum_dto.values[..] {
+            [value] => Value::String(value.clone()),
+            values => Value::Array(values.iter().cloned().map(Value::String).collect()),
+        },
+        Input::Interval(interval_dto) => match &interval_dto.interval.intervals[..] {
+            [interval] => interval_to_json(interval),
+            intervals => Value::Array(intervals.iter().map(interval_to_json).collect()),
+        },
+    }
+}
+
+/// Creates a JSON array with one object per `NTupleInput`, keyed by the variable names of the feature.
+///
+/// Interval inputs are objects like `{ "min": 0, "max": 10, "minInclusive": true, "maxInclusive": false }`,
+/// or an array of such objects if the input consists of multiple intervals. Bool inputs are JSON booleans,
+/// and `Any` inputs are the string `"*"`. Variables of the feature which are not in a tuple are `null`.
+pub fn ntuples_to_json(feature: &Feature, tuples: &[NTupleInput]) -> Value {
+    let rows = tuples
+        .iter()
+        .map(|ntuple| {
+            let row = feature
+                .variables
+                .iter()
+                .map(|variable| {
+                    let value = ntuple
+                        .inputs
+                        .get(&variable.var_name)
+                        .map_or(Value::Null, input_to_json);
+
+                    (variable.var_name.clone(), value)
+                })
+                .collect::<Map<String, Value>>();
+
+            Value::Object(row)
+        })
+        .collect();
+
+    Value::Array(rows)
+}
+
+fn value_to_json(value: dto::Value) -> Value {
+    match value {
+        dto::Value::Number(num) => endpoint_to_json(num),
+        dto::Value::Bool(bool_val) => Value::Bool(bool_val),
+        dto::Value::Enum(value) => Value::String(value),
+    }
+}
+
+/// Creates a JSON array with one object per `NTupleInput`, like [`ntuples_to_json`], but with a concrete value for every variable.
+///
+/// The values are picked with [`Input::pick_value`] using the precision of the variable,
+/// `Any` inputs are the string `"*"`, and variables of the feature which are not in a tuple are `null`.
+pub fn ntuples_to_values_json(feature: &Feature, tuples: &[NTupleInput]) -> Value {
+    let rows = tuples
+        .iter()
+        .map(|ntuple| {
+            let row = feature
+                .variables
+                .iter()
+                .map(|variable| {
+                    let precision = variable.var_type.get_precision().unwrap_or(1.0);
+                    let value =
+                        ntuple
+                            .inputs
+                            .get(&variable.var_name)
+                            .map_or(Value::Null, |input| {
+                                input
+                                    .pick_value(precision)
+                                    .map_or_else(|| Value::String("*".to_owned()), value_to_json)
+                            });
+
+                    (variable.var_name.clone(), value)
+                })
+                .collect::<Map<String, Value>>();
+
+            Value::Object(row)
+        })
+        .collect();
+
+    Value::Array(rows)
+}
+
+/// The type as it's written in the GPT language, like `num(0.1)`
+fn type_to_gpt(var_type: &Type) -> String {
+    match var_type {
+        Type::Bool => "bool".to_owned(),
+        Type::Integer => "int".to_owned(),
+        Type::Float { precision } => format!("num({precision})"),
+        Type::Enum(values) => format!("enum {{{}}}", values.join(", ")),
+        Type::Decimal { places } => format!("decimal({places})"),
+    }
+}
+
+/// The conditions of the predicate as they are written, the negations are not resolved.
+fn leaf_conditions(predicate: &Predicate) -> Vec<&Condition> {
+    match predicate {
+        Predicate::Expression(condition) => vec![condition],
+        Predicate::Negated(predicate) => leaf_conditions(predicate),
+        Predicate::Group { left, right, .. } => {
+            let mut conditions = leaf_conditions(left);
+            conditions.extend(leaf_conditions(right));
+            conditions
+        }
+    }
+}
+
+fn escape_dot(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Creates a Graphviz DOT graph of the feature, where every variable is connected to the predicates mentioning it.
+///
+/// The variable nodes are boxes labeled with their types and domains, like `age: int in [0, 150]`,
+/// and the predicate nodes are labeled with their index and the predicate itself.
+/// Every condition of a predicate is an edge from its variable, labeled like `x ∈ [0, 10]` or `flag = true`.
+pub fn feature_to_dot(feature: &Feature) -> String {
+    let variable_nodes = feature.variables.iter().map(|variable| {
+        let domain = variable
+            .domain
+            .map_or_else(String::new, |domain| format!(" in {domain}"));
+        let label = format!(
+            "{}: {}{domain}",
+            variable.var_name,
+            type_to_gpt(&variable.var_type)
+        );
+
+        format!(
+            "    \"var {}\" [shape=box, label=\"{}\"];",
+            escape_dot(&variable.var_name),
+            escape_dot(&label)
+        )
+    });
+
+    let predicate_nodes_and_edges =
+        feature
+            .predicates
+            .iter()
+            .enumerate()
+            .flat_map(|(i, predicate)| {
+                let node = format!(
+                    "    \"predicate {i}\" [shape=ellipse, label=\"#{i}: {}\"];",
+                    escape_dot(&predicate.to_string())
+                );
+
+                let edges = leaf_conditions(predicate)
+                    .into_iter()
+                    .flat_map(move |condition| {
+                        let label = match condition {
+                            Condition::Bool(BoolCondition {
+                                var_name,
+                                should_equal_to,
+                            }) => format!("{var_name} = {should_equal_to}"),
+                            Condition::Interval(IntervalCondition { var_name, interval }) => {
+                                format!("{var_name} ∈ {interval}")
+                            }
+                            Condition::Enum(condition) => condition.to_string(),
+                            Condition::Relation(condition) => condition.to_string(),
+                        };
+                        // A relation has an edge from both of its variables
+                        let var_names = match condition {
+                            Condition::Relation(RelationCondition { left, right, .. }) => {
+                                vec![left.as_str(), right.as_str()]
+                            }
+                            _ => vec![condition.get_variable()],
+                        };
+
+                        var_names.into_iter().map(move |var_name| {
+                            format!(
+                                "    \"var {}\" -> \"predicate {i}\" [label=\"{}\"];",
+                                escape_dot(var_name),
+                                escape_dot(&label)
+                            )
+                        })
+                    });
+
+                std::iter::once(node).chain(edges)
+            });
+
+    let lines = variable_nodes
+        .chain(predicate_nodes_and_edges)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!("digraph feature {{\n    rankdir=LR;\n{lines}\n}}\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::{feature_to_dot, ntuples_to_json, ntuples_to_values_json};
+    use crate::{ir::ir_to_ntuple::ir_to_ntuple, parser::parse_gpt_to_ir};
+
+    #[test]
+    fn test_ntuples_to_json() {
+        let input = r#"
+            var vip: bool
+            var price: num(0.1)
+            var weight: num(0.1)
+
+            if(vip == true && price in [0, 10))
+            if(weight < 5)
+        "#;
+        let (_, features) = parse_gpt_to_ir(input).unwrap();
+        let feature = &features[0];
+        let (ntuples, _) = ir_to_ntuple(feature);
+
+        assert_eq!(
+            ntuples_to_json(feature, &ntuples),
+            json!([
+                {
+                    "vip": true,
+                    "price": { "min": 0.0, "max": 10.0, "minInclusive": true, "maxInclusive": false },
+                    "weight": "*",
+                },
+                {
+                    "vip": "*",
+                    "price": "*",
+                    "weight": { "min": "-Inf", "max": 5.0, "minInclusive": false, "maxInclusive": false },
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_ntuples_to_json_multiple_intervals() {
+        let input = r#"
+            var x: num(0.1)
+
+            if(x != 0.1)
+        "#;
+        let (_, features) = parse_gpt_to_ir(input).unwrap();
+        let feature = &features[0];
+        let (ntuples, _) = ir_to_ntuple(feature);
+
+        assert_eq!(
+            ntuples_to_json(feature, &ntuples),
+            json!([
+                {
+                    "x": [
+                        { "min": "-Inf", "max": 0.1, "minInclusive": false, "maxInclusive": false },
+                        { "min": 0.1, "max": "Inf", "minInclusive": false, "maxInclusive": false },
+                    ],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_ntuples_to_values_json() {
+        let input = r#"
+            var vip: bool
+            var age: int
+            var price: num(0.1)
+
+            if(vip == true && price in (0, 10))
+            if(age > 17)
+        "#;
+        let (_, features) = parse_gpt_to_ir(input).unwrap();
+        let feature = &features[0];
+        let (ntuples, _) = ir_to_ntuple(feature);
+
+        assert_eq!(
+            ntuples_to_values_json(feature, &ntuples),
+            json!([
+                { "vip": true, "age": "*", "price": 0.1 },
+                { "vip": "*", "age": 18.0, "price": "*" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_feature_to_dot() {
+        let input = r#"
+            var age: int in [0, 150]
+            var vip: bool
+            var price: num(0.1)
+
+            if(age < 18 && vip == true)
+            if(!(price in [0, 10)))
+        "#;
+        let (_, features) = parse_gpt_to_ir(input).unwrap();
+
+        assert_eq!(
+            feature_to_dot(&features[0]),
+            r##"digraph feature {
+    rankdir=LR;
+    "var age" [shape=box, label="age: int in [0, 150]"];
+    "var vip" [shape=box, label="vip: bool"];
+    "var price" [shape=box, label="price: num(0.1)"];
+    "predicate 0" [shape=ellipse, label="#0: (age in (-Inf, 18) && vip == true)"];
+    "var age" -> "predicate 0" [label="age ∈ (-Inf, 18)"];
+    "var vip" -> "predicate 0" [label="vip = true"];
+    "predicate 1" [shape=ellipse, label="#1: !(price in [0, 10))"];
+    "var price" -> "predicate 1" [label="price ∈ [0, 10)"];
+}
+"##
+        );
+    }
+}
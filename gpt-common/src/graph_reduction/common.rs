@@ -65,7 +65,7 @@ where
 
     for edge_index in graph.edge_indices() {
         let (a, b) = graph.edge_endpoints(edge_index).expect(
-            "We're iterating through the edge indicies, this index should exist in the graph",
+            "We're iterating through the edge indices, this index should exist in the graph",
         );
 
         new_graph.add_edge(a, b, ENew::default());
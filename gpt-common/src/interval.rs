@@ -1,7 +1,22 @@
-use std::{cmp::Ordering, fmt};
-
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Range, RangeInclusive},
+    str::FromStr,
+};
+
+use nom::{
+    combinator::{all_consuming, complete},
+    multi::many0,
+};
 use serde::{Serialize, Serializer};
 
+use crate::{
+    parser::{ast::BinaryOp, interval as interval_parser},
+    util::{grid_value, snap_to_precision},
+};
+
 pub trait Intersectable {
     fn intersects_with(&self, other: &Self) -> bool;
 
@@ -14,7 +29,7 @@ pub trait Unionable<TOther, TResult> {
     fn union(&self, other: &TOther) -> TResult;
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum Boundary {
     Open,
     Closed,
@@ -28,10 +43,54 @@ impl Boundary {
             Self::Closed => Self::Open,
         }
     }
+
+    /// The bracket on the lo side of an interval, `(` or `[`
+    pub const fn open_bracket(&self) -> char {
+        match self {
+            Self::Open => '(',
+            Self::Closed => '[',
+        }
+    }
+
+    /// The bracket on the hi side of an interval, `)` or `]`
+    pub const fn close_bracket(&self) -> char {
+        match self {
+            Self::Open => ')',
+            Self::Closed => ']',
+        }
+    }
+
+    /// The boundary of a bracket, and the side of the interval the bracket belongs to.
+    ///
+    /// Example: `(` is an open lo boundary, `]` is a closed hi boundary.
+    pub const fn from_bracket(bracket: char) -> Result<(Self, EndpointKind), InvalidBracket> {
+        match bracket {
+            '(' => Ok((Self::Open, EndpointKind::Lo)),
+            '[' => Ok((Self::Closed, EndpointKind::Lo)),
+            ')' => Ok((Self::Open, EndpointKind::Hi)),
+            ']' => Ok((Self::Closed, EndpointKind::Hi)),
+            other => Err(InvalidBracket(other)),
+        }
+    }
+}
+
+/// The character is not one of the brackets `(`, `[`, `)`, `]`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct InvalidBracket(pub char);
+
+/// Use `Boundary::from_bracket` to know the side of the bracket as well.
+impl TryFrom<char> for Boundary {
+    type Error = InvalidBracket;
+
+    fn try_from(bracket: char) -> Result<Self, Self::Error> {
+        Self::from_bracket(bracket).map(|(boundary, _)| boundary)
+    }
 }
 
 /// Represents one interval with boundaries, a low value and a high value
-#[derive(PartialEq, Clone, Copy)]
+///
+/// The endpoints are compared like in [`Endpoint`], so `-0` and `0` are equal, and they have the same hash.
+#[derive(Clone, Copy)]
 pub struct Interval {
     pub lo_boundary: Boundary,
     pub lo: f32,
@@ -39,6 +98,18 @@ pub struct Interval {
     pub hi_boundary: Boundary,
 }
 
+/// Where a point is compared to an interval, see [`Interval::locate`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PointLocation {
+    /// Strictly between the endpoints
+    Inside,
+    /// Exactly the `lo` endpoint, whether the boundary is open or closed
+    OnLo,
+    /// Exactly the `hi` endpoint, whether the boundary is open or closed
+    OnHi,
+    Outside,
+}
+
 impl Interval {
     pub fn contains_point(&self, point: f32) -> bool {
         !self.is_empty()
@@ -47,19 +118,310 @@ impl Interval {
                 || (self.hi == point && self.hi_boundary == Boundary::Closed))
     }
 
+    /// Tells whether the point is inside, on one of the endpoints, or outside of the interval.
+    ///
+    /// An endpoint is reported even for an open boundary, like `10` for `[0, 10)`, because that's the on point of the boundary.
+    /// The point of a single point interval, like `[5, 5]`, is `OnLo`. Every point is `Outside` of an empty interval.
+    pub fn locate(&self, point: f32) -> PointLocation {
+        if self.is_empty() {
+            PointLocation::Outside
+        } else if point == self.lo {
+            PointLocation::OnLo
+        } else if point == self.hi {
+            PointLocation::OnHi
+        } else if self.lo < point && point < self.hi {
+            PointLocation::Inside
+        } else {
+            PointLocation::Outside
+        }
+    }
+
+    /// Projects the point into the interval. If the point is outside, the closest value of the interval is returned,
+    /// which is the endpoint for a closed boundary, and the endpoint stepped inside by `precision` for an open one.
+    ///
+    /// Example: clamping `-5` and `15` into `(0, 10)` with the precision of `1` results in `1` and `9`.
+    /// If the stepped value would overshoot a narrow interval, like `(0, 0.5)` with the precision of `1`, the midpoint is used.
+    /// An empty interval has no values, so the point is returned as it is.
+    pub fn clamp(&self, point: f32, precision: f32) -> f32 {
+        if self.is_empty() || self.contains_point(point) {
+            return point;
+        }
+
+        let clamped = if point <= self.lo {
+            match self.lo_boundary {
+                Boundary::Closed => self.lo,
+                Boundary::Open => self.lo + precision,
+            }
+        } else {
+            match self.hi_boundary {
+                Boundary::Closed => self.hi,
+                Boundary::Open => self.hi - precision,
+            }
+        };
+
+        if self.contains_point(clamped) {
+            clamped
+        } else {
+            self.midpoint()
+        }
+    }
+
+    /// The closest value of the interval to `point`, which is `point` itself if it's inside, like `clamp`.
+    ///
+    /// Returns `None` for an empty interval, because it has no values.
+    pub fn nearest_inside(&self, point: f32, precision: f32) -> Option<f32> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(self.clamp(point, precision))
+    }
+
+    /// The grid points of `precision` inside the interval, as a closed interval.
+    ///
+    /// `lo` is rounded up and `hi` is rounded down to a multiple of `precision`, so the result is a subset.
+    /// An open endpoint on the grid is excluded, so it's stepped inside. Endpoints within a tiny fraction of a step from
+    /// a grid point are considered on the grid, so floating point noise like `0.30000001` doesn't move them by a whole step.
+    /// Example: `(0, 10.5]` becomes `[1, 10]` with the precision of `1`.
+    /// Returns an empty interval if there is no grid point inside, and the interval as it is for a non-positive precision.
+    #[must_use]
+    pub fn quantize(&self, precision: f32) -> Self {
+        const GRID_TOLERANCE: f32 = 1e-4;
+
+        if self.is_empty() || precision <= 0.0 {
+            return *self;
+        }
+
+        let grid_steps = |value: f32, round: fn(f32) -> f32| {
+            let steps = value / precision;
+            if (steps - steps.round()).abs() < GRID_TOLERANCE {
+                steps.round()
+            } else {
+                round(steps)
+            }
+        };
+
+        let (lo_boundary, lo) = if self.lo.is_infinite() {
+            (Boundary::Open, self.lo)
+        } else {
+            let steps = grid_steps(self.lo, f32::ceil);
+            let steps = match self.lo_boundary {
+                Boundary::Open if grid_value(steps, precision) <= self.lo => steps + 1.0,
+                _ => steps,
+            };
+            (Boundary::Closed, grid_value(steps, precision))
+        };
+
+        let (hi, hi_boundary) = if self.hi.is_infinite() {
+            (self.hi, Boundary::Open)
+        } else {
+            let steps = grid_steps(self.hi, f32::floor);
+            let steps = match self.hi_boundary {
+                Boundary::Open if grid_value(steps, precision) >= self.hi => steps - 1.0,
+                _ => steps,
+            };
+            (grid_value(steps, precision), Boundary::Closed)
+        };
+
+        if lo > hi {
+            return Self {
+                lo_boundary: Boundary::Open,
+                lo,
+                hi: lo,
+                hi_boundary: Boundary::Open,
+            };
+        }
+
+        Self {
+            lo_boundary,
+            lo,
+            hi,
+            hi_boundary,
+        }
+    }
+
+    /// A value of the interval to represent it: the lo endpoint, or the hi endpoint if lo is unbounded,
+    /// or `0` for `(-Inf, Inf)`. An open endpoint is stepped inside by `precision`, like in `clamp`.
+    ///
+    /// The endpoints of the `quantize`d interval are used, so the value is on the precision grid,
+    /// unless there is no grid point inside, like for `(0, 1)` with the precision of `1`.
+    /// Returns `None` for an empty interval.
+    pub fn representative_value(&self, precision: f32) -> Option<f32> {
+        let quantized = self.quantize(precision);
+        let interval = if quantized.is_empty() {
+            self
+        } else {
+            &quantized
+        };
+        let candidate = match (interval.lo.is_infinite(), interval.hi.is_infinite()) {
+            (true, true) => 0.0,
+            (true, false) => interval.hi,
+            (false, _) => interval.lo,
+        };
+
+        interval.nearest_inside(snap_to_precision(candidate, precision), precision)
+    }
+
+    /// The value on the precision grid closest to the middle of the interval, or its representative value if it's unbounded.
+    /// Returns `None` if it's empty.
+    pub fn middle_value(&self, precision: f32) -> Option<f32> {
+        if self.width().is_infinite() {
+            return self.representative_value(precision);
+        }
+
+        self.nearest_inside(snap_to_precision(self.midpoint(), precision), precision)
+    }
+
+    /// The distance between the endpoints, `f32::INFINITY` if any of the sides is unbounded.
+    pub fn width(&self) -> f32 {
+        if self.lo.is_infinite() || self.hi.is_infinite() {
+            return f32::INFINITY;
+        }
+
+        self.hi - self.lo
+    }
+
+    /// The width of the intersection, `0` if the intervals are disjoint or only share a single point,
+    /// and `f32::INFINITY` if the intersection is unbounded.
+    pub fn overlap(&self, other: &Self) -> f32 {
+        self.intersect(other)
+            .map_or(0.0, |intersection| intersection.width())
+    }
+
+    /// One of the intervals ends where the other one starts, and at least one of them contains that endpoint,
+    /// so there is no gap between them. Intervals overlapping in more than a single point don't touch.
+    ///
+    /// Example: `[0, 10)` touches `[10, 20]`, but not `(10, 20]`, because `10` is in neither of them.
+    pub fn touches(&self, other: &Self) -> bool {
+        let meets = |left: &Self, right: &Self| {
+            left.hi == right.lo
+                && (left.hi_boundary == Boundary::Closed || right.lo_boundary == Boundary::Closed)
+        };
+
+        meets(self, other) || meets(other, self)
+    }
+
+    /// The smallest interval containing both intervals, including the gap between them, unlike `union`.
+    /// At a shared endpoint the closed boundary wins. An empty interval is left out.
+    ///
+    /// Example: the hull of `(0, 5]` and `[8, 10)` is `(0, 10)`, and the hull of `(0, 5]` and `[0, 2]` is `[0, 5]`.
+    #[must_use]
+    pub fn hull(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+
+        let lo = if self.lo_cmp(other) == Ordering::Greater {
+            other
+        } else {
+            self
+        };
+        let hi = if self.hi_cmp(other) == Ordering::Less {
+            other
+        } else {
+            self
+        };
+
+        Self {
+            lo_boundary: lo.lo_boundary,
+            lo: lo.lo,
+            hi: hi.hi,
+            hi_boundary: hi.hi_boundary,
+        }
+    }
+
+    /// The interval as a `lo..hi` range, if it is closed on the left and open on the right, like `[0, 10)`.
+    ///
+    /// An infinite `lo` is always open, so `(-Inf, 10)` is `None`, because the range would contain `-Inf`.
+    pub const fn to_range(&self) -> Option<Range<f32>> {
+        match (self.lo_boundary, self.hi_boundary) {
+            (Boundary::Closed, Boundary::Open) => Some(self.lo..self.hi),
+            _ => None,
+        }
+    }
+
+    /// The interval as a `lo..=hi` range, if it is closed on both sides, like `[0, 10]`.
+    pub const fn to_range_inclusive(&self) -> Option<RangeInclusive<f32>> {
+        match (self.lo_boundary, self.hi_boundary) {
+            (Boundary::Closed, Boundary::Closed) => Some(self.lo..=self.hi),
+            _ => None,
+        }
+    }
+
+    /// The value halfway between the endpoints.
+    ///
+    /// Halving before subtracting, so `[-f32::MAX, f32::MAX]` doesn't overflow.
+    /// If only one side is unbounded, that infinity is returned, and `(-Inf, Inf)` has the midpoint of `0`.
+    pub fn midpoint(&self) -> f32 {
+        match (self.lo.is_infinite(), self.hi.is_infinite()) {
+            (true, true) => 0.0,
+            (true, false) => self.lo,
+            (false, true) => self.hi,
+            (false, false) => self.lo + (self.hi / 2.0 - self.lo / 2.0),
+        }
+    }
+
+    /// Moves both endpoints away from, or towards the pivot by the factor: `lo` becomes `pivot + (lo - pivot) * factor`.
+    /// A negative factor mirrors the interval around the pivot, so the endpoints and their boundaries are swapped.
+    ///
+    /// Example: `[0, 10]` scaled by `0.5` around `5` is `[2.5, 7.5]`, and `[0, 10)` scaled by `-1` around `0` is `(-10, 0]`.
+    /// A factor of `0` collapses the interval to the pivot point. An empty interval is returned as it is.
+    ///
+    /// The result is created with `new`, so an endpoint overflowing to infinity becomes open,
+    /// and a `f32::NaN` endpoint, like from a `f32::NaN` factor or an infinite pivot, is an error.
+    pub fn scale_around(&self, pivot: f32, factor: f32) -> Result<Self, IntervalError> {
+        if self.is_empty() {
+            return Ok(*self);
+        }
+        if factor == 0.0 {
+            return Self::new(Boundary::Closed, pivot, pivot, Boundary::Closed);
+        }
+
+        let lo = pivot + (self.lo - pivot) * factor;
+        let hi = pivot + (self.hi - pivot) * factor;
+
+        if factor < 0.0 {
+            Self::new(self.hi_boundary, hi, lo, self.lo_boundary)
+        } else {
+            Self::new(self.lo_boundary, lo, hi, self.hi_boundary)
+        }
+    }
+
+    /// Like `intersect`, but tells why there is no intersection.
+    ///
+    /// Example: `[0, 10)` and `[10, 20]` have the `ExcludedEndpoint` reason, because `10` is not in `[0, 10)`.
+    pub fn try_intersect(&self, other: &Self) -> Result<Self, DisjointReason> {
+        if let Some(intersection) = self.intersect(other) {
+            return Ok(intersection);
+        }
+
+        if self.is_empty() || other.is_empty() {
+            Err(DisjointReason::Empty)
+        } else if self.lo == other.hi || other.lo == self.hi {
+            Err(DisjointReason::ExcludedEndpoint)
+        } else {
+            Err(DisjointReason::Separated)
+        }
+    }
+
     pub fn contains(&self, _other: &Self) -> bool {
         todo!()
     }
 
     /// Creates an interval. If lo or hi would be infinity, that side will be open, no matter what boundary was passed to it,
-    /// because that is the semantically correct way to handle it.
+    /// because that is the semantically correct way to handle it. The `f32::NaN` endpoints are rejected.
     pub fn new(
         lo_boundary: Boundary,
         lo: f32,
         hi: f32,
         hi_boundary: Boundary,
     ) -> Result<Self, IntervalError> {
-        if lo > hi {
+        if lo.is_nan() || hi.is_nan() {
+            Err(IntervalError::NotANumber)
+        } else if lo > hi {
             Err(IntervalError::LoIsGreaterThanHi)
         } else {
             Ok(Self {
@@ -68,8 +430,8 @@ impl Interval {
                 } else {
                     lo_boundary
                 },
-                lo,
-                hi,
+                lo: normalize_zero(lo),
+                hi: normalize_zero(hi),
                 hi_boundary: if hi == f32::INFINITY {
                     Boundary::Open
                 } else {
@@ -83,7 +445,13 @@ impl Interval {
         Self::new(Boundary::Closed, lo, hi, Boundary::Closed)
     }
 
+    pub fn builder() -> IntervalBuilder {
+        IntervalBuilder::default()
+    }
+
     pub const fn new_closed_point(point: f32) -> Self {
+        let point = normalize_zero(point);
+
         Self {
             lo_boundary: Boundary::Closed,
             lo: point,
@@ -92,6 +460,54 @@ impl Interval {
         }
     }
 
+    /// `(-Inf, Inf)`
+    pub const fn whole_line() -> Self {
+        Self {
+            lo_boundary: Boundary::Open,
+            lo: f32::NEG_INFINITY,
+            hi: f32::INFINITY,
+            hi_boundary: Boundary::Open,
+        }
+    }
+
+    /// The values up to `hi`, like `(-Inf, 10]` for `x <= 10`. An infinite `hi` is always open.
+    pub const fn at_most(hi: f32, boundary: Boundary) -> Self {
+        Self {
+            lo_boundary: Boundary::Open,
+            lo: f32::NEG_INFINITY,
+            hi: normalize_zero(hi),
+            hi_boundary: if hi.is_infinite() {
+                Boundary::Open
+            } else {
+                boundary
+            },
+        }
+    }
+
+    /// The values from `lo`, like `[10, Inf)` for `x >= 10`. An infinite `lo` is always open.
+    pub const fn at_least(lo: f32, boundary: Boundary) -> Self {
+        Self {
+            lo_boundary: if lo.is_infinite() {
+                Boundary::Open
+            } else {
+                boundary
+            },
+            lo: normalize_zero(lo),
+            hi: f32::INFINITY,
+            hi_boundary: Boundary::Open,
+        }
+    }
+
+    /// `(-Inf, hi)` for `x < hi`
+    pub const fn less_than(hi: f32) -> Self {
+        Self::at_most(hi, Boundary::Open)
+    }
+
+    /// `(lo, Inf)` for `x > lo`
+    pub const fn greater_than(lo: f32) -> Self {
+        Self::at_least(lo, Boundary::Open)
+    }
+
     pub fn is_empty(&self) -> bool {
         self.lo == self.hi
             && (self.lo_boundary == Boundary::Open || self.hi_boundary == Boundary::Open)
@@ -103,39 +519,36 @@ impl Interval {
             && self.hi_boundary == Boundary::Closed
     }
 
-    fn lo_cmp(&self, other: &Self) -> Ordering {
-        match self.lo.partial_cmp(&other.lo).unwrap() {
-            std::cmp::Ordering::Equal => match (self.lo_boundary, other.lo_boundary) {
-                (Boundary::Open, Boundary::Closed) => std::cmp::Ordering::Greater,
-                (Boundary::Closed, Boundary::Open) => std::cmp::Ordering::Less,
-                (Boundary::Open, Boundary::Open) => std::cmp::Ordering::Equal,
-                (Boundary::Closed, Boundary::Closed) => std::cmp::Ordering::Equal,
-            },
-            x => x,
+    pub const fn lo_endpoint(&self) -> Endpoint {
+        Endpoint {
+            value: self.lo,
+            boundary: self.lo_boundary,
+            kind: EndpointKind::Lo,
         }
     }
 
-    fn hi_cmp(&self, other: &Self) -> Ordering {
-        match self.hi.partial_cmp(&other.hi).unwrap() {
-            std::cmp::Ordering::Equal => match (self.hi_boundary, other.hi_boundary) {
-                (Boundary::Open, Boundary::Closed) => std::cmp::Ordering::Less,
-                (Boundary::Closed, Boundary::Open) => std::cmp::Ordering::Greater,
-                (Boundary::Open, Boundary::Open) => std::cmp::Ordering::Equal,
-                (Boundary::Closed, Boundary::Closed) => std::cmp::Ordering::Equal,
-            },
-            x => x,
+    pub const fn hi_endpoint(&self) -> Endpoint {
+        Endpoint {
+            value: self.hi,
+            boundary: self.hi_boundary,
+            kind: EndpointKind::Hi,
         }
     }
 
+    /// Orders the `lo` endpoints like [`Endpoint`], so `[5` is before `(5`
+    fn lo_cmp(&self, other: &Self) -> Ordering {
+        self.lo_endpoint().cmp(&other.lo_endpoint())
+    }
+
+    /// Orders the `hi` endpoints like [`Endpoint`], so `5)` is before `5]`
+    fn hi_cmp(&self, other: &Self) -> Ordering {
+        self.hi_endpoint().cmp(&other.hi_endpoint())
+    }
+
     pub fn complement(&self) -> MultiInterval {
         if self.is_empty() {
             return MultiInterval {
-                intervals: vec![Self {
-                    lo_boundary: Boundary::Open,
-                    lo: f32::NEG_INFINITY,
-                    hi: f32::INFINITY,
-                    hi_boundary: Boundary::Open,
-                }],
+                intervals: vec![Self::whole_line()],
             };
         }
 
@@ -163,11 +576,152 @@ impl Interval {
             intervals: new_intervals,
         }
     }
+
+    /// The complement relative to `universe` instead of `(-Inf, Inf)`, see [`MultiInterval::complement_in`].
+    pub fn complement_in(&self, universe: &Self) -> MultiInterval {
+        MultiInterval::from_interval(*self).complement_in(universe)
+    }
+
+    /// The part of the interval where `x op value` holds for `x`.
+    ///
+    /// Example: `[0, 10]` compared with `> 6` is `(6, 10]`, and with `!= 5` it's `[0, 5) (5, 10]`
+    pub fn compare(&self, op: &BinaryOp, value: f32) -> MultiInterval {
+        MultiInterval::from_interval(*self)
+            .intersect(&MultiInterval::from_comparison(op, value))
+            .unwrap_or_else(MultiInterval::new_empty)
+    }
+
+    /// Like `intersect`, but it also returns which of the two intervals supplied the `lo` and the `hi` of the result.
+    /// If both intervals have the same endpoint, it's credited to `self_id`.
+    ///
+    /// Folding this over many conditions tells which condition the final boundaries come from,
+    /// like `[0, 20]` with id `1` and `(5, 10]` with id `2` result in `(5, 10]` with both boundaries from `2`.
+    pub fn intersect_tracked(
+        &self,
+        other: &Self,
+        self_id: usize,
+        other_id: usize,
+    ) -> Option<(Self, BoundarySource)> {
+        let intersection = self.intersect(other)?;
+
+        let lo = if self.lo_cmp(other) == Ordering::Less {
+            other_id
+        } else {
+            self_id
+        };
+        let hi = if self.hi_cmp(other) == Ordering::Greater {
+            other_id
+        } else {
+            self_id
+        };
+
+        Some((intersection, BoundarySource { lo, hi }))
+    }
+
+    /// Splits the interval into `n` contiguous pieces of equal width, whose union is the original interval.
+    ///
+    /// The interior boundaries are closed on the left and open on the right, so the pieces don't overlap,
+    /// like `[0, 10]` into 2 pieces being `[0, 5) [5, 10]`. The outer boundaries are kept.
+    /// A single point can't be split, so it's the only piece, and an empty interval has no pieces.
+    /// If the interval is too narrow for `n` distinct `f32` cut points, the empty pieces are left out, so there are fewer than `n` pieces.
+    pub fn split_into(&self, n: usize) -> Result<Vec<Self>, IntervalError> {
+        if n == 0 {
+            return Err(IntervalError::ZeroPieces);
+        }
+        if self.is_empty() {
+            return Ok(Vec::new());
+        }
+        if self.width().is_infinite() {
+            return Err(IntervalError::Unbounded);
+        }
+        if self.is_single_point() {
+            return Ok(vec![*self]);
+        }
+
+        let width = self.width();
+        // The last cut point is exactly `hi`, even with rounding errors
+        let cut_point = |k: usize| {
+            if k == n {
+                self.hi
+            } else {
+                self.lo + width * k as f32 / n as f32
+            }
+        };
+
+        Ok((0..n)
+            .map(|k| Self {
+                lo_boundary: if k == 0 {
+                    self.lo_boundary
+                } else {
+                    Boundary::Closed
+                },
+                lo: cut_point(k),
+                hi: cut_point(k + 1),
+                hi_boundary: if k == n - 1 {
+                    self.hi_boundary
+                } else {
+                    Boundary::Open
+                },
+            })
+            .filter(|piece| !piece.is_empty())
+            .collect())
+    }
+
+    /// Cuts the interval at the endpoints of `other` which are inside of it, so every piece is either in `other` or outside of it.
+    ///
+    /// The cuts follow the boundaries of `other`, the pieces don't overlap and their union is `self`.
+    /// Example: `[0, 10]` split on `[3, 5]` is `[0, 3) [3, 5] (5, 10]`, and on `(3, 5)` it's `[0, 3] (3, 5) [5, 10]`.
+    /// An empty interval has no pieces, and an empty `other` has no endpoints to cut at.
+    pub fn split_on_boundary_of(&self, other: &Self) -> Vec<Self> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        if other.is_empty() {
+            return vec![*self];
+        }
+
+        let below = Self::at_most(other.lo, other.lo_boundary.inverse());
+        let above = Self::at_least(other.hi, other.hi_boundary.inverse());
+
+        [below, *other, above]
+            .iter()
+            .filter_map(|piece| self.intersect(piece))
+            .collect()
+    }
+
+    /// Like `intersect`, but the lack of an intersection is an explicit empty interval instead of `None`,
+    /// so a chain of intersections can be folded without special-casing `None` at every step.
+    ///
+    /// The empty result is `(x, x)`, where `x` is the greater `lo`, and `is_empty` is true for it.
+    /// Intersecting with an empty interval is empty as well, so it's enough to check `is_empty` at the end of the chain.
+    /// Example: `[0, 10)` and `[10, 20]` result in `(10, 10)`.
+    pub fn intersect_keep_empty(&self, other: &Self) -> Self {
+        self.intersect(other).unwrap_or_else(|| {
+            let lo = self.lo.max(other.lo);
+
+            Self {
+                lo_boundary: Boundary::Open,
+                lo,
+                hi: lo,
+                hi_boundary: Boundary::Open,
+            }
+        })
+    }
+}
+
+/// The ids of the intervals which supplied the boundaries of an intersection, see [`Interval::intersect_tracked`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BoundarySource {
+    pub lo: usize,
+    pub hi: usize,
 }
 
 impl Intersectable for Interval {
+    /// Empty intervals, like `(0,0)`, don't intersect with anything, not even with themselves.
     fn intersects_with(&self, other: &Self) -> bool {
-        let doesnt_intersect = (self.lo > other.hi || other.lo > self.hi)
+        let doesnt_intersect = self.is_empty()
+            || other.is_empty()
+            || (self.lo > other.hi || other.lo > self.hi)
             || self.lo == other.hi
                 && (self.lo_boundary == Boundary::Open || other.hi_boundary == Boundary::Open)
             || other.lo == self.hi
@@ -203,74 +757,351 @@ impl Intersectable for Interval {
     }
 }
 
-impl fmt::Display for Interval {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let lo_boundary = match self.lo_boundary {
-            Boundary::Open => "(",
-            Boundary::Closed => "[",
-        };
+/// A total order of the endpoint values, which never panics: `f32::NaN` is greater than everything, and equal to itself.
+///
+/// `Interval::new` rejects `f32::NaN`, but the fields are public, so an interval with a `f32::NaN` endpoint can still be created.
+/// Unlike `f32::total_cmp`, `-0` and `0` are equal.
+fn cmp_endpoint_values(a: f32, b: f32) -> Ordering {
+    a.partial_cmp(&b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
 
-        let lo = if self.lo == f32::NEG_INFINITY {
-            "-Inf".to_owned()
-        } else {
-            self.lo.to_string()
-        };
+/// Turns `-0` into `0`, and leaves the other values as they are.
+/// The endpoints calculated by subtraction or negation can be `-0`, which would be displayed and hashed differently.
+const fn normalize_zero(value: f32) -> f32 {
+    value + 0.0
+}
 
-        let hi = if self.hi == f32::INFINITY {
-            "Inf".to_owned()
-        } else {
-            self.hi.to_string()
-        };
+/// Hashes the endpoint value consistently with `cmp_endpoint_values`, so `-0` and `0`, and every `f32::NaN` have the same hash.
+fn hash_endpoint_value<H: Hasher>(value: f32, state: &mut H) {
+    let bits = if value.is_nan() {
+        f32::NAN.to_bits()
+    } else {
+        normalize_zero(value).to_bits()
+    };
 
-        let hi_boundary = match self.hi_boundary {
-            Boundary::Open => ")",
-            Boundary::Closed => "]",
-        };
+    bits.hash(state);
+}
 
-        write!(f, "{lo_boundary}{lo}, {hi}{hi_boundary}")
+impl PartialEq for Interval {
+    fn eq(&self, other: &Self) -> bool {
+        self.lo_boundary == other.lo_boundary
+            && self.hi_boundary == other.hi_boundary
+            && cmp_endpoint_values(self.lo, other.lo) == Ordering::Equal
+            && cmp_endpoint_values(self.hi, other.hi) == Ordering::Equal
     }
 }
 
-impl fmt::Debug for Interval {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{self}")
+impl Eq for Interval {}
+
+impl Hash for Interval {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.lo_boundary.hash(state);
+        hash_endpoint_value(self.lo, state);
+        hash_endpoint_value(self.hi, state);
+        self.hi_boundary.hash(state);
     }
 }
 
-impl Serialize for Interval {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.collect_str(self)
+/// Formats an endpoint with the infinities as `-Inf` and `Inf`, rounded to `decimals` if it's given.
+/// The trailing zeros of the rounded value are left out, so `9.999999` with 2 decimals is `10`.
+fn format_endpoint(value: f32, decimals: Option<usize>) -> String {
+    if value.is_infinite() {
+        return if value > 0.0 { "Inf" } else { "-Inf" }.to_owned();
     }
-}
 
-#[derive(PartialEq, Clone)]
-pub struct MultiInterval {
-    /// `intervals` is always sorted in ascending order and there are no overlapping intervals
-    pub(crate) intervals: Vec<Interval>,
-}
+    let Some(decimals) = decimals else {
+        return normalize_zero(value).to_string();
+    };
 
-// TODO: There could be a more pragmatic rust solution
-#[derive(Debug)]
-pub enum IntervalError {
-    LoIsGreaterThanHi,
+    let rounded = format!("{value:.decimals$}");
+    let trimmed = if rounded.contains('.') {
+        rounded.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &rounded
+    };
+
+    // Small negative values are rounded to `-0`
+    if trimmed == "-0" { "0" } else { trimmed }.to_owned()
 }
 
-// TODO: implement a simplifier function, which
-//          - removes empty intervals, like (0,0)
-//          - merges bordering intervals, like [10, 20] [20, 30] becomes [10, 30]
-impl MultiInterval {
-    pub fn new(
-        lo_boundary: Boundary,
-        lo: f32,
-        hi: f32,
-        hi_boundary: Boundary,
-    ) -> Result<Self, IntervalError> {
-        Ok(Self {
-            intervals: vec![Interval::new(lo_boundary, lo, hi, hi_boundary)?],
-        })
+impl Interval {
+    fn format(&self, decimals: Option<usize>) -> String {
+        format!(
+            "{}{}, {}{}",
+            self.lo_boundary.open_bracket(),
+            format_endpoint(self.lo, decimals),
+            format_endpoint(self.hi, decimals),
+            self.hi_boundary.close_bracket()
+        )
+    }
+
+    /// Like `Display`, but the endpoints are rounded to `decimals`, so float artifacts like `9.999999` are not shown.
+    /// The stored values are not changed.
+    pub fn to_string_with_precision(&self, decimals: usize) -> String {
+        self.format(Some(decimals))
+    }
+
+    /// The printed interval can be parsed back to the same interval, `parse(format(self)) == self`.
+    pub fn round_trip_check(&self) -> bool {
+        parse_intervals(&self.to_string()).is_some_and(|intervals| intervals == [*self])
+    }
+}
+
+/// Parses the intervals as they are printed, like `(-Inf, 0) [5, 10]`. Returns `None` if there is anything else in the input.
+fn parse_intervals(input: &str) -> Option<Vec<Interval>> {
+    let (_, intervals) = all_consuming(many0(complete(interval_parser)))(input.trim()).ok()?;
+
+    Some(
+        intervals
+            .into_iter()
+            .flat_map(|interval| interval.intervals)
+            .collect(),
+    )
+}
+
+/// The input is not in the syntax of the intervals, like `[0, 10)`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct InvalidInterval(pub String);
+
+impl fmt::Display for InvalidInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} is not a valid interval, like [0, 10)", self.0)
+    }
+}
+
+impl std::error::Error for InvalidInterval {}
+
+/// Parses the syntax of `Display`, like `[5, 10)` or `(-Inf, 0]`, the input has to be exactly one interval.
+impl FromStr for Interval {
+    type Err = InvalidInterval;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_intervals(s).as_deref() {
+            Some([interval]) => Ok(*interval),
+            _ => Err(InvalidInterval(s.to_owned())),
+        }
+    }
+}
+
+/// Serializes an `Interval` as its string form, like `"[0, 100)"`, instead of its fields.
+///
+/// It's deserialized with `FromStr`, so a config file can contain the intervals the way they are written in the specs.
+///
+/// Example: `#[serde(with = "gpt_common::interval::interval_string")] range: Interval`
+pub mod interval_string {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use super::Interval;
+
+    pub fn serialize<S: Serializer>(interval: &Interval, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(interval)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Interval, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format(None))
+    }
+}
+
+impl fmt::Debug for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Serialize for Interval {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct MultiInterval {
+    /// `intervals` is always sorted in ascending order and there are no overlapping intervals
+    pub(crate) intervals: Vec<Interval>,
+}
+
+/// The uncovered parts of a domain by where they are, see [`MultiInterval::classify_gaps`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GapReport {
+    /// The uncovered values at the start of the domain, before every covered value
+    pub leading: Option<Interval>,
+    /// The uncovered values between two covered ones
+    pub interior: MultiInterval,
+    /// The uncovered values at the end of the domain, after every covered value
+    pub trailing: Option<Interval>,
+}
+
+// TODO: There could be a more pragmatic rust solution
+#[derive(Debug)]
+pub enum IntervalError {
+    LoIsGreaterThanHi,
+    /// One of the endpoints is `f32::NaN`
+    NotANumber,
+    /// One of the endpoints is infinite, but the operation needs a finite width
+    Unbounded,
+    /// The interval can't be split into zero pieces
+    ZeroPieces,
+}
+
+/// Builds an `Interval` with named boundaries, so a boundary can't be swapped with a value by accident.
+///
+/// Example: `Interval::builder().open_lo(5.0).closed_hi(10.0).build()` is `(5, 10]`.
+/// The boundaries are closed by default, and a side which is not set is unbounded.
+#[derive(Debug, Clone, Copy)]
+pub struct IntervalBuilder {
+    lo_boundary: Boundary,
+    lo: f32,
+    hi: f32,
+    hi_boundary: Boundary,
+}
+
+impl Default for IntervalBuilder {
+    fn default() -> Self {
+        Self {
+            lo_boundary: Boundary::Closed,
+            lo: f32::NEG_INFINITY,
+            hi: f32::INFINITY,
+            hi_boundary: Boundary::Closed,
+        }
+    }
+}
+
+impl IntervalBuilder {
+    #[must_use]
+    pub const fn lo(self, lo: f32) -> Self {
+        Self { lo, ..self }
+    }
+
+    #[must_use]
+    pub const fn hi(self, hi: f32) -> Self {
+        Self { hi, ..self }
+    }
+
+    #[must_use]
+    pub const fn open_lo(self, lo: f32) -> Self {
+        Self {
+            lo_boundary: Boundary::Open,
+            lo,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub const fn closed_lo(self, lo: f32) -> Self {
+        Self {
+            lo_boundary: Boundary::Closed,
+            lo,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub const fn open_hi(self, hi: f32) -> Self {
+        Self {
+            hi,
+            hi_boundary: Boundary::Open,
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub const fn closed_hi(self, hi: f32) -> Self {
+        Self {
+            hi,
+            hi_boundary: Boundary::Closed,
+            ..self
+        }
+    }
+
+    /// Validates the endpoints like `Interval::new`.
+    pub fn build(self) -> Result<Interval, IntervalError> {
+        Interval::new(self.lo_boundary, self.lo, self.hi, self.hi_boundary)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EndpointKind {
+    Lo,
+    Hi,
+}
+
+/// One end of an interval, ordered by its position on the number line.
+///
+/// At the same value the order is `5)` < `[5` < `5]` < `(5`, because an open hi ends right before the value,
+/// an open lo starts right after it, and a closed lo is before a closed hi, so `[5, 5]` is in order.
+#[derive(Debug, Clone, Copy)]
+pub struct Endpoint {
+    pub value: f32,
+    pub boundary: Boundary,
+    pub kind: EndpointKind,
+}
+
+impl Endpoint {
+    /// The position of the endpoint relative to its value
+    const fn tie_break_rank(&self) -> u8 {
+        match (self.kind, self.boundary) {
+            (EndpointKind::Hi, Boundary::Open) => 0,
+            (EndpointKind::Lo, Boundary::Closed) => 1,
+            (EndpointKind::Hi, Boundary::Closed) => 2,
+            (EndpointKind::Lo, Boundary::Open) => 3,
+        }
+    }
+}
+
+impl PartialEq for Endpoint {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Endpoint {}
+
+impl PartialOrd for Endpoint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Endpoint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_endpoint_values(self.value, other.value)
+            .then_with(|| self.tie_break_rank().cmp(&other.tie_break_rank()))
+    }
+}
+
+/// The reason why two intervals don't intersect
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DisjointReason {
+    /// There is a gap between the intervals, like `[0, 10]` and `[20, 30]`
+    Separated,
+    /// The intervals share an endpoint, but at least one of them excludes it, like `[0, 10)` and `[10, 20]`
+    ExcludedEndpoint,
+    /// At least one of the intervals is empty, like `(0, 0)`
+    Empty,
+}
+
+impl MultiInterval {
+    pub fn new(
+        lo_boundary: Boundary,
+        lo: f32,
+        hi: f32,
+        hi_boundary: Boundary,
+    ) -> Result<Self, IntervalError> {
+        Ok(Self {
+            intervals: vec![Interval::new(lo_boundary, lo, hi, hi_boundary)?],
+        })
     }
 
     pub fn from_interval(interval: Interval) -> Self {
@@ -287,6 +1118,35 @@ impl MultiInterval {
         multi_interval
     }
 
+    /// Creates a multiinterval without the sorting and merging of `from_intervals`, for the hot paths.
+    ///
+    /// The intervals must already be non-empty, sorted, and separated by gaps, like the results of other operations.
+    /// This is only checked in debug builds.
+    pub(crate) fn from_sorted_unchecked(intervals: Vec<Interval>) -> Self {
+        let multi_interval = Self { intervals };
+
+        debug_assert!(
+            multi_interval.is_simplified(),
+            "MultiInterval::from_sorted_unchecked got unsorted or overlapping intervals: {multi_interval}"
+        );
+
+        multi_interval
+    }
+
+    /// The intervals are non-empty, sorted, and there is a gap between the neighbours, like after `clean`.
+    fn is_simplified(&self) -> bool {
+        self.intervals.iter().all(|interval| !interval.is_empty())
+            && self.intervals.windows(2).all(|pair| {
+                let (left, right) = (pair[0], pair[1]);
+
+                left.hi < right.lo
+                    || (left.hi == right.lo
+                        && left.hi_boundary == Boundary::Open
+                        && right.lo_boundary == Boundary::Open)
+            })
+    }
+
+    /// The `MultiInterval` without any values, the identity of `union` and the result of an empty `intersect`.
     pub const fn new_empty() -> Self {
         Self {
             intervals: Vec::new(),
@@ -302,63 +1162,70 @@ impl MultiInterval {
             .expect("Closed point creation should not cause any errors")
     }
 
-    fn highest_hi(&self) -> f32 {
-        self.intervals
-            .last()
-            .expect("Interval should always contain an interval")
-            .hi
+    /// The `hi` of the last interval, `None` if it's empty.
+    pub fn highest_hi(&self) -> Option<f32> {
+        self.intervals.last().map(|interval| interval.hi)
     }
 
-    fn lowest_lo(&self) -> f32 {
-        self.intervals
-            .first()
-            .expect("Interval should always contain an interval")
-            .lo
+    /// The `lo` of the first interval, `None` if it's empty.
+    pub fn lowest_lo(&self) -> Option<f32> {
+        self.intervals.first().map(|interval| interval.lo)
     }
 
-    fn highest_boundary(&self) -> Boundary {
-        self.intervals
-            .last()
-            .expect("Interval should always contain an interval")
-            .hi_boundary
+    /// The `hi_boundary` of the last interval, `None` if it's empty.
+    pub fn highest_boundary(&self) -> Option<Boundary> {
+        self.intervals.last().map(|interval| interval.hi_boundary)
     }
 
-    fn lowest_boundary(&self) -> Boundary {
-        self.intervals
-            .first()
-            .expect("Interval should always contain an interval")
-            .lo_boundary
+    /// The `lo_boundary` of the first interval, `None` if it's empty.
+    pub fn lowest_boundary(&self) -> Option<Boundary> {
+        self.intervals.first().map(|interval| interval.lo_boundary)
+    }
+
+    /// The smallest interval enclosing all the intervals, `None` if it's empty.
+    ///
+    /// Example: the bounding interval of `(-Inf, 0) [5, 5] (10, 20]` is `(-Inf, 20]`
+    pub fn bounding_interval(&self) -> Option<Interval> {
+        let (first, last) = (self.intervals.first()?, self.intervals.last()?);
+
+        Some(Interval {
+            lo_boundary: first.lo_boundary,
+            lo: first.lo,
+            hi: last.hi,
+            hi_boundary: last.hi_boundary,
+        })
     }
 
     pub fn is_empty(&self) -> bool {
         self.intervals.is_empty()
     }
 
+    /// A single `[x, x]` interval, so a variable constrained by it is effectively a constant.
     pub fn is_single_point(&self) -> bool {
         self.intervals.len() == 1 && self.intervals[0].is_single_point()
     }
 
+    /// A single `(-Inf, Inf)` interval, so a variable constrained by it is effectively unconstrained.
+    pub fn is_whole_line(&self) -> bool {
+        self.intervals.len() == 1 && self.intervals[0] == Interval::whole_line()
+    }
+
     #[must_use]
     pub fn complement(&self) -> Self {
-        if self.intervals.is_empty() {
+        let (Some(first), Some(last)) = (self.intervals.first(), self.intervals.last()) else {
             return Self {
-                intervals: vec![Interval {
-                    lo_boundary: Boundary::Open,
-                    lo: f32::NEG_INFINITY,
-                    hi: f32::INFINITY,
-                    hi_boundary: Boundary::Open,
-                }],
+                intervals: vec![Interval::whole_line()],
             };
-        }
+        };
 
         let mut new_intervals = Vec::new();
 
-        if self.lowest_lo() != f32::NEG_INFINITY {
+        if first.lo != f32::NEG_INFINITY {
             new_intervals.push(Interval {
                 lo_boundary: Boundary::Open,
                 lo: f32::NEG_INFINITY,
-                hi: self.lowest_lo(),
-                hi_boundary: self.lowest_boundary().inverse(),
+                hi: first.lo,
+                hi_boundary: first.lo_boundary.inverse(),
             });
         }
 
@@ -379,184 +1246,661 @@ impl MultiInterval {
                 .collect(),
         );
 
-        if self.highest_hi() != f32::INFINITY {
+        if last.hi != f32::INFINITY {
             new_intervals.push(Interval {
-                lo_boundary: self.highest_boundary().inverse(),
-                lo: self.highest_hi(),
+                lo_boundary: last.hi_boundary.inverse(),
+                lo: last.hi,
                 hi: f32::INFINITY,
                 hi_boundary: Boundary::Open,
             });
         }
 
-        Self {
-            intervals: new_intervals,
-        }
+        // `self` can have empty or touching pieces, like a parsed `(0, 0)`, so the gaps are simplified
+        Self::from_intervals(new_intervals)
     }
 
-    fn clean(&mut self) {
-        // Removing empty intervals
-        self.intervals.retain(|x| !x.is_empty());
-
-        // Sort the intervals
-        self.intervals.sort_by(Interval::lo_cmp);
-
-        // Merging overlapping intervals
-        if self.intervals.len() >= 2 {
-            // Start at the back and go backwards, because then we can remove indicies easily from the end
-            for i in (0..=(self.intervals.len() - 2)).rev() {
-                let (left, right) = (self.intervals[i], self.intervals[i + 1]);
-
-                // left.lo <= right.lo beacuse of the sort
-                if left.intersects_with(&right) {
-                    if left.hi_cmp(&right) == Ordering::Greater {
-                        self.intervals[i] = Interval {
-                            lo_boundary: left.lo_boundary,
-                            lo: left.lo,
-                            hi: left.hi,
-                            hi_boundary: left.hi_boundary,
-                        };
-                    } else {
-                        self.intervals[i] = Interval {
-                            lo_boundary: left.lo_boundary,
-                            lo: left.lo,
-                            hi: right.hi,
-                            hi_boundary: right.hi_boundary,
-                        };
-                    }
-                    self.intervals.remove(i + 1);
-                }
+    /// All the values `x` for which `x op value` holds.
+    ///
+    /// Example: `< 10` is `(-Inf, 10)`, `!= 0` is `(-Inf, 0) (0, Inf)`
+    pub fn from_comparison(op: &BinaryOp, value: f32) -> Self {
+        match op {
+            BinaryOp::NotEqual => Self::new_closed_point(value).complement(),
+            BinaryOp::Equal => Self::new_closed_point(value),
+            BinaryOp::LessThan => Self::from_interval(Interval::less_than(value)),
+            BinaryOp::GreaterThan => Self::from_interval(Interval::greater_than(value)),
+            BinaryOp::LessThanEqualTo => {
+                Self::from_interval(Interval::at_most(value, Boundary::Closed))
+            }
+            BinaryOp::GreaterThanEqualTo => {
+                Self::from_interval(Interval::at_least(value, Boundary::Closed))
             }
         }
     }
-}
 
-impl Intersectable for MultiInterval {
-    // TODO: This could be sped up, because the interval Vecs are sorted
-    // It could be a step-by-step comparison
-    fn intersects_with(&self, other: &Self) -> bool {
-        for x in &self.intervals {
-            for y in &other.intervals {
-                if x.intersects_with(y) {
-                    return true;
-                }
-            }
-        }
+    /// The complement relative to `universe` instead of `(-Inf, Inf)`, `universe \ self`.
+    ///
+    /// Example: the complement of `[0, 10]` in `[0, 100]` is `(10, 100]`
+    #[must_use]
+    pub fn complement_in(&self, universe: &Interval) -> Self {
+        Self::from_interval(*universe).difference(self)
+    }
 
-        false
+    /// The parts of `self` which are not in `other`, `self \ other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.intersect(&other.complement())
+            .map_or_else(Self::new_empty, |difference| {
+                Self::from_intervals(difference.intervals)
+            })
     }
 
-    fn intersect(&self, other: &Self) -> Option<Self> {
-        let mut intersected_intervals: Vec<Interval> = self
-            .intervals
-            .iter()
-            .flat_map(|x| other.intervals.iter().map(|y| x.intersect(y)))
-            .flatten()
-            .collect();
+    /// The sum of the widths of the intervals, `f32::INFINITY` if any of them is unbounded.
+    pub fn total_width(&self) -> f32 {
+        self.intervals.iter().map(Interval::width).sum()
+    }
 
-        intersected_intervals.sort_unstable_by(|a, b| {
-            a.lo.partial_cmp(&b.lo)
-                .expect("f32::NaN should not be the lo value of intervals")
-        });
+    /// The ratio of the `universe` covered by `self`, between `0` and `1`.
+    ///
+    /// Example: `[0, 30)` covers `0.25` of `[0, 120]`.
+    /// A single point universe is either fully covered or not at all.
+    /// Returns `f32::NAN` for an unbounded or empty universe, because there is no meaningful ratio.
+    pub fn coverage_ratio(&self, universe: &Interval) -> f32 {
+        if universe.is_empty() || universe.width().is_infinite() {
+            return f32::NAN;
+        }
 
-        if intersected_intervals.is_empty() {
-            None
-        } else {
-            Some(Self {
-                intervals: intersected_intervals,
-            })
+        let covered = self.intersect_interval(universe);
+
+        if universe.is_single_point() {
+            return if covered.is_empty() { 0.0 } else { 1.0 };
         }
+
+        (covered.total_width() / universe.width()).clamp(0.0, 1.0)
     }
-}
 
-impl fmt::Display for MultiInterval {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.is_empty() {
-            return Ok(());
+    /// The uncovered parts of the `domain`, split into the ones at its edges and the ones between the covered values.
+    ///
+    /// Example: `[0, 10] [20, 30]` in `[-5, 50]` has the leading gap `[-5, 0)`, the interior gap `(10, 20)` and the trailing gap `(30, 50]`.
+    /// An edge gap usually means a forgotten range of values, an interior one a missing or mistyped case.
+    /// If nothing is covered, the whole domain is the leading gap.
+    pub fn classify_gaps(&self, domain: &Interval) -> GapReport {
+        let mut gaps = self.complement_in(domain).intervals;
+
+        let leading = gaps
+            .first()
+            .filter(|gap| gap.lo_cmp(domain) == Ordering::Equal)
+            .copied();
+        if leading.is_some() {
+            gaps.remove(0);
         }
-        write!(f, "{}", self.intervals[0])?;
 
-        for interval in self.intervals.iter().skip(1) {
-            write!(f, " {interval}")?;
+        let trailing = gaps
+            .last()
+            .filter(|gap| gap.hi_cmp(domain) == Ordering::Equal)
+            .copied();
+        if trailing.is_some() {
+            gaps.pop();
         }
 
-        Ok(())
+        GapReport {
+            leading,
+            // The gaps are still sorted and separated
+            interior: Self::from_sorted_unchecked(gaps),
+            trailing,
+        }
     }
-}
 
-impl Serialize for MultiInterval {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.collect_str(self)
+    /// There is no value which is in both `self` and `other`, the opposite of `intersects_with`.
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        !self.intersects_with(other)
     }
-}
 
-impl fmt::Debug for MultiInterval {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{self}")
-    }
-}
+    /// Intersects `self` with each of `others`, like calling `intersect` in a loop, but cheaper.
+    ///
+    /// Both sides are sorted, so the intervals are intersected by walking them side by side, instead of every pair.
+    /// The intersections are collected in a buffer reused between the elements, every result is allocated only once.
+    /// They are still simplified, because the pieces of an unsimplified side, like `[0, 5) [5, 10]`, can touch each other.
+    pub fn intersect_each(&self, others: &[Self]) -> Vec<Option<Self>> {
+        let mut buffer = Vec::new();
 
-impl Unionable<Self, Self> for MultiInterval {
-    fn union(&self, other: &Self) -> Self {
-        let mut intervals = self.intervals.clone();
-        intervals.append(&mut other.intervals.clone());
+        others
+            .iter()
+            .map(|other| {
+                buffer.clear();
 
-        let mut multi_interval = Self { intervals };
-        multi_interval.clean();
+                let (mut i, mut j) = (0, 0);
+                while i < self.intervals.len() && j < other.intervals.len() {
+                    let (left, right) = (&self.intervals[i], &other.intervals[j]);
 
-        multi_interval
-    }
-}
+                    if let Some(intersection) = left.intersect(right) {
+                        buffer.push(intersection);
+                    }
 
-impl Unionable<Self, MultiInterval> for Interval {
-    fn union(&self, other: &Self) -> MultiInterval {
-        MultiInterval::from_intervals(vec![*self, *other])
+                    // The one ending first can't intersect with the next interval of the other side
+                    if left.hi_cmp(right) == Ordering::Less {
+                        i += 1;
+                    } else {
+                        j += 1;
+                    }
+                }
+
+                (!buffer.is_empty()).then(|| Self::from_intervals(buffer.clone()))
+            })
+            .collect()
     }
-}
 
-#[cfg(test)]
-pub(crate) mod test {
-    use std::{cmp::Ordering, str::FromStr};
+    /// Intersects `self` with a single interval, like clamping it to a domain, without wrapping `other` in a multiinterval.
+    ///
+    /// Example: `(-Inf, 0) [5, 10] (20, 30]` intersected with `[-5, 25]` is `[-5, 0) [5, 10] (20, 25]`.
+    /// The intervals are sorted, so the walk stops at the first one starting after `other`. The result can be empty.
+    pub fn intersect_interval(&self, other: &Interval) -> Self {
+        let intervals = self
+            .intervals
+            .iter()
+            .take_while(|interval| interval.lo <= other.hi)
+            .filter_map(|interval| interval.intersect(other))
+            .collect();
 
-    use nom::{combinator::complete, multi::many0};
-    use pretty_assertions::assert_eq;
-    use rstest::rstest;
-    use Ordering::{Equal, Greater, Less};
+        // The pieces of an unsimplified `self` can still touch each other, like `[0, 5) [5, 10]`
+        Self::from_intervals(intervals)
+    }
 
-    use super::{Intersectable, Interval, MultiInterval};
-    use crate::parser::interval;
+    /// Compares the simplified forms, so the same set of values is equal regardless of its representation.
+    ///
+    /// Example: `[0, 10] [10, 20]` and `[0, 20]` are equal, even if they were not simplified by the constructors.
+    pub fn eq_canonical(&self, other: &Self) -> bool {
+        Self::from_intervals(self.intervals.clone())
+            == Self::from_intervals(other.intervals.clone())
+    }
 
-    pub fn int(input: &str) -> Interval {
-        let (_, x) = interval(input).unwrap();
-        *x.intervals.first().unwrap()
+    /// The closest value of any of the intervals to `point`, see `Interval::nearest_inside`.
+    ///
+    /// Example: for `[0, 10] (20, 30]` the nearest value to `14` is `10`, and to `16` is `21` with the precision of `1`.
+    /// On a tie the lower value is chosen. Returns `None` for an empty multiinterval.
+    pub fn nearest_inside(&self, point: f32, precision: f32) -> Option<f32> {
+        self.intervals
+            .iter()
+            .filter_map(|interval| interval.nearest_inside(point, precision))
+            .min_by(|a, b| (a - point).abs().total_cmp(&(b - point).abs()))
     }
 
-    pub fn multiint(input: &str) -> MultiInterval {
-        let (_, x) = many0(complete(interval))(input.trim()).unwrap();
-        let intervals = x
-            .into_iter()
-            .map(|y| *y.intervals.first().unwrap())
+    /// The part of `self` inside `domain`, and whether anything was cut off.
+    ///
+    /// Example: `[-10, 10]` clamped to `[0, 100]` is `[0, 10]`, and it was truncated.
+    pub fn clamp_to(&self, domain: &Interval) -> (Self, bool) {
+        let domain = Self::from_interval(*domain);
+        let clamped = self.intersect(&domain).unwrap_or_else(Self::new_empty);
+        let is_truncated = !self.is_subset_of(&domain);
+
+        (clamped, is_truncated)
+    }
+
+    /// Every value of `self` is in `other` as well. The empty multiinterval is the subset of everything.
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        other.contains_multi(self)
+    }
+
+    /// Every value of `other` is in `self` as well, the same as `other.is_subset_of(self)`.
+    ///
+    /// Walks the sorted intervals of both sides once: every interval of `other` has to be inside a single interval of `self`,
+    /// because the intervals of `self` are separated by gaps. Touching an open boundary is not enough,
+    /// `[0, 10)` doesn't contain `[5, 10]`.
+    pub fn contains_multi(&self, other: &Self) -> bool {
+        let mut containers = self.intervals.iter().peekable();
+
+        other
+            .intervals
+            .iter()
+            .filter(|interval| !interval.is_empty())
+            .all(|interval| {
+                // The intervals ending before `interval` can't contain it, or any of the later ones
+                while containers
+                    .next_if(|container| container.hi_cmp(interval) == Ordering::Less)
+                    .is_some()
+                {}
+
+                containers
+                    .peek()
+                    .is_some_and(|container| container.lo_cmp(interval) != Ordering::Greater)
+            })
+    }
+
+    /// The parts which are covered by exactly one of `self` and `other`, `(self \ other) ∪ (other \ self)`.
+    #[must_use]
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.difference(other).union(&other.difference(self))
+    }
+
+    /// Splits at `point` into the values `< point` and the values `>= point`, the point itself goes to the right side.
+    ///
+    /// Example: splitting `[0, 10] [20, 30]` at `5` results in `[0, 5)` and `[5, 10] [20, 30]`.
+    /// Splitting at an existing boundary doesn't create empty pieces, `[0, 5) [5, 10]` at `5` becomes `[0, 5)` and `[5, 10]`.
+    pub fn split_at(&self, point: f32) -> (Self, Self) {
+        let below = Interval {
+            lo_boundary: Boundary::Open,
+            lo: f32::NEG_INFINITY,
+            hi: point,
+            hi_boundary: Boundary::Open,
+        };
+        let above = Interval {
+            lo_boundary: if point.is_finite() {
+                Boundary::Closed
+            } else {
+                Boundary::Open
+            },
+            lo: point,
+            hi: f32::INFINITY,
+            hi_boundary: Boundary::Open,
+        };
+
+        let split = |half: &Interval| {
+            Self::from_intervals(
+                self.intervals
+                    .iter()
+                    .filter_map(|interval| interval.intersect(half))
+                    .collect(),
+            )
+        };
+
+        (split(&below), split(&above))
+    }
+
+    /// Cuts the intervals at the `points` inside them, into adjacent pieces, without changing the covered values.
+    /// Like in `split_at`, a point goes to the piece on its right.
+    ///
+    /// The pieces are returned as intervals, not as a multiinterval, because they touch each other,
+    /// so `from_intervals` would merge them back. The points outside of the intervals, or on their endpoints are ignored,
+    /// so there are no empty pieces.
+    ///
+    /// Example: `[0, 10] (20, 30)` cut at `5` and `25` is `[0, 5) [5, 10] (20, 25) [25, 30)`.
+    pub fn densify(&self, points: &[f32]) -> Vec<Interval> {
+        let mut points = points
+            .iter()
+            .copied()
+            .filter(|point| point.is_finite())
+            .collect::<Vec<_>>();
+        points.sort_by(f32::total_cmp);
+        points.dedup();
+
+        self.intervals
+            .iter()
+            .flat_map(|interval| {
+                let cuts = points
+                    .iter()
+                    .copied()
+                    .filter(|point| interval.lo < *point && *point < interval.hi)
+                    .collect::<Vec<_>>();
+
+                let los = std::iter::once((interval.lo_boundary, interval.lo))
+                    .chain(cuts.iter().map(|cut| (Boundary::Closed, *cut)));
+                let his = cuts
+                    .iter()
+                    .map(|cut| (*cut, Boundary::Open))
+                    .chain(std::iter::once((interval.hi, interval.hi_boundary)));
+
+                los.zip(his)
+                    .map(|((lo_boundary, lo), (hi, hi_boundary))| Interval {
+                        lo_boundary,
+                        lo,
+                        hi,
+                        hi_boundary,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Like `Display`, but the endpoints are rounded to `decimals`, see `Interval::to_string_with_precision`.
+    pub fn to_string_with_precision(&self, decimals: usize) -> String {
+        self.intervals
+            .iter()
+            .map(|interval| interval.to_string_with_precision(decimals))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The printed multi-interval can be parsed back to the same multi-interval, `parse(format(self)) == self`.
+    pub fn round_trip_check(&self) -> bool {
+        parse_intervals(&self.to_string())
+            .is_some_and(|intervals| Self::from_intervals(intervals) == *self)
+    }
+
+    /// Keeps only the intervals for which `f` returns `true`.
+    /// Removing intervals keeps them sorted and non-overlapping, so there is nothing to simplify.
+    ///
+    /// Example: `multi_interval.retain(|interval| interval.width() >= 0.01)` drops the insignificant fragments.
+    pub fn retain(&mut self, f: impl Fn(&Interval) -> bool) {
+        self.intervals.retain(f);
+    }
+
+    /// Like `retain`, but returns the kept intervals as a new multiinterval.
+    #[must_use]
+    pub fn filter(&self, f: impl Fn(&Interval) -> bool) -> Self {
+        let mut filtered = self.clone();
+        filtered.retain(f);
+        filtered
+    }
+
+    /// Rewrites the boundaries of every interval with `f`. Infinite endpoints stay open.
+    ///
+    /// The result is simplified, because the new boundaries can make the intervals overlap, or become empty,
+    /// like `(0, 5) (5, 10)` becoming `[0, 10]` when every boundary is closed.
+    #[must_use]
+    pub fn map_boundaries(&self, f: impl Fn(Boundary) -> Boundary) -> Self {
+        let intervals = self
+            .intervals
+            .iter()
+            .map(|interval| {
+                Interval::new(
+                    f(interval.lo_boundary),
+                    interval.lo,
+                    interval.hi,
+                    f(interval.hi_boundary),
+                )
+                .expect("Changing the boundaries doesn't change lo and hi, so it should be a valid interval")
+            })
+            .collect();
+
+        Self::from_intervals(intervals)
+    }
+
+    /// Applies the monotonic `f` to both endpoints of every interval, like converting a domain from Celsius to Fahrenheit.
+    ///
+    /// If `f` is decreasing, the endpoints and their boundaries are swapped, so `lo <= hi` still holds.
+    /// The result is simplified, because the mapped intervals can be in a different order, or touch each other.
+    ///
+    /// Example: `[0, 10) (20, Inf)` mapped with `|x| -x` becomes `(-Inf, -20) (-10, 0]`
+    ///
+    /// Returns `IntervalError::NotANumber` if `f` maps an endpoint to `f32::NaN`, like `|x| x * 0.0` an infinite one.
+    pub fn map_values(&self, f: impl Fn(f32) -> f32) -> Result<Self, IntervalError> {
+        let intervals = self
+            .intervals
+            .iter()
+            .map(|interval| {
+                let (lo, hi) = (f(interval.lo), f(interval.hi));
+
+                if lo <= hi {
+                    Interval::new(interval.lo_boundary, lo, hi, interval.hi_boundary)
+                } else {
+                    Interval::new(interval.hi_boundary, hi, lo, interval.lo_boundary)
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::from_intervals(intervals))
+    }
+
+    /// Every finite boundary becomes closed.
+    ///
+    /// Example: `(0, 5) (5, Inf)` becomes `[0, Inf)`
+    #[must_use]
+    pub fn with_all_closed(&self) -> Self {
+        self.map_boundaries(|_| Boundary::Closed)
+    }
+
+    /// Every boundary becomes open, the single points disappear.
+    ///
+    /// Example: `[0, 5] [7, 7]` becomes `(0, 5)`
+    #[must_use]
+    pub fn with_all_open(&self) -> Self {
+        self.map_boundaries(|_| Boundary::Open)
+    }
+
+    /// Draws the intervals on a number line between `min` and `max`, which is `width` characters wide.
+    ///
+    /// The intervals are drawn with `=`, with their boundaries at the edges, and a single point is drawn as `|`.
+    /// The intervals reaching beyond `min` or `max` are cut off, and marked with `<` or `>`.
+    /// Example: `[0, 10] (20, 30)` between `0` and `30` with the width of `31`:
+    /// ```text
+    /// [=========]---------(=========)
+    /// ```
+    pub fn to_ascii_timeline(&self, min: f32, max: f32, width: usize) -> String {
+        let mut line = vec!['-'; width];
+
+        if width == 0 || max <= min {
+            return line.into_iter().collect();
+        }
+
+        let last_column = width - 1;
+        let column = |value: f32| -> usize {
+            let ratio = (value.clamp(min, max) - min) / (max - min);
+            ((ratio * last_column as f32).round() as usize).min(last_column)
+        };
+
+        for interval in &self.intervals {
+            if interval.hi < min || max < interval.lo {
+                continue;
+            }
+
+            let (lo_column, hi_column) = (column(interval.lo), column(interval.hi));
+            for c in &mut line[lo_column..=hi_column] {
+                *c = '=';
+            }
+
+            line[lo_column] = match interval.lo_boundary {
+                _ if interval.lo < min => '<',
+                boundary => boundary.open_bracket(),
+            };
+            line[hi_column] = match interval.hi_boundary {
+                _ if max < interval.hi => '>',
+                _ if lo_column == hi_column => '|',
+                boundary => boundary.close_bracket(),
+            };
+        }
+
+        line.into_iter().collect()
+    }
+
+    /// Simplifies the intervals, so the `intervals` invariant holds
+    ///     - removes empty intervals, like (0,0)
+    ///     - merges overlapping and bordering intervals, like `[10, 20] [15, 30]` or `[10, 20) [20, 30]` becomes `[10, 30]`
+    fn clean(&mut self) {
+        // Removing empty intervals
+        self.intervals.retain(|x| !x.is_empty());
+
+        // Sort the intervals
+        self.intervals.sort_by(Interval::lo_cmp);
+
+        // Merging overlapping intervals
+        // Going forward, so an interval containing multiple later ones is merged with all of them, like `[0, 10] [1, 2] [5, 6]`
+        let mut merged: Vec<Interval> = Vec::with_capacity(self.intervals.len());
+        for interval in self.intervals.drain(..) {
+            match merged.last_mut() {
+                // last.lo <= interval.lo beacuse of the sort
+                // Touching intervals are merged too, like [10, 20) [20, 30]
+                Some(last) if last.intersects_with(&interval) || last.touches(&interval) => {
+                    if last.hi_cmp(&interval) == Ordering::Less {
+                        last.hi = interval.hi;
+                        last.hi_boundary = interval.hi_boundary;
+                    }
+                }
+                _ => merged.push(interval),
+            }
+        }
+        self.intervals = merged;
+    }
+}
+
+impl Intersectable for MultiInterval {
+    /// Walks the sorted intervals of both sides step-by-step, always stepping past the interval which ends first.
+    fn intersects_with(&self, other: &Self) -> bool {
+        let mut xs = self.intervals.iter().peekable();
+        let mut ys = other.intervals.iter().peekable();
+
+        while let (Some(x), Some(y)) = (xs.peek(), ys.peek()) {
+            if x.intersects_with(y) {
+                return true;
+            }
+
+            if x.hi_cmp(y) == Ordering::Less {
+                xs.next();
+            } else {
+                ys.next();
+            }
+        }
+
+        false
+    }
+
+    fn intersect(&self, other: &Self) -> Option<Self> {
+        // `from_intervals` sorts the intersections
+        let intersected_intervals: Vec<Interval> = self
+            .intervals
+            .iter()
+            .flat_map(|x| other.intervals.iter().map(|y| x.intersect(y)))
+            .flatten()
             .collect();
 
-        MultiInterval::from_intervals(intervals)
+        if intersected_intervals.is_empty() {
+            None
+        } else {
+            Some(Self::from_intervals(intersected_intervals))
+        }
+    }
+}
+
+/// Parses the intervals separated by whitespace, like `(-Inf, 0) [5, 10]`, the empty input is the empty set.
+impl FromStr for MultiInterval {
+    type Err = InvalidInterval;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_intervals(s)
+            .map(Self::from_intervals)
+            .ok_or_else(|| InvalidInterval(s.to_owned()))
     }
+}
 
-    impl FromStr for Interval {
-        type Err = ();
+/// The reason why [`parse_multi_interval`] can't parse the input
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseMultiIntervalError {
+    /// A piece is not in the syntax of the intervals, like `[0, 10` or `[10, 0]`
+    InvalidPiece(String),
+    /// Two of the intervals have common values, like `[0, 5]` and `[5, 10]`
+    Overlapping(Interval, Interval),
+}
 
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            Ok(int(s))
+impl fmt::Display for ParseMultiIntervalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPiece(piece) => {
+                write!(f, "{piece:?} is not a valid interval, like [0, 10)")
+            }
+            Self::Overlapping(left, right) => write!(f, "{left} and {right} overlap"),
         }
     }
+}
+
+impl std::error::Error for ParseMultiIntervalError {}
+
+/// Parses the intervals separated by whitespace, like `(-Inf, 0) [5, 10]`, the empty input is the empty set.
+///
+/// Unlike `FromStr`, which merges the overlapping intervals, an overlap is an error, because it's usually a typo in a config file.
+/// The intervals can be in any order. The touching ones, like `[0, 5) [5, 10]`, are merged, and the empty ones, like `(5, 5)`, are dropped.
+pub fn parse_multi_interval(input: &str) -> Result<MultiInterval, ParseMultiIntervalError> {
+    let mut intervals = Vec::new();
+    let mut rest = input.trim_start();
+
+    while !rest.is_empty() {
+        let Ok((remaining, parsed)) = complete(interval_parser)(rest) else {
+            // The invalid piece probably lasts until the next closing bracket
+            let piece_end = rest.find([']', ')']).map_or(rest.len(), |idx| idx + 1);
+            return Err(ParseMultiIntervalError::InvalidPiece(
+                rest[..piece_end].to_owned(),
+            ));
+        };
+
+        intervals.extend(parsed.intervals);
+        rest = remaining.trim_start();
+    }
+
+    // An empty piece between two overlapping ones would hide the overlap from the neighbour check
+    intervals.retain(|interval| !interval.is_empty());
+    intervals.sort_by(Interval::lo_cmp);
+    if let Some([left, right]) = intervals
+        .windows(2)
+        .find(|pair| pair[0].intersects_with(&pair[1]))
+    {
+        return Err(ParseMultiIntervalError::Overlapping(*left, *right));
+    }
+
+    Ok(MultiInterval::from_intervals(intervals))
+}
 
-    impl FromStr for MultiInterval {
-        type Err = ();
+impl fmt::Display for MultiInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+        write!(f, "{}", self.intervals[0])?;
 
-        fn from_str(s: &str) -> Result<Self, Self::Err> {
-            Ok(multiint(s))
+        for interval in self.intervals.iter().skip(1) {
+            write!(f, " {interval}")?;
         }
+
+        Ok(())
+    }
+}
+
+impl Serialize for MultiInterval {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl fmt::Debug for MultiInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl Unionable<Self, Self> for MultiInterval {
+    fn union(&self, other: &Self) -> Self {
+        let mut intervals = self.intervals.clone();
+        intervals.append(&mut other.intervals.clone());
+
+        let mut multi_interval = Self { intervals };
+        multi_interval.clean();
+
+        multi_interval
+    }
+}
+
+impl Unionable<Self, MultiInterval> for Interval {
+    fn union(&self, other: &Self) -> MultiInterval {
+        MultiInterval::from_intervals(vec![*self, *other])
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::{
+        cmp::Ordering,
+        hash::{DefaultHasher, Hash, Hasher},
+        ops::{Range, RangeInclusive},
+    };
+
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use Ordering::{Equal, Greater, Less};
+
+    use super::{
+        cmp_endpoint_values, interval_string, parse_multi_interval, Boundary, BoundarySource,
+        DisjointReason, Endpoint, EndpointKind, GapReport, Intersectable, Interval, IntervalError,
+        InvalidBracket, InvalidInterval, MultiInterval, ParseMultiIntervalError, PointLocation,
+        Unionable,
+    };
+    use crate::parser::{ast::BinaryOp, interval};
+
+    pub fn int(input: &str) -> Interval {
+        let (_, x) = interval(input).unwrap();
+        *x.intervals.first().unwrap()
+    }
+
+    pub fn multiint(input: &str) -> MultiInterval {
+        parse_multi_interval(input).unwrap()
     }
 
     #[rstest]
@@ -576,283 +1920,1776 @@ pub(crate) mod test {
     }
 
     #[rstest]
-    // self.hi equals other.lo
-    #[case("[0, 10]", "[10, 20]", true)]
-    #[case("[0, 10]", "(10, 20]", false)]
-    #[case("[0, 10)", "[10, 20]", false)]
-    #[case("[0, 10)", "(10, 20]", false)]
-    // self.lo equals other.hi
-    #[case("[10, 20]", "[0, 10]", true)]
-    #[case("(10, 20]", "[0, 10]", false)]
-    #[case("[10, 20]", "[0, 10)", false)]
-    #[case("(10, 20]", "[0, 10)", false)]
-    // self.hi inside other == other.lo inside self
-    #[case("[0, 10]", "[5, 20]", true)]
-    #[case("[0, 10]", "(5, 20]", true)]
-    #[case("[0, 10)", "[5, 20]", true)]
-    #[case("[0, 10)", "(5, 20]", true)]
-    // self.lo inside other == other.hi inside self
-    #[case("[5, 20]", "[0, 10]", true)]
-    #[case("(5, 20]", "[0, 10]", true)]
-    #[case("[5, 20]", "[0, 10)", true)]
-    #[case("(5, 20]", "[0, 10)", true)]
-    // self inside other
-    #[case("[10, 20]", "[0, 30]", true)]
-    #[case("[10, 20)", "[0, 30]", true)]
-    #[case("(10, 20]", "[0, 30]", true)]
-    #[case("(10, 20)", "[0, 30]", true)]
-    // other inside self
-    #[case("[0, 30]", "[10, 20]", true)]
-    #[case("[0, 30]", "[10, 20)", true)]
-    #[case("[0, 30]", "(10, 20]", true)]
-    #[case("[0, 30]", "(10, 20)", true)]
-    // self.lo > other.hi
-    #[case("[20, 30]", "[0, 10]", false)]
-    #[case("[20, 30]", "[0, 10)", false)]
-    #[case("(20, 30]", "[0, 10]", false)]
-    #[case("(20, 30]", "[0, 10)", false)]
-    // other.lo > self.hi
-    #[case("[0, 10]", "[20, 30]", false)]
-    #[case("[0, 10)", "[20, 30]", false)]
-    #[case("[0, 10]", "(20, 30]", false)]
-    #[case("[0, 10)", "(20, 30]", false)]
-    // self.lo == other.lo
-    #[case("[0, 10]", "[0, 20]", true)]
-    #[case("[0, 10]", "(0, 20]", true)]
-    #[case("(0, 10]", "[0, 20]", true)]
-    #[case("(0, 10]", "(0, 20]", true)]
-    // self.hi == other.hi
-    #[case("[10, 20]", "[0, 20]", true)]
-    #[case("[10, 20)", "[0, 20]", true)]
-    #[case("[10, 20]", "[0, 20)", true)]
-    #[case("[10, 20)", "[0, 20)", true)]
-    // TODO: Inf, -Inf
-    // TODO: What about empty intervals like (0,0) (0,0)?
-    fn test_interval_intersects_with(
-        #[case] this: Interval,
-        #[case] that: Interval,
+    #[case("[0, 10]", 5.0, PointLocation::Inside)]
+    #[case("[0, 10]", 0.0, PointLocation::OnLo)]
+    #[case("[0, 10]", 10.0, PointLocation::OnHi)]
+    #[case("(0, 10)", 0.0, PointLocation::OnLo)]
+    #[case("(0, 10)", 10.0, PointLocation::OnHi)]
+    #[case("[0, 10]", -1.0, PointLocation::Outside)]
+    #[case("[0, 10]", 11.0, PointLocation::Outside)]
+    #[case("[5, 5]", 5.0, PointLocation::OnLo)]
+    #[case("(5, 5)", 5.0, PointLocation::Outside)]
+    #[case("(-Inf, 10]", -1000.0, PointLocation::Inside)]
+    #[case("(-Inf, Inf)", 0.0, PointLocation::Inside)]
+    fn test_interval_locate(
+        #[case] interval: Interval,
+        #[case] point: f32,
+        #[case] expected: PointLocation,
+    ) {
+        assert_eq!(interval.locate(point), expected);
+    }
+
+    #[rstest]
+    #[case('(', Ok((Boundary::Open, EndpointKind::Lo)))]
+    #[case('[', Ok((Boundary::Closed, EndpointKind::Lo)))]
+    #[case(')', Ok((Boundary::Open, EndpointKind::Hi)))]
+    #[case(']', Ok((Boundary::Closed, EndpointKind::Hi)))]
+    #[case('{', Err(InvalidBracket('{')))]
+    #[case('5', Err(InvalidBracket('5')))]
+    fn test_boundary_from_bracket(
+        #[case] bracket: char,
+        #[case] expected: Result<(Boundary, EndpointKind), InvalidBracket>,
+    ) {
+        assert_eq!(Boundary::from_bracket(bracket), expected);
+        assert_eq!(
+            Boundary::try_from(bracket),
+            expected.map(|(boundary, _)| boundary)
+        );
+
+        if let Ok((boundary, kind)) = expected {
+            let round_trip = match kind {
+                EndpointKind::Lo => boundary.open_bracket(),
+                EndpointKind::Hi => boundary.close_bracket(),
+            };
+            assert_eq!(round_trip, bracket);
+        }
+    }
+
+    #[test]
+    fn test_endpoint_ord() {
+        let endpoint = |value, boundary, kind| Endpoint {
+            value,
+            boundary,
+            kind,
+        };
+        let hi_open = endpoint(5.0, Boundary::Open, EndpointKind::Hi);
+        let lo_closed = endpoint(5.0, Boundary::Closed, EndpointKind::Lo);
+        let hi_closed = endpoint(5.0, Boundary::Closed, EndpointKind::Hi);
+        let lo_open = endpoint(5.0, Boundary::Open, EndpointKind::Lo);
+        let smaller = endpoint(4.0, Boundary::Open, EndpointKind::Lo);
+        let bigger = endpoint(6.0, Boundary::Open, EndpointKind::Hi);
+
+        let mut endpoints = vec![lo_open, bigger, hi_closed, lo_closed, smaller, hi_open];
+        endpoints.sort();
+
+        assert_eq!(
+            endpoints,
+            vec![smaller, hi_open, lo_closed, hi_closed, lo_open, bigger]
+        );
+        assert_eq!(
+            int("[5, 5]")
+                .lo_endpoint()
+                .cmp(&int("[5, 5]").hi_endpoint()),
+            Less
+        );
+        assert_eq!(
+            int("[0, 5)")
+                .hi_endpoint()
+                .cmp(&int("[5, 10]").lo_endpoint()),
+            Less
+        );
+        assert_eq!(
+            int("[0, 5]")
+                .hi_endpoint()
+                .cmp(&int("(5, 10]").lo_endpoint()),
+            Less
+        );
+        assert_eq!(
+            int("[0, 5]")
+                .hi_endpoint()
+                .cmp(&int("[5, 10]").lo_endpoint()),
+            Greater
+        );
+    }
+
+    #[rstest]
+    #[case("[0, 10]", 10.0)]
+    #[case("(2.5, 3)", 0.5)]
+    #[case("[5, 5]", 0.0)]
+    #[case("(-Inf, 10]", f32::INFINITY)]
+    #[case("[10, Inf)", f32::INFINITY)]
+    #[case("(-Inf, Inf)", f32::INFINITY)]
+    fn test_width(#[case] interval: Interval, #[case] expected: f32) {
+        assert_eq!(interval.width(), expected);
+    }
+
+    #[rstest]
+    #[case("[0, 10]", "[5, 20]", 5.0)]
+    #[case("[0, 10]", "[2, 3]", 1.0)]
+    #[case("[0, 10]", "[10, 20]", 0.0)]
+    #[case("[0, 10)", "[10, 20]", 0.0)]
+    #[case("[0, 10]", "[20, 30]", 0.0)]
+    #[case("(5, 5)", "[0, 10]", 0.0)]
+    #[case("(-Inf, 10]", "(-Inf, 0)", f32::INFINITY)]
+    #[case("(-Inf, 10]", "[0, Inf)", 10.0)]
+    fn test_overlap(#[case] this: Interval, #[case] other: Interval, #[case] expected: f32) {
+        assert_eq!(this.overlap(&other), expected);
+        assert_eq!(other.overlap(&this), expected);
+    }
+
+    #[rstest]
+    #[case("[0, 5]", "[8, 10]", "[0, 10]")]
+    #[case("(0, 5]", "[0, 2]", "[0, 5]")]
+    #[case("(0, 5)", "(0, 5]", "(0, 5]")]
+    #[case("[2, 3]", "(0, 10)", "(0, 10)")]
+    #[case("(-Inf, 0)", "[5, 5]", "(-Inf, 5]")]
+    #[case("[5, 5]", "(10, Inf)", "[5, Inf)")]
+    #[case("(5, 5)", "[8, 10]", "[8, 10]")]
+    fn test_hull(#[case] this: Interval, #[case] other: Interval, #[case] expected: Interval) {
+        assert_eq!(
+            this.hull(&other),
+            expected,
+            "Interval.hull failed: {this} and {other}"
+        );
+        assert_eq!(other.hull(&this), expected);
+    }
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_negative_zero() {
+        let negative = Interval::new(Boundary::Closed, -0.0, 0.0, Boundary::Closed).unwrap();
+        let positive = Interval::new_closed_point(0.0);
+
+        assert!(negative.lo.is_sign_positive());
+        assert!(Interval::new_closed_point(-0.0).lo.is_sign_positive());
+        assert!(Interval::at_most(-0.0, Boundary::Closed)
+            .hi
+            .is_sign_positive());
+        assert_eq!(negative, positive);
+        assert_eq!(hash_of(&negative), hash_of(&positive));
+        assert_eq!(negative.to_string(), "[0, 0]");
+        for point in [-0.0, 0.0] {
+            assert!(negative.contains_point(point));
+            assert!(positive.contains_point(point));
+        }
+
+        // Bypassing the constructors, like the results of arithmetic on the public fields
+        let raw = Interval {
+            lo_boundary: Boundary::Closed,
+            lo: -0.0,
+            hi: -0.0,
+            hi_boundary: Boundary::Closed,
+        };
+        assert_eq!(raw, positive);
+        assert_eq!(hash_of(&raw), hash_of(&positive));
+        assert_eq!(raw.to_string(), "[0, 0]");
+        assert!(raw.contains_point(0.0));
+        assert_eq!(
+            hash_of(&MultiInterval::from_interval(raw)),
+            hash_of(&MultiInterval::new_closed_point(0.0))
+        );
+
+        let nan = Interval {
+            lo: f32::NAN,
+            ..positive
+        };
+        assert_eq!(nan, nan);
+        assert_eq!(
+            hash_of(&nan),
+            hash_of(&Interval {
+                lo: -f32::NAN,
+                ..positive
+            })
+        );
+    }
+
+    #[rstest]
+    #[case("[0, 10)", Some(0.0..10.0))]
+    #[case("[5, Inf)", Some(5.0..f32::INFINITY))]
+    #[case("[0, 10]", None)]
+    #[case("(0, 10)", None)]
+    #[case("(0, 10]", None)]
+    #[case("(-Inf, 10)", None)]
+    fn test_to_range(#[case] interval: Interval, #[case] expected: Option<Range<f32>>) {
+        assert_eq!(
+            interval.to_range(),
+            expected,
+            "Interval.to_range failed: {interval}"
+        );
+    }
+
+    #[rstest]
+    #[case("[0, 10]", Some(0.0..=10.0))]
+    #[case("[5, 5]", Some(5.0..=5.0))]
+    #[case("[0, 10)", None)]
+    #[case("(0, 10]", None)]
+    #[case("(-Inf, Inf)", None)]
+    fn test_to_range_inclusive(
+        #[case] interval: Interval,
+        #[case] expected: Option<RangeInclusive<f32>>,
+    ) {
+        assert_eq!(
+            interval.to_range_inclusive(),
+            expected,
+            "Interval.to_range_inclusive failed: {interval}"
+        );
+    }
+
+    #[rstest]
+    #[case("[0, 10]", "[10, 20]", true)]
+    #[case("[0, 10)", "[10, 20]", true)]
+    #[case("[0, 10]", "(10, 20]", true)]
+    #[case("[0, 10)", "(10, 20]", false)]
+    #[case("[0, 10]", "[5, 20]", false)]
+    #[case("[0, 10]", "[20, 30]", false)]
+    #[case("[5, 5]", "[5, 10)", true)]
+    #[case("(-Inf, 0]", "(0, Inf)", true)]
+    fn test_touches(#[case] this: Interval, #[case] other: Interval, #[case] expected: bool) {
+        assert_eq!(
+            this.touches(&other),
+            expected,
+            "Interval.touches failed: {this} and {other}"
+        );
+        assert_eq!(other.touches(&this), expected);
+    }
+
+    #[rstest]
+    #[case("[0, 10]", 5.0)]
+    #[case("[-10, 10]", 0.0)]
+    #[case("(2, 3)", 2.5)]
+    #[case("[5, 5]", 5.0)]
+    #[case("(-Inf, 10]", f32::NEG_INFINITY)]
+    #[case("[10, Inf)", f32::INFINITY)]
+    #[case("(-Inf, Inf)", 0.0)]
+    fn test_midpoint(#[case] interval: Interval, #[case] expected: f32) {
+        assert_eq!(interval.midpoint(), expected);
+    }
+
+    #[rstest]
+    #[case("[0, 10]", 5.0, 0.5, "[2.5, 7.5]")]
+    #[case("[0, 10)", 0.0, 2.0, "[0, 20)")]
+    #[case("[0, 10)", 0.0, -1.0, "(-10, 0]")]
+    #[case("(2, 4]", 3.0, -2.0, "[1, 5)")]
+    #[case("(0, 10)", 5.0, 0.0, "[5, 5]")]
+    #[case("[1, Inf)", 0.0, 3.0, "[3, Inf)")]
+    #[case("[1, Inf)", 0.0, -1.0, "(-Inf, -1]")]
+    #[case("(5, 5)", 0.0, 2.0, "(5, 5)")]
+    #[case("[0, 10]", 0.0, f32::MAX, "[0, Inf)")]
+    fn test_scale_around(
+        #[case] interval: Interval,
+        #[case] pivot: f32,
+        #[case] factor: f32,
+        #[case] expected: Interval,
+    ) {
+        assert_eq!(
+            interval.scale_around(pivot, factor).unwrap(),
+            expected,
+            "Interval.scale_around failed: {interval} around {pivot} by {factor}"
+        );
+    }
+
+    #[test]
+    fn test_scale_around_nan() {
+        let interval = int("[0, 10]");
+
+        assert!(matches!(
+            interval.scale_around(f32::INFINITY, 2.0),
+            Err(IntervalError::NotANumber)
+        ));
+        assert!(matches!(
+            interval.scale_around(5.0, f32::NAN),
+            Err(IntervalError::NotANumber)
+        ));
+        assert!(!Interval::new_closed(0.0, f32::MAX)
+            .unwrap()
+            .scale_around(0.0, 2.0)
+            .unwrap()
+            .contains_point(f32::INFINITY));
+    }
+
+    #[test]
+    fn test_width_and_midpoint_near_max() {
+        let huge = Interval::new_closed(-f32::MAX, f32::MAX).unwrap();
+        assert_eq!(huge.width(), f32::INFINITY);
+        assert_eq!(huge.midpoint(), 0.0);
+
+        let large = Interval::new_closed(f32::MAX / 2.0, f32::MAX).unwrap();
+        assert_eq!(large.width(), f32::MAX / 2.0);
+        assert_eq!(large.midpoint(), f32::MAX * 0.75);
+
+        for interval in [huge, large] {
+            assert!(!interval.width().is_nan());
+            assert!(!interval.midpoint().is_nan());
+            assert!(interval.contains_point(interval.midpoint()));
+        }
+    }
+
+    #[rstest]
+    #[case("[0, 10]", "[5, 20]", Ok("[5, 10]"))]
+    #[case("[0, 10]", "[10, 20]", Ok("[10, 10]"))]
+    #[case("[0, 10)", "[10, 20]", Err(DisjointReason::ExcludedEndpoint))]
+    #[case("[0, 10]", "(10, 20]", Err(DisjointReason::ExcludedEndpoint))]
+    #[case("(0, 10)", "(10, 20)", Err(DisjointReason::ExcludedEndpoint))]
+    #[case("[0, 10]", "[20, 30]", Err(DisjointReason::Separated))]
+    #[case("(-Inf, 0)", "(5, Inf)", Err(DisjointReason::Separated))]
+    #[case("(5, 5)", "[0, 10]", Err(DisjointReason::Empty))]
+    fn test_try_intersect(
+        #[case] this: Interval,
+        #[case] other: Interval,
+        #[case] expected: Result<&str, DisjointReason>,
+    ) {
+        let expected = expected.map(int);
+
+        assert_eq!(this.try_intersect(&other), expected);
+        assert_eq!(other.try_intersect(&this), expected);
+    }
+
+    #[rstest]
+    #[case("[0, 10]", 1.0, 5.0, 5.0)]
+    #[case("[0, 10]", 1.0, -5.0, 0.0)]
+    #[case("[0, 10]", 1.0, 15.0, 10.0)]
+    #[case("(0, 10)", 1.0, -5.0, 1.0)]
+    #[case("(0, 10)", 1.0, 0.0, 1.0)]
+    #[case("(0, 10)", 1.0, 10.0, 9.0)]
+    #[case("(0, 10)", 1.0, 15.0, 9.0)]
+    #[case("(0, 10]", 0.01, 0.0, 0.01)]
+    #[case("(-Inf, 10)", 1.0, 15.0, 9.0)]
+    #[case("(-Inf, 10)", 1.0, f32::NEG_INFINITY, f32::NEG_INFINITY)]
+    #[case("[0, Inf)", 1.0, -5.0, 0.0)]
+    #[case("(0, 0.5)", 1.0, 2.0, 0.25)]
+    #[case("(0, 0)", 1.0, 2.0, 2.0)]
+    fn test_clamp(
+        #[case] interval: Interval,
+        #[case] precision: f32,
+        #[case] point: f32,
+        #[case] expected: f32,
+    ) {
+        assert_eq!(
+            interval.clamp(point, precision),
+            expected,
+            "Interval.clamp failed: {interval}.clamp({point}, {precision}) should be {expected}",
+        );
+    }
+
+    #[rstest]
+    // self.hi equals other.lo
+    #[case("[0, 10]", "[10, 20]", true)]
+    #[case("[0, 10]", "(10, 20]", false)]
+    #[case("[0, 10)", "[10, 20]", false)]
+    #[case("[0, 10)", "(10, 20]", false)]
+    // self.lo equals other.hi
+    #[case("[10, 20]", "[0, 10]", true)]
+    #[case("(10, 20]", "[0, 10]", false)]
+    #[case("[10, 20]", "[0, 10)", false)]
+    #[case("(10, 20]", "[0, 10)", false)]
+    // self.hi inside other == other.lo inside self
+    #[case("[0, 10]", "[5, 20]", true)]
+    #[case("[0, 10]", "(5, 20]", true)]
+    #[case("[0, 10)", "[5, 20]", true)]
+    #[case("[0, 10)", "(5, 20]", true)]
+    // self.lo inside other == other.hi inside self
+    #[case("[5, 20]", "[0, 10]", true)]
+    #[case("(5, 20]", "[0, 10]", true)]
+    #[case("[5, 20]", "[0, 10)", true)]
+    #[case("(5, 20]", "[0, 10)", true)]
+    // self inside other
+    #[case("[10, 20]", "[0, 30]", true)]
+    #[case("[10, 20)", "[0, 30]", true)]
+    #[case("(10, 20]", "[0, 30]", true)]
+    #[case("(10, 20)", "[0, 30]", true)]
+    // other inside self
+    #[case("[0, 30]", "[10, 20]", true)]
+    #[case("[0, 30]", "[10, 20)", true)]
+    #[case("[0, 30]", "(10, 20]", true)]
+    #[case("[0, 30]", "(10, 20)", true)]
+    // self.lo > other.hi
+    #[case("[20, 30]", "[0, 10]", false)]
+    #[case("[20, 30]", "[0, 10)", false)]
+    #[case("(20, 30]", "[0, 10]", false)]
+    #[case("(20, 30]", "[0, 10)", false)]
+    // other.lo > self.hi
+    #[case("[0, 10]", "[20, 30]", false)]
+    #[case("[0, 10)", "[20, 30]", false)]
+    #[case("[0, 10]", "(20, 30]", false)]
+    #[case("[0, 10)", "(20, 30]", false)]
+    // self.lo == other.lo
+    #[case("[0, 10]", "[0, 20]", true)]
+    #[case("[0, 10]", "(0, 20]", true)]
+    #[case("(0, 10]", "[0, 20]", true)]
+    #[case("(0, 10]", "(0, 20]", true)]
+    // self.hi == other.hi
+    #[case("[10, 20]", "[0, 20]", true)]
+    #[case("[10, 20)", "[0, 20]", true)]
+    #[case("[10, 20]", "[0, 20)", true)]
+    #[case("[10, 20)", "[0, 20)", true)]
+    // TODO: Inf, -Inf
+    // empty intervals
+    #[case("(5, 5)", "[0, 10]", false)]
+    #[case("(5, 5]", "[0, 10]", false)]
+    #[case("[5, 5)", "[0, 10]", false)]
+    #[case("[0, 10]", "(5, 5)", false)]
+    #[case("(0, 0)", "(0, 0)", false)]
+    #[case("[5, 5]", "[0, 10]", true)]
+    #[case("[5, 5]", "[5, 5]", true)]
+    fn test_interval_intersects_with(
+        #[case] this: Interval,
+        #[case] that: Interval,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(
+            this.intersects_with(&that),
+            expected,
+            "Interval.intersects_with failed: {this}.intersects_with({that}) should be {expected}",
+        );
+    }
+
+    #[rstest]
+    // self.hi equals other.lo
+    #[case("[0, 10]", "[10, 20]", Some("[10, 10]"))]
+    #[case("[0, 10]", "(10, 20]", None)]
+    #[case("[0, 10)", "[10, 20]", None)]
+    #[case("[0, 10)", "(10, 20]", None)]
+    // self.lo equals other.hi
+    #[case("[10, 20]", "[0, 10]", Some("[10, 10]"))]
+    #[case("(10, 20]", "[0, 10]", None)]
+    #[case("[10, 20]", "[0, 10)", None)]
+    #[case("(10, 20]", "[0, 10)", None)]
+    // self.hi inside other == other.lo inside self
+    #[case("[0, 10]", "[5, 20]", Some("[5, 10]"))]
+    #[case("[0, 10]", "(5, 20]", Some("(5, 10]"))]
+    #[case("[0, 10)", "[5, 20]", Some("[5, 10)"))]
+    #[case("[0, 10)", "(5, 20]", Some("(5, 10)"))]
+    // self.lo inside other == other.hi inside self
+    #[case("[5, 20]", "[0, 10]", Some("[5, 10]"))]
+    #[case("(5, 20]", "[0, 10]", Some("(5, 10]"))]
+    #[case("[5, 20]", "[0, 10)", Some("[5, 10)"))]
+    #[case("(5, 20]", "[0, 10)", Some("(5, 10)"))]
+    // self inside other
+    #[case("[10, 20]", "[0, 30]", Some("[10, 20]"))]
+    #[case("[10, 20)", "[0, 30]", Some("[10, 20)"))]
+    #[case("(10, 20]", "[0, 30]", Some("(10, 20]"))]
+    #[case("(10, 20)", "[0, 30]", Some("(10, 20)"))]
+    // other inside self
+    #[case("[0, 30]", "[10, 20]", Some("[10, 20]"))]
+    #[case("[0, 30]", "[10, 20)", Some("[10, 20)"))]
+    #[case("[0, 30]", "(10, 20]", Some("(10, 20]"))]
+    #[case("[0, 30]", "(10, 20)", Some("(10, 20)"))]
+    // self.lo > other.hi
+    #[case("[20, 30]", "[0, 10]", None)]
+    #[case("[20, 30]", "[0, 10)", None)]
+    #[case("(20, 30]", "[0, 10]", None)]
+    #[case("(20, 30]", "[0, 10)", None)]
+    // other.lo > self.hi
+    #[case("[0, 10]", "[20, 30]", None)]
+    #[case("[0, 10)", "[20, 30]", None)]
+    #[case("[0, 10]", "(20, 30]", None)]
+    #[case("[0, 10)", "(20, 30]", None)]
+    // self.lo == other.lo
+    #[case("[0, 10]", "[0, 20]", Some("[0, 10]"))]
+    #[case("[0, 10]", "(0, 20]", Some("(0, 10]"))]
+    #[case("(0, 10]", "[0, 20]", Some("(0, 10]"))]
+    #[case("(0, 10]", "(0, 20]", Some("(0, 10]"))]
+    // self.hi == other.hi
+    #[case("[10, 20]", "[0, 20]", Some("[10, 20]"))]
+    #[case("[10, 20)", "[0, 20]", Some("[10, 20)"))]
+    #[case("[10, 20]", "[0, 20)", Some("[10, 20)"))]
+    #[case("[10, 20)", "[0, 20)", Some("[10, 20)"))]
+    // TODO: Inf, -Inf
+    // empty intervals
+    #[case("(5, 5)", "[0, 10]", None)]
+    #[case("(5, 5]", "[0, 10]", None)]
+    #[case("[5, 5)", "[0, 10]", None)]
+    #[case("[0, 10]", "(5, 5)", None)]
+    #[case("(0, 0)", "(0, 0)", None)]
+    #[case("[5, 5]", "[0, 10]", Some("[5, 5]"))]
+    #[case("[5, 5]", "[5, 5]", Some("[5, 5]"))]
+    fn test_interval_intersect(
+        #[case] this: Interval,
+        #[case] that: Interval,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(
+            this.intersect(&that),
+            expected.map(int),
+            "Interval.intersect failed: {this}.intersect({that}) should be {expected:?}",
+        );
+    }
+
+    #[rstest]
+    #[case("[0, 10]", 1, vec!["[0, 10]"])]
+    #[case("[0, 10]", 2, vec!["[0, 5)", "[5, 10]"])]
+    #[case("(0, 10)", 2, vec!["(0, 5)", "[5, 10)"])]
+    #[case("[0, 1]", 4, vec!["[0, 0.25)", "[0.25, 0.5)", "[0.5, 0.75)", "[0.75, 1]"])]
+    #[case("[-3, 3]", 3, vec!["[-3, -1)", "[-1, 1)", "[1, 3]"])]
+    #[case("[5, 5]", 3, vec!["[5, 5]"])]
+    #[case("(5, 5)", 3, vec![])]
+    #[case("[1, 1.0000001]", 4, vec!["[1, 1.0000001)", "[1.0000001, 1.0000001]"])]
+    fn test_interval_split_into(
+        #[case] interval: Interval,
+        #[case] n: usize,
+        #[case] expected: Vec<&str>,
+    ) {
+        let pieces = interval.split_into(n).unwrap();
+
+        assert_eq!(pieces, expected.into_iter().map(int).collect::<Vec<_>>());
+        if !interval.is_empty() {
+            assert_eq!(
+                MultiInterval::from_intervals(pieces),
+                MultiInterval::from_interval(interval)
+            );
+        }
+    }
+
+    #[test]
+    fn test_interval_split_into_errors() {
+        assert!(matches!(
+            int("[0, Inf)").split_into(2),
+            Err(IntervalError::Unbounded)
+        ));
+        assert!(matches!(
+            int("[0, 10]").split_into(0),
+            Err(IntervalError::ZeroPieces)
+        ));
+    }
+
+    #[rstest]
+    #[case("[0, 10]", "[3, 5]", vec!["[0, 3)", "[3, 5]", "(5, 10]"])]
+    #[case("[0, 10]", "(3, 5)", vec!["[0, 3]", "(3, 5)", "[5, 10]"])]
+    #[case("[0, 10]", "(-Inf, 5]", vec!["[0, 5]", "(5, 10]"])]
+    #[case("[0, 10]", "[5, Inf)", vec!["[0, 5)", "[5, 10]"])]
+    #[case("[0, 10]", "[0, 10]", vec!["[0, 10]"])]
+    #[case("[0, 10]", "(0, 10)", vec!["[0, 0]", "(0, 10)", "[10, 10]"])]
+    #[case("[0, 10]", "[-5, 20]", vec!["[0, 10]"])]
+    #[case("[0, 10]", "[20, 30]", vec!["[0, 10]"])]
+    #[case("[0, 10)", "[10, 20]", vec!["[0, 10)"])]
+    #[case("(-Inf, Inf)", "[7, 7]", vec!["(-Inf, 7)", "[7, 7]", "(7, Inf)"])]
+    #[case("[0, 10]", "(5, 5)", vec!["[0, 10]"])]
+    #[case("(5, 5)", "[0, 10]", vec![])]
+    fn test_interval_split_on_boundary_of(
+        #[case] interval: Interval,
+        #[case] other: Interval,
+        #[case] expected: Vec<&str>,
+    ) {
+        let pieces = interval.split_on_boundary_of(&other);
+
+        assert_eq!(pieces, expected.into_iter().map(int).collect::<Vec<_>>());
+        if !interval.is_empty() {
+            assert_eq!(
+                MultiInterval::from_intervals(pieces.clone()),
+                MultiInterval::from_interval(interval)
+            );
+        }
+        assert!(
+            pieces
+                .iter()
+                .all(|piece| piece.intersect(&other) == Some(*piece)
+                    || !piece.intersects_with(&other))
+        );
+    }
+
+    #[rstest]
+    #[case("[0, 20]", "(5, 10]", "(5, 10]")]
+    #[case("[0, 10)", "[10, 20]", "(10, 10)")]
+    #[case("[0, 1]", "[5, 6]", "(5, 5)")]
+    #[case("(3, 3)", "[0, 10]", "(3, 3)")]
+    #[case("[0, 10]", "(-Inf, Inf)", "[0, 10]")]
+    fn test_interval_intersect_keep_empty(
+        #[case] this: Interval,
+        #[case] that: Interval,
+        #[case] expected: Interval,
+    ) {
+        let result = this.intersect_keep_empty(&that);
+
+        assert_eq!(result, expected);
+        assert_eq!(result.is_empty(), this.intersect(&that).is_none());
+    }
+
+    #[test]
+    fn test_interval_intersect_keep_empty_fold() {
+        let intersect_all = |intervals: &[&str]| {
+            intervals
+                .iter()
+                .map(|interval| int(interval))
+                .fold(Interval::whole_line(), |acc, interval| {
+                    acc.intersect_keep_empty(&interval)
+                })
+        };
+
+        assert_eq!(
+            intersect_all(&["[0, 20]", "(-Inf, 10)", "[5, Inf)"]),
+            int("[5, 10)")
+        );
+        assert!(intersect_all(&["[0, 5]", "[10, 20]", "[0, 20]"]).is_empty());
+    }
+
+    #[rstest]
+    #[case("[0, 20]", "(5, 10]", Some(("(5, 10]", 2, 2)))]
+    #[case("(5, 10]", "[0, 20]", Some(("(5, 10]", 1, 1)))]
+    #[case("[0, 10]", "[5, 20]", Some(("[5, 10]", 2, 1)))]
+    #[case("[0, 10)", "[0, 10]", Some(("[0, 10)", 1, 1)))]
+    #[case("[0, 10]", "(0, 10)", Some(("(0, 10)", 2, 2)))]
+    #[case("[0, 10]", "[0, 10]", Some(("[0, 10]", 1, 1)))]
+    #[case("(-Inf, 10]", "[0, Inf)", Some(("[0, 10]", 2, 1)))]
+    #[case("[0, 10)", "[10, 20]", None)]
+    fn test_interval_intersect_tracked(
+        #[case] this: Interval,
+        #[case] that: Interval,
+        #[case] expected: Option<(&str, usize, usize)>,
+    ) {
+        assert_eq!(
+            this.intersect_tracked(&that, 1, 2),
+            expected.map(|(interval, lo, hi)| (int(interval), BoundarySource { lo, hi })),
+        );
+    }
+
+    #[rstest]
+    #[case("(0,0)", true)]
+    #[case("(0,0]", true)]
+    #[case("[0,0)", true)]
+    #[case("[0,0]", false)]
+    #[case("(0,1)", false)]
+    fn test_interval_is_empty(#[case] interval: Interval, #[case] expected: bool) {
+        assert_eq!(
+            interval.is_empty(),
+            expected,
+            "Interval.isEmpty failed: {interval}.is_empty() should be {expected}"
+        );
+    }
+
+    #[rstest]
+    // Same endpoint
+    #[case("(0,0)", "(0,0)", Equal)]
+    #[case("[0,0)", "[0,0)", Equal)]
+    #[case("(0,10)", "(0,20]", Equal)]
+    #[case("[0,0)", "(0,0)", Less)]
+    #[case("(0,0)", "[0,0)", Greater)]
+    // No matter the boundary, it is Less
+    #[case("(-20,0)", "(0,0)", Less)]
+    #[case("(-20,0)", "[0,0)", Less)]
+    #[case("[-20,0)", "(0,0)", Less)]
+    #[case("[-20,0)", "[0,0)", Less)]
+    // No matter the boundary, it is Greater
+    #[case("(20,30)", "(0,0)", Greater)]
+    #[case("(20,30)", "[0,0)", Greater)]
+    #[case("[20,30)", "(0,0)", Greater)]
+    #[case("[20,30)", "[0,0)", Greater)]
+    fn test_interval_lo_cmp(
+        #[case] left: Interval,
+        #[case] right: Interval,
+        #[case] expected: Ordering,
+    ) {
+        assert_eq!(
+            left.lo_cmp(&right),
+            expected,
+            "Interval.lo_cmp failed: {left}.lo_cmp({right}) should be {expected:?}"
+        );
+    }
+
+    #[rstest]
+    // Same endpoint
+    #[case("(0,0)", "(0,0)", Equal)]
+    #[case("(0,0]", "(0,0]", Equal)]
+    #[case("(-10,0)", "(-20,0)", Equal)]
+    #[case("(0,0)", "(0,0]", Less)]
+    #[case("(0,0]", "(0,0)", Greater)]
+    // No matter the boundary, it is Less
+    #[case("(-30,-20)", "(0,0)", Less)]
+    #[case("(-30,-20)", "(0,0]", Less)]
+    #[case("(-30,-20]", "(0,0)", Less)]
+    #[case("(-30,-20]", "(0,0]", Less)]
+    // No matter the boundary, it is Greater
+    #[case("(0,20)", "(0,0)", Greater)]
+    #[case("(0,20]", "(0,0)", Greater)]
+    #[case("(0,20)", "(0,0]", Greater)]
+    #[case("(0,20]", "(0,0]", Greater)]
+    fn test_interval_hi_cmp(
+        #[case] left: Interval,
+        #[case] right: Interval,
+        #[case] expected: Ordering,
+    ) {
+        assert_eq!(
+            left.hi_cmp(&right),
+            expected,
+            "Interval.hi_cmp failed: {left}.hi_cmp({right}) should be {expected:?}"
+        );
+    }
+
+    #[rstest]
+    // zero elements
+    #[case("", "", None)]
+    // one elem - zero elem
+    #[case("[0, 10]", "", None)]
+    #[case("", "[0, 10]", None)]
+    // one element, has intersection
+    #[case("[0, 10]", "[5, 20]", Some("[5, 10]"))]
+    // one element - two elements, has intersection
+    #[case("[0, 10]", "[5, 20] [100, 200]", Some("[5, 10]"))]
+    #[case("[5, 20] [100, 200]", "[0, 10]", Some("[5, 10]"))]
+    // contains multiple intervals
+    #[case("[0, 100]", "[10, 20] [30, 40]", Some("[10, 20] [30, 40]"))]
+    // overlaps with multiple intervals
+    #[case("[20, 50]", "[0, 30] [40, 60]", Some("[20, 30] [40, 50]"))]
+    // empty pieces are dropped
+    #[case("[0, 10]", "[5, 5]", Some("[5, 5]"))]
+    #[case("[0, 10] (20, 30)", "(10, 20]", None)]
+    // multiple elements
+    #[case(
+        "(-Inf, 10] [20, 30] [40, 50]",
+        "(-Inf, 10) [15, 25] (26, 35]",
+        Some("(-Inf, 10) [20, 25] (26, 30]")
+    )]
+    fn test_multiinterval_intersect(
+        #[case] this: MultiInterval,
+        #[case] that: MultiInterval,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(
+            this.intersect(&that),
+            expected.map(multiint),
+            "MultiInterval.intersect failed: {this}.intersect({that}) should be {expected:?}",
+        );
+    }
+
+    #[test]
+    fn test_multiinterval_clean_merges_every_contained_interval() {
+        // The neighbours were merged from the back, so `[0, 10]` only absorbed `[1, 2]`, and `[5, 6]` was kept
+        let multi =
+            MultiInterval::from_intervals(vec![int("[0, 10]"), int("[1, 2]"), int("[5, 6]")]);
+
+        assert_eq!(multi.intervals, vec![int("[0, 10]")]);
+    }
+
+    #[rstest]
+    #[case(vec!["[10, 20]", "[15, 30]"], vec!["[10, 30]"])]
+    #[case(vec!["[20, 30]", "[10, 20)"], vec!["[10, 30]"])]
+    #[case(vec!["[0, 10]", "[1, 2]", "[5, 6]"], vec!["[0, 10]"])]
+    #[case(vec!["(-Inf, Inf)", "(-3, 0]", "(-2.5, 10]"], vec!["(-Inf, Inf)"])]
+    #[case(vec!["[0, 10]", "[1, 2]", "[5, 12]", "(12, 20)", "[30, 40]"], vec!["[0, 20)", "[30, 40]"])]
+    #[case(vec!["(1, 2)", "(0, 1)"], vec!["(0, 1)", "(1, 2)"])]
+    fn test_multiinterval_from_intervals(
+        #[case] intervals: Vec<&str>,
+        #[case] expected: Vec<&str>,
+    ) {
+        let parse_all = |xs: Vec<&str>| xs.into_iter().map(int).collect::<Vec<_>>();
+
+        assert_eq!(
+            MultiInterval::from_intervals(parse_all(intervals)).intervals,
+            parse_all(expected)
+        );
+    }
+
+    #[test]
+    fn test_multiinterval_from_sorted_unchecked() {
+        let intervals = vec![int("(-Inf, 0)"), int("(0, 10]"), int("[20, 20]")];
+
+        assert_eq!(
+            MultiInterval::from_sorted_unchecked(intervals.clone()),
+            MultiInterval::from_intervals(intervals)
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "unsorted or overlapping")]
+    fn test_multiinterval_from_sorted_unchecked_checks_in_debug() {
+        MultiInterval::from_sorted_unchecked(vec![int("[10, 20]"), int("[0, 10]")]);
+    }
+
+    #[test]
+    fn test_multiinterval_intersect_is_simplified() {
+        // Bypassing the constructors, which would already simplify the intervals
+        let this = MultiInterval {
+            intervals: vec![int("[0, 10)"), int("[10, 20]"), int("(30, 30)")],
+        };
+        let that = MultiInterval {
+            intervals: vec![int("[5, 12]"), int("[8, 15]"), int("[25, 35]")],
+        };
+
+        let intersection = this.intersect(&that).unwrap();
+
+        assert_eq!(intersection.intervals, vec![int("[5, 15]")]);
+        assert_eq!(
+            that.intersect(&this).unwrap().intervals,
+            vec![int("[5, 15]")]
+        );
+    }
+
+    #[rstest]
+    // zero elements
+    #[case("", "(-Inf, Inf)")]
+    #[case("(-Inf, Inf)", "")]
+    // one element - -Inf on left
+    #[case("(-Inf, 10)", "[10, Inf)")]
+    #[case("(-Inf, 10]", "(10, Inf)")]
+    // one element - no Infs on either side
+    #[case("(10, 20)", "(-Inf, 10] [20, Inf)")]
+    #[case("(10, 20]", "(-Inf, 10] (20, Inf)")]
+    #[case("[10, 20)", "(-Inf, 10) [20, Inf)")]
+    #[case("[10, 20]", "(-Inf, 10) (20, Inf)")]
+    // one element - Inf on right
+    #[case("(10, Inf)", "(-Inf, 10]")]
+    #[case("[10, Inf)", "(-Inf, 10)")]
+    // multiple elements - has Inf on either side
+    #[case("(-Inf, 10) (20, Inf)", "[10, 20]")]
+    #[case("(-Inf, 10) (20, 30) (40, Inf)", "[10, 20] [30, 40]")]
+    // multiple elements - Inf on one side
+    #[case("(-Inf, 10) [20, 30)", "[10, 20) [30, Inf)")]
+    #[case(
+        "(-Inf, 10)        (20, 30)        (40, 50)",
+        "          [10, 20]        [30, 40]        [50, Inf)"
+    )]
+    #[case("(0, 10) [20, Inf)", "(-Inf, 0] [10, 20)")]
+    #[case(
+        "         [0, 10)        (20, 30)        (40, Inf)",
+        "(-Inf, 0)       [10, 20]        [30, 40]"
+    )]
+    // TODO: same endpoint, like (0,0) [0,0] (0,0] [0,0)
+    // complex examples
+    #[case(
+        "           [-42, 3)      (3, 67)         (100, 101)          [205, 607]          (700, Inf)",
+        "(-Inf, -42)        [3, 3]       [67, 100]          [101, 205)          (607, 700]",
+    )]
+    fn test_multiinterval_inverse(
+        #[case] interval: MultiInterval,
+        #[case] expected: MultiInterval,
+    ) {
+        assert_eq!(
+            interval.complement(),
+            expected,
+            "MultiInterval.invert failed: {interval}.inverse() should be {expected}",
+        );
+    }
+
+    #[test]
+    fn test_multiinterval_complement_of_unsimplified() {
+        // Bypassing the constructors, like the `(0, 0)` parsed from `x in (0, 0)`
+        let empty_piece = MultiInterval {
+            intervals: vec![int("(0, 0)")],
+        };
+        let touching = MultiInterval {
+            intervals: vec![int("[0, 5)"), int("[5, 10]")],
+        };
+
+        assert_eq!(empty_piece.complement(), multiint("(-Inf, Inf)"));
+        assert_eq!(touching.complement(), multiint("(-Inf, 0) (10, Inf)"));
+        assert_eq!(
+            multiint("[-5, 5]").difference(&empty_piece),
+            multiint("[-5, 5]")
+        );
+    }
+
+    #[rstest]
+    #[case("", None, None)]
+    #[case("[0, 10)", Some((0.0, Boundary::Closed)), Some((10.0, Boundary::Open)))]
+    #[case(
+        "(-Inf, 0) [5, 5] (10, 20]",
+        Some((f32::NEG_INFINITY, Boundary::Open)),
+        Some((20.0, Boundary::Closed))
+    )]
+    fn test_multiinterval_lowest_and_highest(
+        #[case] interval: MultiInterval,
+        #[case] lowest: Option<(f32, Boundary)>,
+        #[case] highest: Option<(f32, Boundary)>,
+    ) {
+        assert_eq!(interval.lowest_lo(), lowest.map(|(lo, _)| lo));
+        assert_eq!(
+            interval.lowest_boundary(),
+            lowest.map(|(_, boundary)| boundary)
+        );
+        assert_eq!(interval.highest_hi(), highest.map(|(hi, _)| hi));
+        assert_eq!(
+            interval.highest_boundary(),
+            highest.map(|(_, boundary)| boundary)
+        );
+    }
+
+    #[rstest]
+    #[case("", None)]
+    #[case("[0, 10)", Some("[0, 10)"))]
+    #[case("(-Inf, 0) [5, 5] (10, 20]", Some("(-Inf, 20]"))]
+    #[case("[1, 2] (3, Inf)", Some("[1, Inf)"))]
+    fn test_multiinterval_bounding_interval(
+        #[case] interval: MultiInterval,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(interval.bounding_interval(), expected.map(int));
+    }
+
+    #[test]
+    fn test_round_trip_check() {
+        let boundaries = [Boundary::Open, Boundary::Closed];
+        let endpoints = [
+            (f32::NEG_INFINITY, f32::INFINITY),
+            (f32::NEG_INFINITY, -3.0),
+            (-3.0, f32::INFINITY),
+            (-10.5, -0.25),
+            (0.0, 0.0),
+            (-0.0, 0.0),
+            (5.0, 5.0),
+            (-7.0, -7.0),
+            (0.1, 0.3),
+            (1e-7, 2.5e-6),
+            (-3e9, 1e20),
+            (-f32::MAX, f32::MAX),
+            (f32::MIN_POSITIVE, 1.0),
+        ];
+
+        for (lo, hi) in endpoints {
+            for lo_boundary in boundaries {
+                for hi_boundary in boundaries {
+                    let interval = Interval::new(lo_boundary, lo, hi, hi_boundary).unwrap();
+
+                    assert!(
+                        interval.round_trip_check(),
+                        "Interval.round_trip_check failed for {interval}"
+                    );
+                }
+            }
+        }
+
+        for multi_interval in [
+            "",
+            "(-Inf, Inf)",
+            "(-Inf, -5) [-5, -5] (0, 0.5] (100000000000000000000, Inf)",
+            "[0, 0] (1, 2) [3, 3]",
+        ] {
+            let multi_interval = multiint(multi_interval);
+
+            assert!(
+                multi_interval.round_trip_check(),
+                "MultiInterval.round_trip_check failed for {multi_interval}"
+            );
+        }
+        assert!(!MultiInterval {
+            intervals: vec![int("[5, 10]"), int("[0, 1]")]
+        }
+        .round_trip_check());
+    }
+
+    #[test]
+    fn test_multiinterval_empty() {
+        let empty = MultiInterval::new_empty();
+
+        assert!(empty.is_empty());
+        assert_eq!(empty.complement(), multiint("(-Inf, Inf)"));
+        assert_eq!(empty.union(&multiint("[0, 10]")), multiint("[0, 10]"));
+        assert_eq!(empty.to_string(), "");
+    }
+
+    #[rstest]
+    #[case("[0, 10]", "[0, 100]", "(10, 100]")]
+    #[case("(0, 10)", "[0, 100]", "[0, 0] [10, 100]")]
+    #[case("[20, 30] [50, 60)", "[0, 100]", "[0, 20) (30, 50) [60, 100]")]
+    #[case("[0, 10]", "(-Inf, Inf)", "(-Inf, 0) (10, Inf)")]
+    #[case("[0, 100]", "[0, 100]", "")]
+    #[case("", "[0, 100]", "[0, 100]")]
+    #[case("[200, 300]", "[0, 100]", "[0, 100]")]
+    fn test_multiinterval_complement_in(
+        #[case] interval: MultiInterval,
+        #[case] universe: Interval,
+        #[case] expected: MultiInterval,
+    ) {
+        assert_eq!(
+            interval.complement_in(&universe),
+            expected,
+            "MultiInterval.complement_in failed: {interval}.complement_in({universe}) should be {expected}",
+        );
+    }
+
+    #[rstest]
+    #[case("[0, 10]", BinaryOp::GreaterThan, 6.0, "(6, 10]")]
+    #[case("[0, 10]", BinaryOp::GreaterThanEqualTo, 6.0, "[6, 10]")]
+    #[case("[0, 10]", BinaryOp::LessThan, 6.0, "[0, 6)")]
+    #[case("[0, 10]", BinaryOp::LessThanEqualTo, 6.0, "[0, 6]")]
+    #[case("[0, 10]", BinaryOp::Equal, 6.0, "[6, 6]")]
+    #[case("[0, 10]", BinaryOp::NotEqual, 6.0, "[0, 6) (6, 10]")]
+    #[case("[0, 10]", BinaryOp::NotEqual, 0.0, "(0, 10]")]
+    #[case("[0, 10]", BinaryOp::Equal, 20.0, "")]
+    #[case("[0, 10]", BinaryOp::GreaterThan, 10.0, "")]
+    #[case("[0, 10]", BinaryOp::LessThanEqualTo, 10.0, "[0, 10]")]
+    #[case("(-Inf, Inf)", BinaryOp::LessThan, 0.0, "(-Inf, 0)")]
+    fn test_interval_compare(
+        #[case] interval: Interval,
+        #[case] op: BinaryOp,
+        #[case] value: f32,
+        #[case] expected: MultiInterval,
+    ) {
+        assert_eq!(
+            interval.compare(&op, value),
+            expected,
+            "Interval.compare failed: {interval} {op:?} {value} should be {expected}",
+        );
+    }
+
+    #[rstest]
+    #[case("", "", "")]
+    #[case("(0, 5) (5, 10)", "[0, 10]", "(0, 5) (5, 10)")]
+    #[case("[0, 5] [7, 7] (8, 9]", "[0, 5] [7, 7] [8, 9]", "(0, 5) (8, 9)")]
+    #[case("(-Inf, 0) (0, Inf)", "(-Inf, Inf)", "(-Inf, 0) (0, Inf)")]
+    #[case("[0, 10) [10, 20]", "[0, 20]", "(0, 20)")]
+    fn test_multiinterval_with_all_closed_and_open(
+        #[case] interval: MultiInterval,
+        #[case] expected_closed: MultiInterval,
+        #[case] expected_open: MultiInterval,
+    ) {
+        assert_eq!(interval.with_all_closed(), expected_closed);
+        assert_eq!(interval.with_all_open(), expected_open);
+    }
+
+    #[test]
+    fn test_multiinterval_map_boundaries() {
+        let inverted = multiint("[0, 5) (6, 10]").map_boundaries(|boundary| boundary.inverse());
+
+        assert_eq!(inverted.intervals, vec![int("(0, 5]"), int("[6, 10)")]);
+    }
+
+    #[rstest]
+    #[case("", "", "")]
+    #[case("[0, 100]", "[32, 212]", "[-100, 0]")]
+    #[case("[0, 10) (20, Inf)", "[32, 50) (68, Inf)", "(-Inf, -20) (-10, 0]")]
+    #[case("(-Inf, -10] [5, 5]", "(-Inf, 14] [41, 41]", "[-5, -5] [10, Inf)")]
+    #[case("(-Inf, Inf)", "(-Inf, Inf)", "(-Inf, Inf)")]
+    fn test_multiinterval_map_values(
+        #[case] interval: MultiInterval,
+        #[case] expected_fahrenheit: MultiInterval,
+        #[case] expected_negated: MultiInterval,
+    ) {
+        assert_eq!(
+            interval.map_values(|x| x * 1.8 + 32.0).unwrap(),
+            expected_fahrenheit
+        );
+        assert_eq!(interval.map_values(|x| -x).unwrap(), expected_negated);
+    }
+
+    #[test]
+    fn test_multiinterval_map_values_merges() {
+        let rounded = multiint("[0, 2.2] [2.4, 5]").map_values(f32::round);
+
+        assert_eq!(rounded.unwrap(), multiint("[0, 5]"));
+    }
+
+    #[test]
+    fn test_multiinterval_map_values_nan() {
+        assert!(matches!(
+            multiint("[0, 10] (20, Inf)").map_values(|x| x * 0.0),
+            Err(IntervalError::NotANumber)
+        ));
+        assert_eq!(
+            multiint("[0, 10]").map_values(|x| x * 0.0).unwrap(),
+            multiint("[0, 0]")
+        );
+    }
+
+    #[rstest]
+    #[case("[0, 10] (20, 30)", 0.0, 30.0, 31, "[=========]---------(=========)")]
+    #[case(
+        "[0, 10] [20, 30]",
+        0.0,
+        30.0,
+        61,
+        "[===================]-------------------[===================]"
+    )]
+    #[case("", 0.0, 30.0, 10, "----------")]
+    #[case("(-Inf, 5) [15, Inf)", 0.0, 20.0, 21, "<====)---------[====>")]
+    #[case("[5, 5]", 0.0, 10.0, 11, "-----|-----")]
+    #[case("[-20, -10] [5, 8]", 0.0, 10.0, 11, "-----[==]--")]
+    #[case("[0, 10]", 0.0, 10.0, 0, "")]
+    fn test_multiinterval_to_ascii_timeline(
+        #[case] interval: MultiInterval,
+        #[case] min: f32,
+        #[case] max: f32,
+        #[case] width: usize,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(interval.to_ascii_timeline(min, max, width), expected);
+    }
+
+    #[rstest]
+    #[case("", 0.0)]
+    #[case("[0, 10]", 10.0)]
+    #[case("[0, 10) (20, 25] [30, 30]", 15.0)]
+    #[case("[0, 10] [20, Inf)", f32::INFINITY)]
+    fn test_multiinterval_total_width(#[case] interval: MultiInterval, #[case] expected: f32) {
+        assert_eq!(interval.total_width(), expected);
+    }
+
+    #[rstest]
+    #[case("[0, 30)", "[0, 120]", 0.25)]
+    #[case("", "[0, 120]", 0.0)]
+    #[case("(-Inf, Inf)", "[0, 120]", 1.0)]
+    #[case("[0, 10] [50, 60]", "[0, 100]", 0.2)]
+    #[case("(-Inf, 18)", "[0, 120]", 0.15)]
+    #[case("[200, 300]", "[0, 100]", 0.0)]
+    #[case("[0, 10]", "[5, 5]", 1.0)]
+    #[case("[0, 10]", "[50, 50]", 0.0)]
+    fn test_multiinterval_coverage_ratio(
+        #[case] interval: MultiInterval,
+        #[case] universe: Interval,
+        #[case] expected: f32,
+    ) {
+        assert_eq!(interval.coverage_ratio(&universe), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "[0, 10] [20, 30]",
+        "[-5, 50]",
+        Some("[-5, 0)"),
+        "(10, 20)",
+        Some("(30, 50]")
+    )]
+    #[case(
+        "[0, 10] [20, 30] [40, 50]",
+        "[0, 50]",
+        None,
+        "(10, 20) (30, 40)",
+        None
+    )]
+    #[case("(0, 10]", "[0, 10]", Some("[0, 0]"), "", None)]
+    #[case("[5, 10]", "(-Inf, Inf)", Some("(-Inf, 5)"), "", Some("(10, Inf)"))]
+    #[case("(-Inf, 0) (0, Inf)", "[-10, 10]", None, "[0, 0]", None)]
+    #[case("", "[0, 10]", Some("[0, 10]"), "", None)]
+    #[case("[100, 200]", "[0, 10]", Some("[0, 10]"), "", None)]
+    #[case("[0, 10]", "(5, 5)", None, "", None)]
+    fn test_multiinterval_classify_gaps(
+        #[case] covered: MultiInterval,
+        #[case] domain: Interval,
+        #[case] leading: Option<&str>,
+        #[case] interior: MultiInterval,
+        #[case] trailing: Option<&str>,
+    ) {
+        assert_eq!(
+            covered.classify_gaps(&domain),
+            GapReport {
+                leading: leading.map(int),
+                interior,
+                trailing: trailing.map(int),
+            }
+        );
+    }
+
+    #[rstest]
+    #[case("[0, 10]", "[0, Inf)")]
+    #[case("[0, 10]", "(-Inf, Inf)")]
+    #[case("[0, 10]", "(5, 5)")]
+    fn test_multiinterval_coverage_ratio_is_nan(
+        #[case] interval: MultiInterval,
+        #[case] universe: Interval,
+    ) {
+        assert!(interval.coverage_ratio(&universe).is_nan());
+    }
+
+    #[rstest]
+    #[case("", 5.0, "", "")]
+    #[case("[0, 10]", 5.0, "[0, 5)", "[5, 10]")]
+    #[case("(0, 10)", 5.0, "(0, 5)", "[5, 10)")]
+    #[case("[0, 10] [20, 30]", 15.0, "[0, 10]", "[20, 30]")]
+    #[case("[0, 10] [20, 30]", 25.0, "[0, 10] [20, 25)", "[25, 30]")]
+    #[case("[0, 5) [5, 10]", 5.0, "[0, 5)", "[5, 10]")]
+    #[case("[0, 5] (5, 10]", 5.0, "[0, 5)", "[5, 10]")]
+    #[case("[0, 10]", 0.0, "", "[0, 10]")]
+    #[case("[0, 10]", 10.0, "[0, 10)", "[10, 10]")]
+    #[case("(0, 10)", 10.0, "(0, 10)", "")]
+    #[case("[5, 5]", 5.0, "", "[5, 5]")]
+    #[case("(-Inf, Inf)", 0.0, "(-Inf, 0)", "[0, Inf)")]
+    #[case("(-Inf, Inf)", f32::INFINITY, "(-Inf, Inf)", "")]
+    #[case("(-Inf, Inf)", f32::NEG_INFINITY, "", "(-Inf, Inf)")]
+    fn test_multiinterval_split_at(
+        #[case] interval: MultiInterval,
+        #[case] point: f32,
+        #[case] expected_below: MultiInterval,
+        #[case] expected_above: MultiInterval,
+    ) {
+        assert_eq!(
+            interval.split_at(point),
+            (expected_below, expected_above),
+            "MultiInterval.split_at failed: {interval}.split_at({point})",
+        );
+    }
+
+    #[rstest]
+    #[case("[5, 10)", Ok("[5, 10)"))]
+    #[case("  (-Inf, 0] ", Ok("(-Inf, 0]"))]
+    #[case("[5, 10) [20, 30]", Err(()))]
+    #[case("", Err(()))]
+    #[case("[5, 10", Err(()))]
+    #[case("[10, 5]", Err(()))]
+    fn test_interval_from_str(#[case] input: &str, #[case] expected: Result<&str, ()>) {
+        assert_eq!(
+            input.parse::<Interval>(),
+            expected
+                .map(int)
+                .map_err(|()| InvalidInterval(input.to_owned()))
+        );
+    }
+
+    #[rstest]
+    #[case("", Some(""))]
+    #[case("[5, 10] (-Inf, 0)", Some("(-Inf, 0) [5, 10]"))]
+    #[case("[5, 10] foo", None)]
+    fn test_multiinterval_from_str(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(input.parse::<MultiInterval>().ok(), expected.map(multiint));
+    }
+
+    #[rstest]
+    #[case("", Ok(""))]
+    #[case("  [5, 10] (-Inf, 0)\n", Ok("(-Inf, 0) [5, 10]"))]
+    #[case("[0, 5) [5, 10] (5, 5)", Ok("[0, 10]"))]
+    #[case(
+        "[0, 5] [5, 10]",
+        Err(ParseMultiIntervalError::Overlapping(int("[0, 5]"), int("[5, 10]")))
+    )]
+    #[case(
+        "[0, 10] (3, 3) [5, 6]",
+        Err(ParseMultiIntervalError::Overlapping(int("[0, 10]"), int("[5, 6]")))
+    )]
+    #[case(
+        "[3, 8] (-Inf, 0) [0, 4)",
+        Err(ParseMultiIntervalError::Overlapping(int("[0, 4)"), int("[3, 8]")))
+    )]
+    #[case("[0, 5] [10, 5] [20, 30]", Err(ParseMultiIntervalError::InvalidPiece("[10, 5]".to_owned())))]
+    #[case("[0, 5] foo", Err(ParseMultiIntervalError::InvalidPiece("foo".to_owned())))]
+    #[case("[0, 5", Err(ParseMultiIntervalError::InvalidPiece("[0, 5".to_owned())))]
+    fn test_parse_multi_interval(
+        #[case] input: &str,
+        #[case] expected: Result<&str, ParseMultiIntervalError>,
+    ) {
+        assert_eq!(parse_multi_interval(input), expected.map(multiint));
+    }
+
+    #[test]
+    fn test_interval_string() {
+        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+        struct Config {
+            #[serde(with = "interval_string")]
+            range: Interval,
+        }
+
+        let config = Config {
+            range: int("[0, 100)"),
+        };
+        let json = serde_json::to_string(&config).unwrap();
+
+        assert_eq!(json, r#"{"range":"[0, 100)"}"#);
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+
+        let err = serde_json::from_str::<Config>(r#"{"range":"[0, 100"}"#).unwrap_err();
+        assert!(err.to_string().contains("is not a valid interval"));
+    }
+
+    #[rstest]
+    #[case("[0, 10] (20, 30)", &[25.0, 5.0], "[0, 5) [5, 10] (20, 25) [25, 30)")]
+    #[case("[0, 10]", &[2.0, 8.0, 5.0, 5.0], "[0, 2) [2, 5) [5, 8) [8, 10]")]
+    #[case("[0, 10] (20, 30)", &[0.0, 10.0, 15.0, 20.0, 40.0], "[0, 10] (20, 30)")]
+    #[case("(-Inf, Inf)", &[0.0, f32::INFINITY, f32::NAN], "(-Inf, 0) [0, Inf)")]
+    #[case("[5, 5]", &[5.0], "[5, 5]")]
+    #[case("", &[1.0], "")]
+    fn test_multiinterval_densify(
+        #[case] interval: MultiInterval,
+        #[case] points: &[f32],
+        #[case] expected: &str,
+    ) {
+        let densified = interval.densify(points);
+        let pieces = densified
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        assert_eq!(pieces, expected);
+        assert_eq!(
+            MultiInterval::from_intervals(densified),
+            interval,
+            "MultiInterval.densify changed the covered values: {interval} became {pieces}"
+        );
+    }
+
+    #[rstest]
+    #[case(Interval::whole_line(), "(-Inf, Inf)")]
+    #[case(Interval::at_most(10.0, Boundary::Closed), "(-Inf, 10]")]
+    #[case(Interval::at_most(10.0, Boundary::Open), "(-Inf, 10)")]
+    #[case(Interval::at_most(f32::INFINITY, Boundary::Closed), "(-Inf, Inf)")]
+    #[case(Interval::at_least(10.0, Boundary::Closed), "[10, Inf)")]
+    #[case(Interval::at_least(f32::NEG_INFINITY, Boundary::Closed), "(-Inf, Inf)")]
+    #[case(Interval::less_than(10.0), "(-Inf, 10)")]
+    #[case(Interval::greater_than(10.0), "(10, Inf)")]
+    fn test_interval_half_lines(#[case] interval: Interval, #[case] expected: Interval) {
+        assert_eq!(interval, expected);
+    }
+
+    #[test]
+    fn test_interval_builder() {
+        assert_eq!(
+            Interval::builder()
+                .open_lo(5.0)
+                .closed_hi(10.0)
+                .build()
+                .unwrap(),
+            int("(5, 10]")
+        );
+        assert_eq!(
+            Interval::builder().lo(0.0).hi(10.0).build().unwrap(),
+            int("[0, 10]")
+        );
+        assert_eq!(
+            Interval::builder()
+                .closed_lo(0.0)
+                .open_hi(10.0)
+                .build()
+                .unwrap(),
+            int("[0, 10)")
+        );
+        assert_eq!(
+            Interval::builder().lo(0.0).build().unwrap(),
+            int("[0, Inf)")
+        );
+        assert_eq!(Interval::builder().build().unwrap(), int("(-Inf, Inf)"));
+        assert!(matches!(
+            Interval::builder().lo(10.0).hi(0.0).build(),
+            Err(IntervalError::LoIsGreaterThanHi)
+        ));
+        assert!(matches!(
+            Interval::builder().lo(f32::NAN).hi(0.0).build(),
+            Err(IntervalError::NotANumber)
+        ));
+        assert!(matches!(
+            Interval::builder().open_hi(f32::NAN).build(),
+            Err(IntervalError::NotANumber)
+        ));
+    }
+
+    #[test]
+    fn test_nan_endpoints() {
+        assert!(matches!(
+            Interval::new(Boundary::Closed, 0.0, f32::NAN, Boundary::Closed),
+            Err(IntervalError::NotANumber)
+        ));
+
+        assert_eq!(cmp_endpoint_values(f32::NAN, f32::INFINITY), Greater);
+        assert_eq!(cmp_endpoint_values(f32::NEG_INFINITY, f32::NAN), Less);
+        assert_eq!(cmp_endpoint_values(f32::NAN, f32::NAN), Equal);
+        assert_eq!(cmp_endpoint_values(-0.0, 0.0), Equal);
+
+        // The fields are public, so the sorts can still get a NaN endpoint, they shouldn't panic on it
+        let with_nan = Interval {
+            lo_boundary: Boundary::Closed,
+            lo: f32::NAN,
+            hi: f32::NAN,
+            hi_boundary: Boundary::Closed,
+        };
+        let mut intervals = vec![with_nan, int("[0, 1]")];
+        intervals.sort_by(Interval::lo_cmp);
+        assert_eq!(intervals[0], int("[0, 1]"));
+        assert!(with_nan.lo_endpoint() > int("[0, 1]").hi_endpoint());
+
+        let multi_interval = MultiInterval::from_intervals(intervals);
+        let _ = multi_interval.intersect(&multiint("[0, 10]"));
+    }
+
+    #[rstest]
+    #[case("[0, 10]", 1.0, "[0, 10]")]
+    #[case("(0, 10.5]", 1.0, "[1, 10]")]
+    #[case("[0.2, 9.7)", 1.0, "[1, 9]")]
+    #[case("(0, 10)", 1.0, "[1, 9]")]
+    #[case("(0.30000001, 0.69999999]", 0.1, "[0.4, 0.7]")]
+    #[case("[0.30000001, 0.69999999)", 0.1, "[0.3, 0.6]")]
+    #[case("(-Inf, 10.5)", 1.0, "(-Inf, 10]")]
+    #[case("(-2.5, Inf)", 1.0, "[-2, Inf)")]
+    #[case("(-Inf, Inf)", 1.0, "(-Inf, Inf)")]
+    #[case("[0.2, 0.8]", 1.0, "(1, 1)")]
+    #[case("(0, 1)", 1.0, "(1, 1)")]
+    #[case("(0, 0)", 1.0, "(0, 0)")]
+    #[case("(0.5, 10)", 0.0, "(0.5, 10)")]
+    fn test_interval_quantize(
+        #[case] interval: Interval,
+        #[case] precision: f32,
+        #[case] expected: Interval,
+    ) {
+        assert_eq!(
+            interval.quantize(precision),
+            expected,
+            "Interval.quantize failed: {interval}.quantize({precision}) should be {expected}",
+        );
+    }
+
+    #[rstest]
+    #[case("[0, 10]", 1.0, Some(0.0))]
+    #[case("(0, 10]", 1.0, Some(1.0))]
+    #[case("(0, 10]", 0.01, Some(0.01))]
+    #[case("(-Inf, 10]", 1.0, Some(10.0))]
+    #[case("(-Inf, 10)", 1.0, Some(9.0))]
+    #[case("(5, Inf)", 1.0, Some(6.0))]
+    #[case("(-Inf, Inf)", 1.0, Some(0.0))]
+    #[case("(0, 0.5)", 1.0, Some(0.25))]
+    #[case("[0.5, 10]", 1.0, Some(1.0))]
+    #[case("(-Inf, 10.5]", 1.0, Some(10.0))]
+    #[case("[0.123, 1]", 0.01, Some(0.13))]
+    #[case("(0, 0)", 1.0, None)]
+    fn test_interval_representative_value(
+        #[case] interval: Interval,
+        #[case] precision: f32,
+        #[case] expected: Option<f32>,
+    ) {
+        assert_eq!(
+            interval.representative_value(precision),
+            expected,
+            "Interval.representative_value failed: {interval}.representative_value({precision}) should be {expected:?}",
+        );
+    }
+
+    #[rstest]
+    #[case("[0, 10]", 1.0, Some(5.0))]
+    #[case("[0, 5]", 1.0, Some(3.0))]
+    #[case("(0, 1)", 0.1, Some(0.5))]
+    #[case("(0, 1)", 1.0, Some(0.5))]
+    #[case("(-Inf, 10)", 1.0, Some(9.0))]
+    #[case("(-Inf, Inf)", 1.0, Some(0.0))]
+    #[case("(0, 0)", 1.0, None)]
+    fn test_interval_middle_value(
+        #[case] interval: Interval,
+        #[case] precision: f32,
+        #[case] expected: Option<f32>,
+    ) {
+        assert_eq!(interval.middle_value(precision), expected);
+    }
+
+    #[rstest]
+    #[case("[0, 10]", 1.0, 5.0, Some(5.0))]
+    #[case("[0, 10]", 1.0, 15.0, Some(10.0))]
+    #[case("(0, 10)", 1.0, -5.0, Some(1.0))]
+    #[case("(0, 0)", 1.0, 2.0, None)]
+    fn test_interval_nearest_inside(
+        #[case] interval: Interval,
+        #[case] precision: f32,
+        #[case] point: f32,
+        #[case] expected: Option<f32>,
+    ) {
+        assert_eq!(
+            interval.nearest_inside(point, precision),
+            expected,
+            "Interval.nearest_inside failed: {interval}.nearest_inside({point}, {precision}) should be {expected:?}",
+        );
+    }
+
+    #[rstest]
+    #[case("", "", true)]
+    #[case("[0, 10]", "", true)]
+    #[case("[0, 10]", "[5, 20]", false)]
+    #[case("[0, 10)", "[10, 20]", true)]
+    #[case("[0, 10]", "[10, 20]", false)]
+    #[case("[0, 1] [4, 5] [8, 9]", "[2, 3] [6, 7]", true)]
+    #[case("[0, 1] [4, 5] [8, 9]", "[2, 3] [6, 8]", false)]
+    #[case("[2, 3] [6, 8]", "[0, 1] [4, 5] [8, 9]", false)]
+    #[case("[0, 10]", "(-Inf, 0) (10, Inf)", true)]
+    #[case("(-Inf, 0) [100, Inf)", "[20, 30] [40, 50] [150, 160]", false)]
+    fn test_multiinterval_is_disjoint(
+        #[case] this: MultiInterval,
+        #[case] other: MultiInterval,
         #[case] expected: bool,
     ) {
         assert_eq!(
-            this.intersects_with(&that),
+            this.is_disjoint(&other),
             expected,
-            "Interval.intersects_with failed: {this}.intersects_with({that}) should be {expected}",
+            "MultiInterval.is_disjoint failed: {this}.is_disjoint({other}) should be {expected}",
+        );
+        assert_eq!(this.intersects_with(&other), !expected);
+    }
+
+    #[test]
+    fn test_multiinterval_intersect_each() {
+        let this = multiint("(-Inf, 0) [5, 10] (20, 30]");
+        let others = [
+            "",
+            "(-Inf, Inf)",
+            "[0, 5)",
+            "[-5, 5] [10, 25)",
+            "(-10, -5) (-1, 6] [7, 8] [9, 21)",
+            "(10, 20]",
+            "[30, 30] (30, Inf)",
+        ]
+        .map(multiint);
+
+        let expected = others
+            .iter()
+            .map(|other| this.intersect(other))
+            .collect::<Vec<_>>();
+
+        assert_eq!(this.intersect_each(&others), expected);
+        assert_eq!(this.intersect_each(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_multiinterval_intersect_each_unsimplified() {
+        // Bypassing the constructors, the pieces touch each other
+        let this = MultiInterval {
+            intervals: vec![int("[0, 5)"), int("[5, 10]")],
+        };
+        let others = ["[0, 10]", "[3, 7]", "(5, 5)"].map(multiint);
+
+        assert_eq!(
+            this.intersect_each(&others),
+            vec![Some(multiint("[0, 10]")), Some(multiint("[3, 7]")), None]
         );
     }
 
     #[rstest]
-    // self.hi equals other.lo
-    #[case("[0, 10]", "[10, 20]", Some("[10, 10]"))]
-    #[case("[0, 10]", "(10, 20]", None)]
-    #[case("[0, 10)", "[10, 20]", None)]
-    #[case("[0, 10)", "(10, 20]", None)]
-    // self.lo equals other.hi
-    #[case("[10, 20]", "[0, 10]", Some("[10, 10]"))]
-    #[case("(10, 20]", "[0, 10]", None)]
-    #[case("[10, 20]", "[0, 10)", None)]
-    #[case("(10, 20]", "[0, 10)", None)]
-    // self.hi inside other == other.lo inside self
-    #[case("[0, 10]", "[5, 20]", Some("[5, 10]"))]
-    #[case("[0, 10]", "(5, 20]", Some("(5, 10]"))]
-    #[case("[0, 10)", "[5, 20]", Some("[5, 10)"))]
-    #[case("[0, 10)", "(5, 20]", Some("(5, 10)"))]
-    // self.lo inside other == other.hi inside self
-    #[case("[5, 20]", "[0, 10]", Some("[5, 10]"))]
-    #[case("(5, 20]", "[0, 10]", Some("(5, 10]"))]
-    #[case("[5, 20]", "[0, 10)", Some("[5, 10)"))]
-    #[case("(5, 20]", "[0, 10)", Some("(5, 10)"))]
-    // self inside other
-    #[case("[10, 20]", "[0, 30]", Some("[10, 20]"))]
-    #[case("[10, 20)", "[0, 30]", Some("[10, 20)"))]
-    #[case("(10, 20]", "[0, 30]", Some("(10, 20]"))]
-    #[case("(10, 20)", "[0, 30]", Some("(10, 20)"))]
-    // other inside self
-    #[case("[0, 30]", "[10, 20]", Some("[10, 20]"))]
-    #[case("[0, 30]", "[10, 20)", Some("[10, 20)"))]
-    #[case("[0, 30]", "(10, 20]", Some("(10, 20]"))]
-    #[case("[0, 30]", "(10, 20)", Some("(10, 20)"))]
-    // self.lo > other.hi
-    #[case("[20, 30]", "[0, 10]", None)]
-    #[case("[20, 30]", "[0, 10)", None)]
-    #[case("(20, 30]", "[0, 10]", None)]
-    #[case("(20, 30]", "[0, 10)", None)]
-    // other.lo > self.hi
-    #[case("[0, 10]", "[20, 30]", None)]
-    #[case("[0, 10)", "[20, 30]", None)]
-    #[case("[0, 10]", "(20, 30]", None)]
-    #[case("[0, 10)", "(20, 30]", None)]
-    // self.lo == other.lo
-    #[case("[0, 10]", "[0, 20]", Some("[0, 10]"))]
-    #[case("[0, 10]", "(0, 20]", Some("(0, 10]"))]
-    #[case("(0, 10]", "[0, 20]", Some("(0, 10]"))]
-    #[case("(0, 10]", "(0, 20]", Some("(0, 10]"))]
-    // self.hi == other.hi
-    #[case("[10, 20]", "[0, 20]", Some("[10, 20]"))]
-    #[case("[10, 20)", "[0, 20]", Some("[10, 20)"))]
-    #[case("[10, 20]", "[0, 20)", Some("[10, 20)"))]
-    #[case("[10, 20)", "[0, 20)", Some("[10, 20)"))]
-    // TODO: Inf, -Inf
-    // TODO: What about empty intervals like (0,0) (0,0)?
-    fn test_interval_intersect(
-        #[case] this: Interval,
-        #[case] that: Interval,
-        #[case] expected: Option<&str>,
+    #[case("(-Inf, 0) [5, 10] (20, 30]", "[-5, 25]", "[-5, 0) [5, 10] (20, 25]")]
+    #[case(
+        "(-Inf, 0) [5, 10] (20, 30]",
+        "(-Inf, Inf)",
+        "(-Inf, 0) [5, 10] (20, 30]"
+    )]
+    #[case("(-Inf, 0) [5, 10] (20, 30]", "[0, 5)", "")]
+    #[case("(-Inf, 0) [5, 10] (20, 30]", "[10, 20]", "[10, 10]")]
+    #[case("(-Inf, 0) [5, 10] (20, 30]", "(30, 30)", "")]
+    #[case("[0, 10] [20, 30]", "[30, Inf)", "[30, 30]")]
+    #[case("", "[0, 10]", "")]
+    fn test_multiinterval_intersect_interval(
+        #[case] this: MultiInterval,
+        #[case] other: Interval,
+        #[case] expected: MultiInterval,
     ) {
         assert_eq!(
-            this.intersect(&that),
-            expected.map(int),
-            "Interval.intersect failed: {this}.intersect({that}) should be {expected:?}",
+            this.intersect_interval(&other),
+            expected,
+            "MultiInterval.intersect_interval failed: {this}.intersect_interval({other}) should be {expected}",
+        );
+        assert_eq!(
+            this.intersect_interval(&other),
+            this.intersect(&MultiInterval::from_interval(other))
+                .unwrap_or_else(MultiInterval::new_empty)
+        );
+    }
+
+    #[test]
+    fn test_multiinterval_intersect_interval_unsimplified() {
+        // Bypassing the constructors, the pieces touch each other, or are empty
+        let this = MultiInterval {
+            intervals: vec![int("[0, 5)"), int("[5, 10]"), int("(20, 20)")],
+        };
+
+        assert_eq!(
+            this.intersect_interval(&int("[0, 10]")),
+            multiint("[0, 10]")
+        );
+        assert_eq!(
+            this.intersect_interval(&int("[3, 30]")),
+            multiint("[3, 10]")
         );
     }
 
     #[rstest]
-    #[case("(0,0)", true)]
-    #[case("(0,0]", true)]
-    #[case("[0,0)", true)]
-    #[case("[0,0]", false)]
-    #[case("(0,1)", false)]
-    fn test_interval_is_empty(#[case] interval: Interval, #[case] expected: bool) {
+    #[case("(-Inf, Inf)", true, false)]
+    #[case("(-Inf, 0) [0, Inf)", true, false)]
+    #[case("(-Inf, 0) (0, Inf)", false, false)]
+    #[case("(-Inf, 0]", false, false)]
+    #[case("[5, 5]", false, true)]
+    #[case("[5, 5] [7, 7]", false, false)]
+    #[case("[5, 6]", false, false)]
+    #[case("", false, false)]
+    fn test_multiinterval_is_whole_line_and_single_point(
+        #[case] interval: MultiInterval,
+        #[case] is_whole_line: bool,
+        #[case] is_single_point: bool,
+    ) {
+        assert_eq!(interval.is_whole_line(), is_whole_line, "{interval}");
+        assert_eq!(interval.is_single_point(), is_single_point, "{interval}");
+    }
+
+    #[test]
+    fn test_multiinterval_eq_canonical() {
+        // Bypassing the constructors, which would already simplify the intervals
+        let unsimplified = MultiInterval {
+            intervals: vec![int("[10, 20]"), int("(5, 5)"), int("[0, 10]")],
+        };
+        let simplified = multiint("[0, 20]");
+
+        assert_ne!(unsimplified, simplified);
+        assert!(unsimplified.eq_canonical(&simplified));
+        assert!(simplified.eq_canonical(&unsimplified));
+        assert!(
+            MultiInterval::from_interval(int("(5, 5)")).eq_canonical(&MultiInterval::new_empty())
+        );
+        assert!(!unsimplified.eq_canonical(&multiint("[0, 20)")));
+    }
+
+    #[rstest]
+    #[case("", 1.0, 5.0, None)]
+    #[case("[0, 10] (20, 30]", 1.0, 5.0, Some(5.0))]
+    #[case("[0, 10] (20, 30]", 1.0, 25.0, Some(25.0))]
+    #[case("[0, 10] (20, 30]", 1.0, 14.0, Some(10.0))]
+    #[case("[0, 10] (20, 30]", 1.0, 16.0, Some(21.0))]
+    #[case("[0, 10] (20, 30]", 1.0, 15.5, Some(10.0))]
+    #[case("[0, 10] [20, 30]", 1.0, 15.0, Some(10.0))]
+    #[case("[0, 10] (20, 30]", 1.0, -5.0, Some(0.0))]
+    #[case("[0, 10] (20, 30)", 0.1, 35.0, Some(29.9))]
+    #[case("(-Inf, 0) (0, Inf)", 0.5, 0.0, Some(-0.5))]
+    fn test_multiinterval_nearest_inside(
+        #[case] interval: MultiInterval,
+        #[case] precision: f32,
+        #[case] point: f32,
+        #[case] expected: Option<f32>,
+    ) {
         assert_eq!(
-            interval.is_empty(),
+            interval.nearest_inside(point, precision),
             expected,
-            "Interval.isEmpty failed: {interval}.is_empty() should be {expected}"
+            "MultiInterval.nearest_inside failed: {interval}.nearest_inside({point}, {precision}) should be {expected:?}",
         );
     }
 
     #[rstest]
-    // Same endpoint
-    #[case("(0,0)", "(0,0)", Equal)]
-    #[case("[0,0)", "[0,0)", Equal)]
-    #[case("(0,10)", "(0,20]", Equal)]
-    #[case("[0,0)", "(0,0)", Less)]
-    #[case("(0,0)", "[0,0)", Greater)]
-    // No matter the boundary, it is Less
-    #[case("(-20,0)", "(0,0)", Less)]
-    #[case("(-20,0)", "[0,0)", Less)]
-    #[case("[-20,0)", "(0,0)", Less)]
-    #[case("[-20,0)", "[0,0)", Less)]
-    // No matter the boundary, it is Greater
-    #[case("(20,30)", "(0,0)", Greater)]
-    #[case("(20,30)", "[0,0)", Greater)]
-    #[case("[20,30)", "(0,0)", Greater)]
-    #[case("[20,30)", "[0,0)", Greater)]
-    fn test_interval_lo_cmp(
-        #[case] left: Interval,
-        #[case] right: Interval,
-        #[case] expected: Ordering,
+    #[case("", 2, "")]
+    #[case("[0, 10]", 2, "[0, 10]")]
+    #[case("[0.1, 9.999999]", 2, "[0.1, 10]")]
+    #[case("(0.123456, 1.5)", 3, "(0.123, 1.5)")]
+    #[case("(-0.0001, 2.5)", 2, "(0, 2.5)")]
+    #[case("(-Inf, -3.14159) [2.71828, Inf)", 2, "(-Inf, -3.14) [2.72, Inf)")]
+    #[case("[0.6, 1.4]", 0, "[1, 1]")]
+    fn test_multiinterval_to_string_with_precision(
+        #[case] interval: MultiInterval,
+        #[case] decimals: usize,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(interval.to_string_with_precision(decimals), expected);
+    }
+
+    #[rstest]
+    #[case("", "")]
+    #[case("[0, 10]", "[0, 10]")]
+    #[case("[0, 0.001] (5, 10) [20, 20.005]", "(5, 10)")]
+    #[case("[0, 0] [1, 2] [3, 3]", "[1, 2]")]
+    #[case("(-Inf, 0) [0.5, 0.501]", "(-Inf, 0)")]
+    fn test_multiinterval_retain(#[case] interval: MultiInterval, #[case] expected: MultiInterval) {
+        let is_significant = |interval: &Interval| interval.width() >= 0.01;
+
+        assert_eq!(interval.filter(is_significant), expected);
+
+        let mut retained = interval;
+        retained.retain(is_significant);
+        assert_eq!(retained, expected);
+    }
+
+    #[rstest]
+    #[case("", "[0, 100]", "", false)]
+    #[case("[0, 10]", "[0, 100]", "[0, 10]", false)]
+    #[case("[-10, 10]", "[0, 100]", "[0, 10]", true)]
+    #[case("[0, 100]", "(0, 100)", "(0, 100)", true)]
+    #[case("[-10, -5] [200, 300]", "[0, 100]", "", true)]
+    #[case("(-Inf, 10) [50, 60]", "[0, 100]", "[0, 10) [50, 60]", true)]
+    #[case("(-Inf, Inf)", "(-Inf, Inf)", "(-Inf, Inf)", false)]
+    fn test_multiinterval_clamp_to(
+        #[case] interval: MultiInterval,
+        #[case] domain: Interval,
+        #[case] expected: MultiInterval,
+        #[case] expected_is_truncated: bool,
     ) {
         assert_eq!(
-            left.lo_cmp(&right),
+            interval.clamp_to(&domain),
+            (expected, expected_is_truncated),
+            "MultiInterval.clamp_to failed: {interval}.clamp_to({domain})",
+        );
+    }
+
+    #[rstest]
+    #[case("", "", true)]
+    #[case("", "[0, 10]", true)]
+    #[case("[0, 10]", "", false)]
+    #[case("[0, 10]", "[0, 10]", true)]
+    #[case("(0, 10)", "[0, 10]", true)]
+    #[case("[0, 10]", "(0, 10)", false)]
+    #[case("[2, 3] [5, 6]", "[0, 4) (4, 10]", true)]
+    #[case("[2, 5]", "[0, 4) (4, 10]", false)]
+    #[case("[0, 10]", "(-Inf, Inf)", true)]
+    fn test_multiinterval_is_subset_of(
+        #[case] this: MultiInterval,
+        #[case] other: MultiInterval,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(
+            this.is_subset_of(&other),
             expected,
-            "Interval.lo_cmp failed: {left}.lo_cmp({right}) should be {expected:?}"
+            "MultiInterval.is_subset_of failed: {this}.is_subset_of({other}) should be {expected}",
+        );
+        assert_eq!(
+            this.is_subset_of(&other),
+            this.difference(&other).is_empty()
         );
     }
 
     #[rstest]
-    // Same endpoint
-    #[case("(0,0)", "(0,0)", Equal)]
-    #[case("(0,0]", "(0,0]", Equal)]
-    #[case("(-10,0)", "(-20,0)", Equal)]
-    #[case("(0,0)", "(0,0]", Less)]
-    #[case("(0,0]", "(0,0)", Greater)]
-    // No matter the boundary, it is Less
-    #[case("(-30,-20)", "(0,0)", Less)]
-    #[case("(-30,-20)", "(0,0]", Less)]
-    #[case("(-30,-20]", "(0,0)", Less)]
-    #[case("(-30,-20]", "(0,0]", Less)]
-    // No matter the boundary, it is Greater
-    #[case("(0,20)", "(0,0)", Greater)]
-    #[case("(0,20]", "(0,0)", Greater)]
-    #[case("(0,20)", "(0,0]", Greater)]
-    #[case("(0,20]", "(0,0]", Greater)]
-    fn test_interval_hi_cmp(
-        #[case] left: Interval,
-        #[case] right: Interval,
-        #[case] expected: Ordering,
+    #[case("[0, 10)", "[5, 10]", false)]
+    #[case("(0, 10]", "[0, 5]", false)]
+    #[case("[0, 10)", "[5, 10)", true)]
+    #[case("[0, 5) (5, 10]", "[4, 6]", false)]
+    #[case("[0, 5) (5, 10]", "[1, 2] (5, 6] [10, 10]", true)]
+    #[case("[0, 5) (5, 10] [20, 30]", "[1, 2] [25, 31]", false)]
+    #[case("(-Inf, 0) (10, Inf)", "(-Inf, -5] [100, Inf)", true)]
+    #[case("[0, 10]", "", true)]
+    #[case("", "[0, 0]", false)]
+    fn test_multiinterval_contains_multi(
+        #[case] this: MultiInterval,
+        #[case] other: MultiInterval,
+        #[case] expected: bool,
     ) {
         assert_eq!(
-            left.hi_cmp(&right),
+            this.contains_multi(&other),
             expected,
-            "Interval.hi_cmp failed: {left}.hi_cmp({right}) should be {expected:?}"
+            "MultiInterval.contains_multi failed: {this}.contains_multi({other}) should be {expected}",
         );
     }
 
     #[rstest]
-    // zero elements
-    #[case("", "", None)]
-    // one elem - zero elem
-    #[case("[0, 10]", "", None)]
-    #[case("", "[0, 10]", None)]
-    // one element, has intersection
-    #[case("[0, 10]", "[5, 20]", Some("[5, 10]"))]
-    // one element - two elements, has intersection
-    #[case("[0, 10]", "[5, 20] [100, 200]", Some("[5, 10]"))]
-    #[case("[5, 20] [100, 200]", "[0, 10]", Some("[5, 10]"))]
-    // contains multiple intervals
-    #[case("[0, 100]", "[10, 20] [30, 40]", Some("[10, 20] [30, 40]"))]
-    // overlaps with multiple intervals
-    #[case("[20, 50]", "[0, 30] [40, 60]", Some("[20, 30] [40, 50]"))]
-    // multiple elements
-    #[case(
-        "(-Inf, 10] [20, 30] [40, 50]",
-        "(-Inf, 10) [15, 25] (26, 35]",
-        Some("(-Inf, 10) [20, 25] (26, 30]")
-    )]
-    fn test_multiinterval_intersect(
+    #[case("", "", "")]
+    #[case("[0, 10]", "", "[0, 10]")]
+    #[case("", "[0, 10]", "")]
+    #[case("[0, 10]", "[5, 20]", "[0, 5)")]
+    #[case("[0, 10]", "(5, 20]", "[0, 5]")]
+    #[case("[0, 10]", "[20, 30]", "[0, 10]")]
+    #[case("[0, 10]", "[0, 10]", "")]
+    #[case("(-Inf, Inf)", "[0, 10] [20, 30]", "(-Inf, 0) (10, 20) (30, Inf)")]
+    fn test_multiinterval_difference(
         #[case] this: MultiInterval,
         #[case] that: MultiInterval,
-        #[case] expected: Option<&str>,
+        #[case] expected: MultiInterval,
     ) {
         assert_eq!(
-            this.intersect(&that),
-            expected.map(multiint),
-            "MultiInterval.intersect failed: {this}.intersect({that}) should be {expected:?}",
+            this.difference(&that),
+            expected,
+            "MultiInterval.difference failed: {this}.difference({that}) should be {expected}",
         );
     }
 
     #[rstest]
-    // zero elements
-    #[case("", "(-Inf, Inf)")]
-    #[case("(-Inf, Inf)", "")]
-    // one element - -Inf on left
-    #[case("(-Inf, 10)", "[10, Inf)")]
-    #[case("(-Inf, 10]", "(10, Inf)")]
-    // one element - no Infs on either side
-    #[case("(10, 20)", "(-Inf, 10] [20, Inf)")]
-    #[case("(10, 20]", "(-Inf, 10] (20, Inf)")]
-    #[case("[10, 20)", "(-Inf, 10) [20, Inf)")]
-    #[case("[10, 20]", "(-Inf, 10) (20, Inf)")]
-    // one element - Inf on right
-    #[case("(10, Inf)", "(-Inf, 10]")]
-    #[case("[10, Inf)", "(-Inf, 10)")]
-    // multiple elements - has Inf on either side
-    #[case("(-Inf, 10) (20, Inf)", "[10, 20]")]
-    #[case("(-Inf, 10) (20, 30) (40, Inf)", "[10, 20] [30, 40]")]
-    // multiple elements - Inf on one side
-    #[case("(-Inf, 10) [20, 30)", "[10, 20) [30, Inf)")]
-    #[case(
-        "(-Inf, 10)        (20, 30)        (40, 50)",
-        "          [10, 20]        [30, 40]        [50, Inf)"
-    )]
-    #[case("(0, 10) [20, Inf)", "(-Inf, 0] [10, 20)")]
-    #[case(
-        "         [0, 10)        (20, 30)        (40, Inf)",
-        "(-Inf, 0)       [10, 20]        [30, 40]"
-    )]
-    // TODO: same endpoint, like (0,0) [0,0] (0,0] [0,0)
-    // complex examples
-    #[case(
-        "           [-42, 3)      (3, 67)         (100, 101)          [205, 607]          (700, Inf)",
-        "(-Inf, -42)        [3, 3]       [67, 100]          [101, 205)          (607, 700]",
-    )]
-    fn test_multiinterval_inverse(
-        #[case] interval: MultiInterval,
+    // partially overlapping
+    #[case("[0, 10]", "[5, 20]", "[0, 5) (10, 20]")]
+    #[case("[0, 10) [20, 30]", "[5, 25]", "[0, 5) [10, 20) (25, 30]")]
+    // disjoint
+    #[case("[0, 10]", "[20, 30]", "[0, 10] [20, 30]")]
+    #[case("[0, 10)", "[10, 20]", "[0, 20]")]
+    #[case("(0, 10)", "(10, 20)", "(0, 10) (10, 20)")]
+    // identical
+    #[case("[0, 10]", "[0, 10]", "")]
+    #[case("(-Inf, 0) [5, Inf)", "(-Inf, 0) [5, Inf)", "")]
+    #[case("", "", "")]
+    fn test_multiinterval_symmetric_difference(
+        #[case] this: MultiInterval,
+        #[case] that: MultiInterval,
         #[case] expected: MultiInterval,
     ) {
         assert_eq!(
-            interval.complement(),
+            this.symmetric_difference(&that),
             expected,
-            "MultiInterval.invert failed: {interval}.inverse() should be {expected}",
+            "MultiInterval.symmetric_difference failed: {this}.symmetric_difference({that}) should be {expected}",
+        );
+        assert_eq!(
+            that.symmetric_difference(&this),
+            expected,
+            "MultiInterval.symmetric_difference should be symmetric",
         );
     }
 
@@ -1,8 +1,8 @@
-use super::{IntervalCondition, Predicate};
+use super::{FeatureSpans, IntervalCondition, Predicate};
 use crate::{
-    interval::{Boundary, MultiInterval},
+    interval::MultiInterval,
     ir,
-    parser::ast::{self, BinaryOp, BoolOp, ConstantPosition, ElseNode, EqOp, IfNode, RootNode},
+    parser::ast::{self, BoolOp, ConstantPosition, ElseNode, EqOp, IfNode, RootNode, Span},
 };
 
 const fn resolve_bool_condition(eq_op: &EqOp, bool_val: bool) -> bool {
@@ -18,27 +18,6 @@ const fn resolve_bool_condition(eq_op: &EqOp, bool_val: bool) -> bool {
     }
 }
 
-fn binary_op_to_interval(binop: &BinaryOp, num: f32) -> MultiInterval {
-    use Boundary::{Closed, Open};
-
-    match binop {
-        BinaryOp::NotEqual => MultiInterval::new_closed_point(num).complement(),
-        x => {
-            let (lo_boundary, lo, hi, hi_boundary) = match x {
-                BinaryOp::LessThan => (Open, f32::NEG_INFINITY, num, Open),
-                BinaryOp::GreaterThan => (Open, num, f32::INFINITY, Open),
-                BinaryOp::LessThanEqualTo => (Open, f32::NEG_INFINITY, num, Closed),
-                BinaryOp::GreaterThanEqualTo => (Closed, num, f32::INFINITY, Open),
-                BinaryOp::Equal => (Closed, num, num, Closed),
-                _ => unreachable!(),
-            };
-
-            MultiInterval::new(lo_boundary, lo, hi, hi_boundary)
-                .expect("in binary_op_to_interval we've checked that lo <= hi")
-        }
-    }
-}
-
 fn convert_bool_condition(cond: &ast::BoolCondition) -> ir::Condition {
     let should_equal_to = resolve_bool_condition(&cond.eq_op, cond.constant);
 
@@ -59,7 +38,7 @@ fn convert_binary_condition(cond: &ast::BinaryCondition) -> ir::Condition {
 
     ir::Condition::Interval(IntervalCondition {
         var_name: cond.var_name.to_owned(),
-        interval: binary_op_to_interval(&binary_op, cond.constant),
+        interval: MultiInterval::from_comparison(&binary_op, cond.constant),
     })
 }
 
@@ -73,15 +52,33 @@ fn convert_interval_condition(cond: &ast::IntervalCondition) -> ir::Condition {
     })
 }
 
+fn convert_enum_condition(cond: &ast::EnumCondition) -> ir::Condition {
+    ir::Condition::Enum(ir::EnumCondition {
+        var_name: cond.var_name.to_owned(),
+        values: cond.values.iter().map(|&value| value.to_owned()).collect(),
+        should_be_in: cond.interval_op == ast::IntervalOp::In,
+    })
+}
+
+fn convert_relation_condition(cond: &ast::RelationCondition) -> ir::Condition {
+    ir::Condition::Relation(ir::RelationCondition {
+        left: cond.left.to_owned(),
+        binary_op: cond.binary_op.clone(),
+        right: cond.right.to_owned(),
+    })
+}
+
 fn convert_condition_node(conditions_node: &ast::ConditionsNode) -> ir::Predicate {
     match conditions_node {
         ast::ConditionsNode::Negated(cond) => {
             ir::Predicate::Negated(Box::new(convert_condition_node(cond)))
         }
-        ast::ConditionsNode::Expression(cond) => ir::Predicate::Expression(match cond {
+        ast::ConditionsNode::Expression(cond, _) => ir::Predicate::Expression(match cond {
             ast::Condition::Bool(cond) => convert_bool_condition(cond),
             ast::Condition::Binary(cond) => convert_binary_condition(cond),
             ast::Condition::Interval(cond) => convert_interval_condition(cond),
+            ast::Condition::Enum(cond) => convert_enum_condition(cond),
+            ast::Condition::Relation(cond) => convert_relation_condition(cond),
         }),
         ast::ConditionsNode::Group {
             operator,
@@ -95,35 +92,90 @@ fn convert_condition_node(conditions_node: &ast::ConditionsNode) -> ir::Predicat
     }
 }
 
-fn traverse_body(body: &[IfNode], initial_conditions: &Predicate) -> Vec<Predicate> {
-    let body_conditions = body.iter().flat_map(traverse_if_node);
+/// The spans of the conditions, in the order `walk_predicate` visits their converted predicate
+fn condition_spans(conditions_node: &ast::ConditionsNode, input: &str) -> Vec<Span> {
+    match conditions_node {
+        ast::ConditionsNode::Negated(cond) => condition_spans(cond, input),
+        ast::ConditionsNode::Expression(_, source) => vec![Span::of(input, source)],
+        ast::ConditionsNode::Group { left, right, .. } => {
+            let mut spans = condition_spans(left, input);
+            spans.extend(condition_spans(right, input));
+            spans
+        }
+    }
+}
+
+/// A predicate with the span of the branch it comes from, and the spans of its conditions, see [`FeatureSpans`]
+#[derive(Clone)]
+struct SpannedPredicate {
+    predicate: Predicate,
+    span: Span,
+    conditions: Vec<Span>,
+}
+
+impl SpannedPredicate {
+    fn new(conditions_node: &ast::ConditionsNode, source: &str, input: &str) -> Self {
+        Self {
+            predicate: convert_condition_node(conditions_node),
+            span: Span::of(input, source),
+            conditions: condition_spans(conditions_node, input),
+        }
+    }
+
+    /// Both predicates have to hold, the result comes from the branch of `right`
+    fn and(&self, right: Self) -> Self {
+        Self {
+            predicate: ir::Predicate::Group {
+                left: Box::new(self.predicate.clone()),
+                right: Box::new(right.predicate),
+                operator: BoolOp::And,
+            },
+            span: right.span,
+            conditions: [self.conditions.clone(), right.conditions].concat(),
+        }
+    }
+
+    /// The predicate doesn't hold, the result comes from the branch at `span`
+    fn negated(&self, span: Span) -> Self {
+        Self {
+            predicate: ir::Predicate::Negated(Box::new(self.predicate.clone())),
+            span,
+            conditions: self.conditions.clone(),
+        }
+    }
+}
+
+fn traverse_body(
+    body: &[IfNode],
+    initial_conditions: &SpannedPredicate,
+    input: &str,
+) -> Vec<SpannedPredicate> {
+    let body_conditions = body
+        .iter()
+        .flat_map(|if_node| traverse_if_node(if_node, input));
     body_conditions
-        .map(|body_condition| ir::Predicate::Group {
-            left: Box::new(initial_conditions.clone()),
-            right: Box::new(body_condition),
-            operator: BoolOp::And,
-        })
+        .map(|body_condition| initial_conditions.and(body_condition))
         .collect()
 }
 
-fn traverse_if_node(if_node: &ast::IfNode) -> Vec<ir::Predicate> {
-    let initial_conditions = convert_condition_node(&if_node.conditions);
+fn traverse_if_node(if_node: &ast::IfNode, input: &str) -> Vec<SpannedPredicate> {
+    let initial_conditions = SpannedPredicate::new(&if_node.conditions, if_node.source, input);
 
     let mut predicates_so_far = match &if_node.body {
         None => vec![initial_conditions],
         Some(body) if body.is_empty() => vec![initial_conditions],
         // TODO: We need the initial conditions as well as the body.
-        Some(body) => traverse_body(body, &initial_conditions), // TODO: the body of the if doesn't have to be negated onto the else ifs
+        Some(body) => traverse_body(body, &initial_conditions, input), // TODO: the body of the if doesn't have to be negated onto the else ifs
     };
 
     for else_if_node in &if_node.else_if {
-        let initial_conditions = convert_condition_node(&else_if_node.conditions);
-        let previous_negated_plus_initial_conditions: Vec<Predicate> = predicates_so_far
+        let initial_conditions =
+            SpannedPredicate::new(&else_if_node.conditions, else_if_node.source, input);
+        let previous_negated_plus_initial_conditions: Vec<SpannedPredicate> = predicates_so_far
             .iter()
-            .map(|p| ir::Predicate::Group {
-                left: Box::new(ir::Predicate::Negated(Box::new(p.clone()))),
-                right: Box::new(initial_conditions.clone()),
-                operator: BoolOp::And,
+            .map(|p| {
+                p.negated(initial_conditions.span)
+                    .and(initial_conditions.clone())
             })
             .collect();
 
@@ -131,26 +183,30 @@ fn traverse_if_node(if_node: &ast::IfNode) -> Vec<ir::Predicate> {
             [] => previous_negated_plus_initial_conditions,
             body => previous_negated_plus_initial_conditions
                 .iter()
-                .flat_map(|p| traverse_body(body, p))
+                .flat_map(|p| traverse_body(body, p, input))
                 .collect(),
         };
 
         predicates_so_far.append(&mut else_if_predicates);
     }
 
-    let mut else_predicates = {
-        let previous_negated: Vec<Predicate> = predicates_so_far
-            .iter()
-            .map(|p| ir::Predicate::Negated(Box::new(p.clone())))
-            .collect();
-
-        match &if_node.else_node {
-            None => vec![],
-            Some(ElseNode { body }) if body.is_empty() => previous_negated,
-            Some(ElseNode { body }) => previous_negated
+    let mut else_predicates = match &if_node.else_node {
+        None => vec![],
+        Some(ElseNode { source, body }) => {
+            let else_span = Span::of(input, source);
+            let previous_negated = predicates_so_far
                 .iter()
-                .flat_map(|if_predicate| traverse_body(body, if_predicate))
-                .collect(),
+                .map(|p| p.negated(else_span))
+                .collect::<Vec<_>>();
+
+            if body.is_empty() {
+                previous_negated
+            } else {
+                previous_negated
+                    .iter()
+                    .flat_map(|if_predicate| traverse_body(body, if_predicate, input))
+                    .collect()
+            }
         }
     };
 
@@ -162,11 +218,14 @@ fn traverse_if_node(if_node: &ast::IfNode) -> Vec<ir::Predicate> {
 fn convert_variable(var_node: &ast::VarNode) -> ir::Variable {
     ir::Variable {
         var_name: var_node.var_name.to_owned(),
-        var_type: var_node.var_type,
+        var_type: var_node.var_type.clone(),
+        domain: var_node.domain,
+        default: var_node.default,
     }
 }
 
-fn traverse_feature_node(feature_node: &ast::FeatureNode) -> ir::Feature {
+/// Converts the feature, with the spans of its parts in `input`, which the AST was parsed from.
+pub fn traverse_feature_node(feature_node: &ast::FeatureNode, input: &str) -> ir::Feature {
     let variables = feature_node
         .variables
         .iter()
@@ -176,15 +235,29 @@ fn traverse_feature_node(feature_node: &ast::FeatureNode) -> ir::Feature {
     let predicates = feature_node
         .if_statements
         .iter()
-        .flat_map(traverse_if_node)
-        .collect();
+        .flat_map(|if_node| traverse_if_node(if_node, input))
+        .collect::<Vec<_>>();
+
+    let spans = FeatureSpans {
+        variables: feature_node
+            .variables
+            .iter()
+            .map(|var_node| Span::of(input, var_node.source))
+            .collect(),
+        predicates: predicates.iter().map(|p| p.span).collect(),
+        conditions: predicates.iter().map(|p| p.conditions.clone()).collect(),
+    };
 
     ir::Feature {
         variables,
-        predicates,
+        predicates: predicates.into_iter().map(|p| p.predicate).collect(),
+        spans: Some(spans),
     }
 }
 
-pub fn convert_ast_to_ir<'a>(root: &'a RootNode<'a>) -> Vec<ir::Feature> {
-    root.features.iter().map(traverse_feature_node).collect()
+pub fn convert_ast_to_ir<'a>(root: &'a RootNode<'a>, input: &str) -> Vec<ir::Feature> {
+    root.features
+        .iter()
+        .map(|feature_node| traverse_feature_node(feature_node, input))
+        .collect()
 }
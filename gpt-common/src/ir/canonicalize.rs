@@ -0,0 +1,250 @@
+use super::{
+    ir_to_ntuple::{merge_conjunction, relations_of},
+    Condition, EnumCondition, Feature, IntervalCondition, Predicate, Variable,
+};
+use crate::{
+    interval::{Interval, MultiInterval},
+    parser::ast::{BoolOp, Type},
+};
+
+/// The condition restricted to the values the variable can have: the intervals are clamped to the domain,
+/// and the enum conditions are rewritten as `in` the satisfying declared values, in the declared order.
+///
+/// Returns `None` if no value of the variable satisfies the condition.
+fn restrict_to_variable(condition: Condition, variable: Option<&Variable>) -> Option<Condition> {
+    let restricted = match (condition, variable) {
+        (
+            Condition::Interval(IntervalCondition { var_name, interval }),
+            Some(Variable {
+                domain: Some(domain),
+                ..
+            }),
+        ) => Condition::Interval(IntervalCondition {
+            var_name,
+            interval: interval.clamp_to(domain).0,
+        }),
+        (
+            Condition::Enum(condition),
+            Some(Variable {
+                var_type: Type::Enum(declared_values),
+                ..
+            }),
+        ) => Condition::Enum(EnumCondition {
+            values: declared_values
+                .iter()
+                .filter(|value| condition.values.contains(value) == condition.should_be_in)
+                .cloned()
+                .collect(),
+            should_be_in: true,
+            ..condition
+        }),
+        (condition, _) => condition,
+    };
+
+    match &restricted {
+        Condition::Interval(IntervalCondition { interval, .. }) if interval.is_empty() => None,
+        Condition::Enum(EnumCondition {
+            values,
+            should_be_in: true,
+            ..
+        }) if values.is_empty() => None,
+        _ => Some(restricted),
+    }
+}
+
+/// Every value of the variable satisfies the condition, so it doesn't constrain anything
+fn covers_variable(condition: &Condition, variable: Option<&Variable>) -> bool {
+    match condition {
+        Condition::Bool(_) | Condition::Relation(_) => false,
+        Condition::Interval(IntervalCondition { interval, .. }) => {
+            let domain = variable
+                .and_then(|variable| variable.domain)
+                .unwrap_or_else(Interval::whole_line);
+
+            *interval == MultiInterval::from_interval(domain)
+        }
+        Condition::Enum(condition) => match variable.map(|variable| &variable.var_type) {
+            Some(Type::Enum(declared_values)) => declared_values
+                .iter()
+                .all(|value| condition.values.contains(value) == condition.should_be_in),
+            _ => !condition.should_be_in && condition.values.is_empty(),
+        },
+    }
+}
+
+/// Merges the conditions of the conjunction per variable, and sorts them by the variable names.
+/// The conditions covering their whole variable are removed, except if all of them do,
+/// then the first one is kept, so the conjunction still has a condition.
+///
+/// Returns `None` if the conjunction can't be satisfied.
+fn canonicalize_conjunction(
+    conditions: Vec<Condition>,
+    feature: &Feature,
+) -> Option<Vec<Condition>> {
+    let find_variable = |var_name: &str| feature.variable(var_name);
+
+    let mut relations = relations_of(&conditions);
+    relations.sort_by_cached_key(ToString::to_string);
+    relations.dedup();
+
+    let mut conditions = merge_conjunction(conditions)
+        .ok()?
+        .into_values()
+        .map(|condition| {
+            let variable = find_variable(condition.get_variable());
+            restrict_to_variable(condition, variable)
+        })
+        .chain(
+            relations
+                .into_iter()
+                .map(|relation| Some(Condition::Relation(relation))),
+        )
+        .collect::<Option<Vec<_>>>()?;
+
+    // The sort is stable, so the relations stay after the other conditions of their left variable
+    conditions.sort_by(|left, right| left.get_variable().cmp(right.get_variable()));
+
+    if conditions
+        .iter()
+        .all(|condition| covers_variable(condition, find_variable(condition.get_variable())))
+    {
+        conditions.truncate(1);
+    } else {
+        conditions.retain(|condition| {
+            !covers_variable(condition, find_variable(condition.get_variable()))
+        });
+    }
+
+    Some(conditions)
+}
+
+/// The predicate as a disjunction of its canonical conjunctions, which are sorted and deduplicated.
+///
+/// Returns `None` if the predicate can't be satisfied.
+fn canonicalize_predicate(predicate: &Predicate, feature: &Feature) -> Option<Predicate> {
+    let mut conjunctions = predicate
+        .disjunctive_normal_form()
+        .into_iter()
+        .filter_map(|conditions| canonicalize_conjunction(conditions, feature))
+        .filter_map(Predicate::from_conjunction)
+        .collect::<Vec<_>>();
+
+    conjunctions.sort_by_cached_key(ToString::to_string);
+    conjunctions.dedup();
+
+    conjunctions
+        .into_iter()
+        .reduce(|left, right| Predicate::Group {
+            left: Box::new(left),
+            right: Box::new(right),
+            operator: BoolOp::Or,
+        })
+}
+
+/// The canonical form of the feature, so features expressing the same constraints differently can be compared structurally.
+///
+/// - The variables are sorted by their names.
+/// - The predicates are in disjunctive normal form, the conditions of a conjunction are merged per variable, and sorted.
+/// - The conditions are restricted to the domains of the variables, and the ones covering the whole domain are removed.
+/// - The unsatisfiable conjunctions and predicates are dropped.
+///
+/// Example: `if(x > 0 && x < 10 && flag == true)` and `if(flag == true && x in (0, 10))` have the same canonical form.
+pub fn canonicalize(feature: &Feature) -> Feature {
+    let mut variables = feature.variables.clone();
+    variables.sort_by(|left, right| left.var_name.cmp(&right.var_name));
+
+    let predicates = feature
+        .predicates
+        .iter()
+        .filter_map(|predicate| canonicalize_predicate(predicate, feature))
+        .collect();
+
+    // The predicates are rewritten, so the spans don't match them anymore
+    Feature {
+        variables,
+        predicates,
+        spans: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::canonicalize;
+    use crate::parser::parse_gpt_to_ir;
+
+    fn canonical_predicates(input: &str) -> Vec<String> {
+        let (_, features) = parse_gpt_to_ir(input).unwrap();
+
+        canonicalize(&features[0])
+            .predicates
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[rstest]
+    #[case::merged_and_sorted(
+        "var x: int\nvar flag: bool\nif(x > 0 && flag == true && x < 10)",
+        vec!["(flag == true && x in (0, 10))"]
+    )]
+    #[case::unsatisfiable(
+        "var x: int\nif(x > 10 && x < 5)\nif(x == 1)",
+        vec!["x in [1, 1]"]
+    )]
+    #[case::unsatisfiable_conjunction_dropped(
+        "var x: int\nvar y: int\nif((x > 10 && x < 5) || y > 0)",
+        vec!["y in (0, Inf)"]
+    )]
+    #[case::whole_domain(
+        "var x: int in [0, 10]\nvar y: int\nif(x >= 0 && y < 3)",
+        vec!["y in (-Inf, 3)"]
+    )]
+    #[case::only_whole_domain(
+        "var x: int in [0, 10]\nif(x >= -5)",
+        vec!["x in [0, 10]"]
+    )]
+    #[case::restricted_to_domain(
+        "var x: int in [0, 10]\nif(x < 5)",
+        vec!["x in [0, 5)"]
+    )]
+    #[case::disjunction_sorted_and_deduplicated(
+        "var x: int\nif(x > 5 || x < 0 || x > 5)",
+        vec!["(x in (-Inf, 0) || x in (5, Inf))"]
+    )]
+    #[case::enum_in_declared_order(
+        "var status: enum {active, closed, on_hold}\nif(status not in {active})\nif(status in {active, closed, on_hold})",
+        vec!["status in {closed, on_hold}", "status in {active, closed, on_hold}"]
+    )]
+    fn test_canonicalize(#[case] input: &str, #[case] expected: Vec<&str>) {
+        assert_eq!(canonical_predicates(input), expected);
+    }
+
+    #[test]
+    fn test_canonicalize_same_constraints() {
+        assert_eq!(
+            canonical_predicates(
+                "var x: int\nvar flag: bool\nif(x > 0 && x < 10 && flag == true)\nif(!(x >= 0))"
+            ),
+            canonical_predicates(
+                "var flag: bool\nvar x: int\nif(flag == true && x in (0, 10))\nif(x < 0)"
+            ),
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_sorts_variables() {
+        let (_, features) =
+            parse_gpt_to_ir("var y: int\nvar x: bool\nvar z: int\nif(x == true)").unwrap();
+
+        let var_names = canonicalize(&features[0])
+            .variables
+            .into_iter()
+            .map(|variable| variable.var_name)
+            .collect::<Vec<_>>();
+
+        assert_eq!(var_names, vec!["x", "y", "z"]);
+    }
+}
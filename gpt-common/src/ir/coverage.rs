@@ -0,0 +1,301 @@
+use std::{collections::BTreeMap, fmt, mem};
+
+use itertools::Itertools;
+
+use super::{
+    ir_to_ntuple::{merge_conjunction, PredicateId},
+    Condition, Feature, IntervalCondition, Predicate,
+};
+use crate::interval::{Intersectable, Interval, MultiInterval, Unionable};
+
+/// How the predicates of a feature cover the domain of a variable, see [`analyze_coverage`]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CoverageReport {
+    /// The values where at least one predicate applies
+    pub covered: MultiInterval,
+    /// The values of the domain where no predicate applies
+    pub gaps: MultiInterval,
+    /// The values where at least two predicates apply
+    pub overlaps: MultiInterval,
+}
+
+/// The values of the variable for which the predicate can be true.
+///
+/// Every conjunction of the disjunctive normal form mentioning the variable contributes the intersection of its conditions.
+fn predicate_region(predicate: &Predicate, var_name: &str) -> MultiInterval {
+    predicate
+        .disjunctive_normal_form()
+        .into_iter()
+        .filter_map(|conjunction| {
+            conjunction
+                .into_iter()
+                .filter_map(|condition| match condition {
+                    Condition::Interval(IntervalCondition {
+                        var_name: cond_var_name,
+                        interval,
+                    }) if cond_var_name == var_name => Some(interval),
+                    _ => None,
+                })
+                .reduce(|region, interval| {
+                    region
+                        .intersect(&interval)
+                        .unwrap_or_else(MultiInterval::new_empty)
+                })
+        })
+        .fold(MultiInterval::new_empty(), |region, interval| {
+            region.union(&interval)
+        })
+}
+
+/// Checks whether the predicates of the feature cover the whole domain of a variable, and where they overlap.
+///
+/// Only the values of the domain are analyzed, which is the one declared for the variable, or `(-Inf, Inf)`.
+/// The predicates and conjunctions which don't mention the variable are left out, because they don't constrain it.
+///
+/// Example: `x < 0` and `x in [-10, 10]` cover `(-Inf, 10]`, leave the gap `(10, Inf)`, and overlap in `[-10, 0)`.
+pub fn analyze_coverage(feature: &Feature, var_name: &str) -> CoverageReport {
+    let domain = feature
+        .variable(var_name)
+        .and_then(|variable| variable.domain)
+        .unwrap_or_else(Interval::whole_line);
+
+    let regions = feature
+        .predicates
+        .iter()
+        .map(|predicate| predicate_region(predicate, var_name).clamp_to(&domain).0)
+        .collect::<Vec<_>>();
+
+    let covered = regions
+        .iter()
+        .fold(MultiInterval::new_empty(), |covered, region| {
+            covered.union(region)
+        });
+
+    let overlaps = regions
+        .iter()
+        .enumerate()
+        .flat_map(|(i, region)| region.intersect_each(&regions[i + 1..]))
+        .flatten()
+        .fold(MultiInterval::new_empty(), |overlaps, overlap| {
+            overlaps.union(&overlap)
+        });
+
+    CoverageReport {
+        gaps: covered.complement_in(&domain),
+        covered,
+        overlaps,
+    }
+}
+
+/// Two predicates are both satisfied by the values of the variable in `region`, see [`overlapping_classes`]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ClassOverlap {
+    pub first: PredicateId,
+    pub second: PredicateId,
+    pub var_name: String,
+    pub region: MultiInterval,
+}
+
+/// Like `predicates 2 and 5 both cover x ∈ [5, 8]`
+impl fmt::Display for ClassOverlap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "predicates {} and {} both cover {} ∈ {}",
+            self.first, self.second, self.var_name, self.region
+        )
+    }
+}
+
+fn domain_of(feature: &Feature, var_name: &str) -> Interval {
+    feature
+        .variable(var_name)
+        .and_then(|variable| variable.domain)
+        .unwrap_or_else(Interval::whole_line)
+}
+
+/// Whether a variable has conditions of different types, like `x > 0` and `x == true`, which can't be merged
+fn has_mismatched_types(conditions: &[Condition]) -> bool {
+    conditions
+        .iter()
+        .filter(|condition| !matches!(condition, Condition::Relation(_)))
+        .tuple_combinations()
+        .any(|(this, other)| {
+            this.get_variable() == other.get_variable()
+                && mem::discriminant(this) != mem::discriminant(other)
+        })
+}
+
+/// The regions of the interval variables constrained by both conjunctions, where both of them are satisfied.
+/// Returns `None` if there is no value satisfying both conjunctions inside the domains,
+/// or a variable has conditions of different types in them, so no value can satisfy both.
+fn conjunction_overlap(
+    left: &[Condition],
+    right: &[Condition],
+    feature: &Feature,
+) -> Option<Vec<(String, MultiInterval)>> {
+    let conditions = left.iter().chain(right).cloned().collect::<Vec<_>>();
+    if has_mismatched_types(&conditions) {
+        return None;
+    }
+
+    let merged = merge_conjunction(conditions).ok()?;
+    let is_constrained_by = |conditions: &[Condition], var_name: &str| {
+        conditions
+            .iter()
+            .any(|cond| cond.get_variable() == var_name)
+    };
+
+    let mut regions = Vec::new();
+    for (var_name, condition) in merged {
+        let Condition::Interval(IntervalCondition { interval, .. }) = condition else {
+            continue;
+        };
+
+        let region = interval.clamp_to(&domain_of(feature, &var_name)).0;
+        if region.is_empty() {
+            return None;
+        }
+
+        if is_constrained_by(left, &var_name) && is_constrained_by(right, &var_name) {
+            regions.push((var_name, region));
+        }
+    }
+
+    Some(regions)
+}
+
+/// The regions where two predicates overlap, so a value satisfies both of them, and they may generate redundant tests.
+///
+/// The predicates overlap if their conjunctions in disjunctive normal form can be satisfied at the same time,
+/// the conflicting bool and enum conditions rule out the overlap. The regions are reported for the interval variables
+/// constrained by both predicates, restricted to the domains. The result is ordered by the predicates, then the variables.
+///
+/// Example: `x in [0, 8] && flag == true` and `x > 5` both cover `x ∈ (5, 8]`, but `x > 5 && flag == false` doesn't overlap with the first one.
+pub fn overlapping_classes(feature: &Feature) -> Vec<ClassOverlap> {
+    let conjunctions = feature
+        .predicates
+        .iter()
+        .map(Predicate::disjunctive_normal_form)
+        .collect::<Vec<_>>();
+
+    let mut overlaps = Vec::new();
+    for (first, first_conjunctions) in conjunctions.iter().enumerate() {
+        for (second, second_conjunctions) in conjunctions.iter().enumerate().skip(first + 1) {
+            let mut regions = BTreeMap::<String, MultiInterval>::new();
+
+            for left in first_conjunctions {
+                for right in second_conjunctions {
+                    for (var_name, region) in
+                        conjunction_overlap(left, right, feature).unwrap_or_default()
+                    {
+                        let region = regions
+                            .get(&var_name)
+                            .map_or_else(|| region.clone(), |previous| previous.union(&region));
+                        regions.insert(var_name, region);
+                    }
+                }
+            }
+
+            overlaps.extend(regions.into_iter().map(|(var_name, region)| ClassOverlap {
+                first,
+                second,
+                var_name,
+                region,
+            }));
+        }
+    }
+
+    overlaps
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::{analyze_coverage, overlapping_classes, CoverageReport};
+    use crate::{interval::test::multiint, parser::parse_gpt_to_ir};
+
+    #[rstest]
+    #[case::gap_and_overlap(
+        "var x: int\nif(x < 0)\nif(x in [-10, 10])",
+        "(-Inf, 10]",
+        "(10, Inf)",
+        "[-10, 0)"
+    )]
+    #[case::full_coverage("var x: int\nif(x < 0)\nif(x >= 0)", "(-Inf, Inf)", "", "")]
+    #[case::domain(
+        "var x: int in [0, 100]\nif(x in [0, 50))\nif(x > 80)",
+        "[0, 50) (80, 100]",
+        "[50, 80]",
+        ""
+    )]
+    #[case::conjunction(
+        "var x: int\nvar flag: bool\nif(x > 0 && x < 10 && flag == true)\nif(flag == false)",
+        "(0, 10)",
+        "(-Inf, 0] [10, Inf)",
+        ""
+    )]
+    #[case::disjunction(
+        "var x: int\nif(x < 0 || x > 10)\nif(x in [5, 15])",
+        "(-Inf, 0) [5, Inf)",
+        "[0, 5)",
+        "(10, 15]"
+    )]
+    #[case::not_mentioned("var x: int\nvar y: int\nif(y < 0)", "", "(-Inf, Inf)", "")]
+    fn test_analyze_coverage(
+        #[case] input: &str,
+        #[case] covered: &str,
+        #[case] gaps: &str,
+        #[case] overlaps: &str,
+    ) {
+        let (_, features) = parse_gpt_to_ir(input).unwrap();
+
+        assert_eq!(
+            analyze_coverage(&features[0], "x"),
+            CoverageReport {
+                covered: multiint(covered),
+                gaps: multiint(gaps),
+                overlaps: multiint(overlaps),
+            }
+        );
+    }
+
+    #[rstest]
+    #[case::overlap("var x: int\nif(x in [0, 8])\nif(x > 5)", vec!["predicates 0 and 1 both cover x ∈ (5, 8]"])]
+    #[case::exclusive("var x: int\nif(x < 0)\nif(x >= 0)", vec![])]
+    #[case::excluded_by_bool(
+        "var x: int\nvar flag: bool\nif(x in [0, 8] && flag == true)\nif(x > 5 && flag == false)",
+        vec![]
+    )]
+    #[case::excluded_by_enum(
+        "var x: int\nvar status: enum {a, b}\nif(x > 0 && status in {a})\nif(x > 5 && status not in {a})",
+        vec![]
+    )]
+    #[case::domain("var x: int in [0, 10]\nif(x > 5)\nif(x >= 8)\nif(x > 20)", vec!["predicates 0 and 1 both cover x ∈ [8, 10]"])]
+    #[case::disjunction(
+        "var x: int\nif(x < 0 || x > 10)\nif(x in [-5, 15])",
+        vec!["predicates 0 and 1 both cover x ∈ [-5, 0) (10, 15]"]
+    )]
+    #[case::per_variable(
+        "var x: int\nvar y: int\nif(x > 0 && y < 5)\nif(y > 0)\nif(x < 3 && y > 0)",
+        vec![
+            "predicates 0 and 1 both cover y ∈ (0, 5)",
+            "predicates 0 and 2 both cover x ∈ (0, 3)",
+            "predicates 0 and 2 both cover y ∈ (0, 5)",
+            "predicates 1 and 2 both cover y ∈ (0, Inf)",
+        ]
+    )]
+    #[case::mismatched_types("var x: int\nif(x > 0)\nif(x == true)", vec![])]
+    fn test_overlapping_classes(#[case] input: &str, #[case] expected: Vec<&str>) {
+        let (_, features) = parse_gpt_to_ir(input).unwrap();
+
+        let overlaps = overlapping_classes(&features[0])
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        assert_eq!(overlaps, expected);
+    }
+}
@@ -0,0 +1,221 @@
+use std::fmt;
+
+use super::{Condition, Feature, IntervalCondition};
+use crate::{
+    interval::{MultiInterval, Unionable},
+    parser::ast::Type,
+};
+
+#[derive(PartialEq, Clone, Debug)]
+pub struct TypeChange {
+    pub var_name: String,
+    pub old: Type,
+    pub new: Type,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct RegionChange {
+    pub var_name: String,
+    /// The values which are only constrained in the new feature
+    pub grown: MultiInterval,
+    /// The values which were only constrained in the old feature
+    pub shrunk: MultiInterval,
+}
+
+/// The differences between two versions of a feature
+#[derive(PartialEq, Clone, Debug)]
+pub struct FeatureDiff {
+    pub added_variables: Vec<String>,
+    pub removed_variables: Vec<String>,
+    pub changed_types: Vec<TypeChange>,
+    /// The variables in both features, where the union of the intervals in the conditions changed
+    pub changed_regions: Vec<RegionChange>,
+}
+
+impl FeatureDiff {
+    pub const fn is_empty(&self) -> bool {
+        self.added_variables.is_empty()
+            && self.removed_variables.is_empty()
+            && self.changed_types.is_empty()
+            && self.changed_regions.is_empty()
+    }
+}
+
+/// The union of all the intervals the variable is constrained to in the predicates of the feature
+fn constrained_region(feature: &Feature, var_name: &str) -> MultiInterval {
+    feature
+        .predicates
+        .iter()
+        .flat_map(|predicate| predicate.disjunctive_normal_form())
+        .flatten()
+        .filter_map(|condition| match condition {
+            Condition::Interval(IntervalCondition {
+                var_name: cond_var_name,
+                interval,
+            }) if cond_var_name == var_name => Some(interval),
+            _ => None,
+        })
+        .fold(MultiInterval::new_empty(), |region, interval| {
+            region.union(&interval)
+        })
+}
+
+/// Compares two versions of a feature, the variables are matched by their names.
+pub fn diff_features(old: &Feature, new: &Feature) -> FeatureDiff {
+    let added_variables = new
+        .variables
+        .iter()
+        .filter(|new_var| old.variable(&new_var.var_name).is_none())
+        .map(|variable| variable.var_name.clone())
+        .collect();
+
+    let removed_variables = old
+        .variables
+        .iter()
+        .filter(|old_var| new.variable(&old_var.var_name).is_none())
+        .map(|variable| variable.var_name.clone())
+        .collect();
+
+    let variables_in_both = new
+        .variables
+        .iter()
+        .filter_map(|new_var| {
+            old.variable(&new_var.var_name)
+                .map(|old_var| (old_var, new_var))
+        })
+        .collect::<Vec<_>>();
+
+    let changed_types = variables_in_both
+        .iter()
+        .filter(|(old_var, new_var)| old_var.var_type != new_var.var_type)
+        .map(|(old_var, new_var)| TypeChange {
+            var_name: new_var.var_name.clone(),
+            old: old_var.var_type.clone(),
+            new: new_var.var_type.clone(),
+        })
+        .collect();
+
+    let changed_regions = variables_in_both
+        .iter()
+        .filter_map(|(_, variable)| {
+            let old_region = constrained_region(old, &variable.var_name);
+            let new_region = constrained_region(new, &variable.var_name);
+
+            if old_region.symmetric_difference(&new_region).is_empty() {
+                return None;
+            }
+
+            Some(RegionChange {
+                var_name: variable.var_name.clone(),
+                grown: new_region.difference(&old_region),
+                shrunk: old_region.difference(&new_region),
+            })
+        })
+        .collect();
+
+    FeatureDiff {
+        added_variables,
+        removed_variables,
+        changed_types,
+        changed_regions,
+    }
+}
+
+/// One change per line, like `+ x`, `- y`, `~ z: Integer -> Bool` and `z: grown [0, 10], shrunk (20, 30]`
+impl fmt::Display for FeatureDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for var_name in &self.added_variables {
+            writeln!(f, "+ {var_name}")?;
+        }
+        for var_name in &self.removed_variables {
+            writeln!(f, "- {var_name}")?;
+        }
+        for TypeChange { var_name, old, new } in &self.changed_types {
+            writeln!(f, "~ {var_name}: {old:?} -> {new:?}")?;
+        }
+        for RegionChange {
+            var_name,
+            grown,
+            shrunk,
+        } in &self.changed_regions
+        {
+            writeln!(f, "{var_name}: grown {grown}, shrunk {shrunk}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{diff_features, FeatureDiff, RegionChange, TypeChange};
+    use crate::{interval::test::multiint, parser::ast::Type, parser::parse_gpt_to_ir};
+
+    fn diff(old: &str, new: &str) -> FeatureDiff {
+        let (_, old) = parse_gpt_to_ir(old).unwrap();
+        let (_, new) = parse_gpt_to_ir(new).unwrap();
+
+        diff_features(&old[0], &new[0])
+    }
+
+    #[test]
+    fn test_diff_features_same() {
+        let feature = r#"
+            var x: int
+            var flag: bool
+
+            if(x < 10 && flag == true)
+        "#;
+
+        let result = diff(feature, feature);
+
+        assert!(result.is_empty());
+        assert_eq!(result.to_string(), "");
+    }
+
+    #[test]
+    fn test_diff_features() {
+        let old = r#"
+            var x: int
+            var y: int
+            var removed: bool
+
+            if(x in [0, 10] && y < 5)
+            if(x in (20, 30] || y > 100)
+        "#;
+        let new = r#"
+            var x: int
+            var y: num(0.1)
+            var added: bool
+
+            if(x in [0, 15] && y < 5)
+            if(added == true || y > 100)
+        "#;
+
+        let result = diff(old, new);
+
+        assert_eq!(
+            result,
+            FeatureDiff {
+                added_variables: vec!["added".to_owned()],
+                removed_variables: vec!["removed".to_owned()],
+                changed_types: vec![TypeChange {
+                    var_name: "y".to_owned(),
+                    old: Type::Integer,
+                    new: Type::Float { precision: 0.1 },
+                }],
+                changed_regions: vec![RegionChange {
+                    var_name: "x".to_owned(),
+                    grown: multiint("(10, 15]"),
+                    shrunk: multiint("(20, 30]"),
+                }],
+            }
+        );
+        assert_eq!(
+            result.to_string(),
+            "+ added\n- removed\n~ y: Integer -> Float { precision: 0.1 }\nx: grown (10, 15], shrunk (20, 30]\n"
+        );
+    }
+}
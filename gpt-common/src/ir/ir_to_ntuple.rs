@@ -1,10 +1,20 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use thiserror::Error;
 
-use super::{BoolCondition, Condition, Feature, IntervalCondition};
+use super::{
+    coverage::{overlapping_classes, ClassOverlap},
+    visit::{walk_predicate, Visitor},
+    BoolCondition, Condition, EnumCondition, Feature, IntervalCondition, RelationCondition,
+};
 use crate::{
-    dto::{BoolDTO, BoolExpression, Input, IntervalDTO, NTupleInput},
-    interval::Intersectable,
+    dto::{BoolDTO, BoolExpression, EnumDTO, Input, IntervalDTO, NTupleInput},
+    interval::{Intersectable, Interval, MultiInterval},
     ir,
+    parser::ast::{Literal, Type},
     util::UniquesVec,
 };
 
@@ -20,20 +30,93 @@ const fn convert_bool_dto(condition: &ir::BoolCondition) -> BoolDTO {
     }
 }
 
+/// The interval of the condition is restricted to the domain of the variable.
+/// The negated conditions are complements in `(-Inf, Inf)`, so this makes them complements in the domain instead.
+/// If the condition is outside of the domain, the interval is empty, and the tuple is unsatisfiable.
 fn convert_interval_dto(variable: &ir::Variable, condition: &ir::IntervalCondition) -> IntervalDTO {
     let precision = variable.var_type.get_precision().expect("Type error: when converting an interval dto in convert_interval_dto, the variable type doesn't have a precision!");
 
+    let interval = match variable.domain {
+        Some(domain) => {
+            let (interval, is_truncated) = condition.interval.clamp_to(&domain);
+            if is_truncated {
+                log::warn!(
+                    "The condition {} ∈ {} has values outside of the domain {domain}",
+                    variable.var_name,
+                    condition.interval
+                );
+            }
+            interval
+        }
+        None => condition.interval.clone(),
+    };
+
     IntervalDTO {
-        interval: condition.interval.clone(),
+        interval,
         precision,
         is_constant: false,
+        is_integer: variable.var_type.is_integer(),
+    }
+}
+
+/// The default value of the variable as a constant input, so the test case generator doesn't create boundary values for it.
+fn convert_default(variable: &ir::Variable) -> Option<Input> {
+    variable.default.map(|default| match default {
+        Literal::Bool(bool_val) => Input::Bool(BoolDTO {
+            expression: if bool_val {
+                BoolExpression::IsTrue
+            } else {
+                BoolExpression::IsFalse
+            },
+            bool_val,
+            is_constant: true,
+        }),
+        Literal::Number(num) => Input::Interval(IntervalDTO {
+            interval: MultiInterval::new_closed_point(num),
+            precision: variable.var_type.get_precision().expect("Type error: when converting a default value in convert_default, the variable type doesn't have a precision!"),
+            is_constant: true,
+            is_integer: variable.var_type.is_integer(),
+        }),
+    })
+}
+
+/// Splits the declared values of the variable into the ones satisfying the condition, and the others.
+/// The values of the condition which are not declared are left out.
+fn convert_enum_dto(variable: &ir::Variable, condition: &ir::EnumCondition) -> EnumDTO {
+    let Type::Enum(declared_values) = &variable.var_type else {
+        panic!("Type error: when converting an enum dto in convert_enum_dto, the variable type is not an enum!");
+    };
+
+    let (values, other_values) = declared_values
+        .iter()
+        .cloned()
+        .partition(|value| condition.values.contains(value) == condition.should_be_in);
+
+    EnumDTO {
+        values,
+        other_values,
     }
 }
 
-fn convert_condition(variable: &ir::Variable, condition: ir::Condition) -> Input {
+/// The whole domain of a variable in a relation, which isn't constrained by the other conditions,
+/// so the relation can be checked on its generated values.
+fn convert_relation_variable(variable: &ir::Variable) -> Input {
+    Input::Interval(IntervalDTO {
+        interval: MultiInterval::from_interval(variable.domain.unwrap_or_else(Interval::whole_line)),
+        precision: variable.var_type.get_precision().expect("Type error: when converting a variable of a relation in convert_relation_variable, the variable type doesn't have a precision!"),
+        is_constant: false,
+        is_integer: variable.var_type.is_integer(),
+    })
+}
+
+fn convert_condition(variable: &ir::Variable, condition: &ir::Condition) -> Input {
     match condition {
-        ir::Condition::Bool(cond) => Input::Bool(convert_bool_dto(&cond)),
-        ir::Condition::Interval(cond) => Input::Interval(convert_interval_dto(variable, &cond)),
+        ir::Condition::Bool(cond) => Input::Bool(convert_bool_dto(cond)),
+        ir::Condition::Interval(cond) => Input::Interval(convert_interval_dto(variable, cond)),
+        ir::Condition::Enum(cond) => Input::Enum(convert_enum_dto(variable, cond)),
+        ir::Condition::Relation(cond) => {
+            panic!("The relation {cond} should not be merged into the tuple, it doesn't belong to a single variable!")
+        }
     }
 }
 
@@ -62,93 +145,1183 @@ fn convert_condition(variable: &ir::Variable, condition: ir::Condition) -> Input
 //         .collect()
 // }
 
-fn convert_predicate_to_ntuple(
-    variables: &[ir::Variable],
-    predicate: &ir::Predicate,
-) -> Vec<NTupleInput> {
+/// Merges the conditions of a conjunction variable-by-variable, by intersecting the conditions of the same variable.
+/// The relations are left out, because they constrain two variables together, see [`relations_of`].
+///
+/// Returns the conflicting pair of conditions if a variable has no value satisfying all of its conditions.
+/// The first one of the pair is the merge of the earlier conditions of the variable, the second one is the condition it conflicts with.
+pub(super) fn merge_conjunction(
+    conditions: Vec<Condition>,
+) -> Result<HashMap<String, Condition>, Box<(Condition, Condition)>> {
+    let mut ntuple = HashMap::<String, Condition>::new();
+
+    for cond in conditions {
+        if let Condition::Relation(_) = cond {
+            continue;
+        }
+
+        let var_name = cond.get_variable().to_owned();
+        // The conditions are owned, so they can be moved into the tuple without cloning
+        let to_insert = match (cond, ntuple.get(&var_name)) {
+            (x, None) => x,
+            (
+                x @ Condition::Bool(BoolCondition {
+                    should_equal_to: new,
+                    ..
+                }),
+                Some(
+                    previous @ Condition::Bool(BoolCondition {
+                        should_equal_to: old,
+                        ..
+                    }),
+                ),
+            ) => {
+                if new != *old {
+                    return Err(Box::new((previous.clone(), x)));
+                }
+                x
+            }
+            (
+                Condition::Interval(IntervalCondition {
+                    var_name, interval: new,
+                }),
+                Some(
+                    previous @ Condition::Interval(IntervalCondition {
+                        interval: old, ..
+                    }),
+                ),
+            ) => match old.intersect(&new) {
+                Some(intersection) => Condition::Interval(IntervalCondition {
+                    var_name,
+                    interval: intersection,
+                }),
+                None => {
+                    return Err(Box::new((
+                        previous.clone(),
+                        Condition::Interval(IntervalCondition {
+                            var_name,
+                            interval: new,
+                        }),
+                    )))
+                }
+            },
+            (Condition::Enum(new), Some(previous @ Condition::Enum(old))) => {
+                match old.intersect(&new) {
+                    Some(intersection) => Condition::Enum(intersection),
+                    None => return Err(Box::new((previous.clone(), Condition::Enum(new)))),
+                }
+            }
+            (x, y) => panic!("Mismatched types in predicate! Variable {var_name} has conditions of different types! {x:#?} and {y:#?}")
+        };
+        ntuple.insert(var_name, to_insert);
+    }
+
+    Ok(ntuple)
+}
+
+/// The relations between the variables in the conjunction, like `start <= end`
+pub(super) fn relations_of(conditions: &[Condition]) -> Vec<RelationCondition> {
+    conditions
+        .iter()
+        .filter_map(|condition| match condition {
+            Condition::Relation(relation) => Some(relation.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A conjunction with conflicting conditions has no values satisfying it, so it has no tuple
+fn convert_conjunction_to_ntuple(
+    feature: &Feature,
+    conditions: Vec<Condition>,
+) -> Option<NTupleInput> {
+    let relations = relations_of(&conditions);
+
+    merge_conjunction(conditions)
+        .ok()
+        .map(|x| {
+            x.into_iter().map(|(var_name, condition)| {
+                let variable = feature
+                    .variable(&var_name)
+                    // TODO: This should be an actual error in a Result type
+                    .unwrap_or_else(|| panic!("Undefined variable: {var_name}"));
+                (var_name, convert_condition(variable, &condition))
+            })
+        })
+        .map(|x| {
+            let mut inputs = x.collect::<HashMap<String, Input>>();
+
+            // Variables which are not constrained by the predicate get their default values, or can be anything,
+            // except the variables of the relations, which can be anything in their domains
+            for variable in &feature.variables {
+                if !inputs.contains_key(&variable.var_name) {
+                    let in_relation = relations.iter().any(|relation| {
+                        relation.left == variable.var_name || relation.right == variable.var_name
+                    });
+                    let input = if in_relation {
+                        convert_relation_variable(variable)
+                    } else {
+                        convert_default(variable).unwrap_or(Input::Any)
+                    };
+                    inputs.insert(variable.var_name.clone(), input);
+                }
+            }
+
+            NTupleInput { inputs }
+        })
+}
+
+fn convert_predicate_to_ntuple(feature: &Feature, predicate: &ir::Predicate) -> Vec<NTupleInput> {
     predicate
         .conjunction_of_conditions()
         .into_iter()
+        .filter_map(|conditions| convert_conjunction_to_ntuple(feature, conditions))
+        .collect()
+}
+
+/// The index of a predicate in its `Feature`
+pub type PredicateId = usize;
+
+/// A non-fatal issue of the feature, which is probably a mistake in the spec, but the tuples can still be generated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Warning {
+    /// The predicate doesn't constrain the variable, so its default value is used
+    DefaultedVariable {
+        predicate_id: PredicateId,
+        var_name: String,
+    },
+    /// The condition has a finite bound outside of the domain of the variable, like `x in [-10, 20]` for the domain `[0, 100]`,
+    /// or it has no values in the domain at all. The values outside of the domain are left out.
+    OutsideOfDomain {
+        predicate_id: PredicateId,
+        var_name: String,
+        interval: MultiInterval,
+        domain: Interval,
+    },
+    /// The condition covers the whole domain of the variable, so it doesn't constrain anything
+    WholeDomain {
+        predicate_id: PredicateId,
+        var_name: String,
+    },
+    /// A value satisfies both predicates, so they may generate redundant tests, see [`overlapping_classes`]
+    OverlappingClasses(ClassOverlap),
+}
+
+/// Like `predicate 0: x ∈ [-10, 20] is outside of the domain [0, 100]`
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DefaultedVariable {
+                predicate_id,
+                var_name,
+            } => write!(
+                f,
+                "predicate {predicate_id}: {var_name} is not constrained, its default value is used"
+            ),
+            Self::OutsideOfDomain {
+                predicate_id,
+                var_name,
+                interval,
+                domain,
+            } => write!(
+                f,
+                "predicate {predicate_id}: {var_name} ∈ {interval} is outside of the domain {domain}"
+            ),
+            Self::WholeDomain {
+                predicate_id,
+                var_name,
+            } => write!(
+                f,
+                "predicate {predicate_id}: {var_name} covers its whole domain"
+            ),
+            Self::OverlappingClasses(overlap) => write!(f, "{overlap}"),
+        }
+    }
+}
+
+/// The warnings of the predicate, for each conjunction in disjunctive normal form
+fn predicate_warnings(
+    feature: &Feature,
+    predicate_id: PredicateId,
+    predicate: &ir::Predicate,
+) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+
+    for conditions in predicate.disjunctive_normal_form() {
+        for variable in &feature.variables {
+            let is_constrained = conditions
+                .iter()
+                .any(|condition| condition.mentions(&variable.var_name));
+
+            if !is_constrained && variable.default.is_some() {
+                warnings.push(Warning::DefaultedVariable {
+                    predicate_id,
+                    var_name: variable.var_name.clone(),
+                });
+            }
+        }
+
+        for condition in conditions {
+            let Condition::Interval(IntervalCondition { var_name, interval }) = condition else {
+                continue;
+            };
+            let domain = feature
+                .variable(&var_name)
+                .and_then(|variable| variable.domain);
+
+            let clamped =
+                domain.map_or_else(|| interval.clone(), |domain| interval.clamp_to(&domain).0);
+            // The comparisons, like `x < 50`, are unbounded, only their finite bounds were written in the spec
+            let has_bound_outside = |domain: &Interval| {
+                interval
+                    .intervals
+                    .iter()
+                    .flat_map(|interval| [interval.lo, interval.hi])
+                    .filter(|bound| bound.is_finite())
+                    .any(|bound| bound < domain.lo || bound > domain.hi)
+            };
+
+            if clamped == MultiInterval::from_interval(domain.unwrap_or_else(Interval::whole_line))
+            {
+                warnings.push(Warning::WholeDomain {
+                    predicate_id,
+                    var_name,
+                });
+            } else if let Some(domain) =
+                domain.filter(|domain| clamped.is_empty() || has_bound_outside(domain))
+            {
+                warnings.push(Warning::OutsideOfDomain {
+                    predicate_id,
+                    var_name,
+                    interval,
+                    domain,
+                });
+            }
+        }
+    }
+
+    warnings.uniques()
+}
+
+/// The tuples of the feature, and the warnings about the parts of the spec which are valid, but probably a mistake.
+pub fn ir_to_ntuple(feature: &Feature) -> (Vec<NTupleInput>, Vec<Warning>) {
+    let ntuples = ir_to_tagged_ntuples(feature)
+        .into_iter()
+        .map(|(_, ntuple)| ntuple)
+        .collect::<Vec<_>>()
+        .uniques();
+
+    (ntuples, ir_warnings(feature))
+}
+
+/// The warnings of every predicate, and the overlapping classes of the feature, see [`ir_to_ntuple`]
+fn ir_warnings(feature: &Feature) -> Vec<Warning> {
+    let mut warnings = feature
+        .predicates
+        .iter()
+        .enumerate()
+        .flat_map(|(predicate_id, predicate)| predicate_warnings(feature, predicate_id, predicate))
+        .collect::<Vec<_>>();
+    warnings.extend(
+        overlapping_classes(feature)
+            .into_iter()
+            .map(Warning::OverlappingClasses),
+    );
+
+    warnings
+}
+
+/// The tuples of the feature, each with the relations between its variables, like `start <= end`.
+///
+/// The tuples can't express the relations, so the values generated from them have to be checked against the relations,
+/// like in `generate` with `GenConfig::honor_relations`. The same tuple with different relations is kept for each of them.
+pub fn ir_to_ntuples_with_relations(
+    feature: &Feature,
+) -> Vec<(NTupleInput, Vec<RelationCondition>)> {
+    feature
+        .predicates
+        .iter()
+        .flat_map(|predicate| predicate.conjunction_of_conditions())
         .filter_map(|conditions| {
-            conditions
+            let relations = relations_of(&conditions);
+            convert_conjunction_to_ntuple(feature, conditions).map(|ntuple| (ntuple, relations))
+        })
+        .collect::<Vec<_>>()
+        .uniques()
+}
+
+/// Like `ir_to_ntuple`, but every tuple is tagged with the predicate it comes from,
+/// so a generated test case can be traced back to the line of the spec it exercises.
+///
+/// The tuples are only deduplicated per predicate, a tuple produced by multiple predicates is kept for each of them.
+pub fn ir_to_tagged_ntuples(feature: &Feature) -> Vec<(PredicateId, NTupleInput)> {
+    feature
+        .predicates
+        .iter()
+        .enumerate()
+        .flat_map(|(predicate_id, predicate)| {
+            convert_predicate_to_ntuple(feature, predicate)
+                .uniques()
                 .into_iter()
-                .fold(
-                    Some(HashMap::<String, Condition>::new()),
-                    |ntuple, cond| {
-                        ntuple.map(|mut ntuple| {
-                            let var_name = cond.get_variable();
-                            let to_insert = match (&cond, ntuple.get(var_name)) {
-                                (x, None) => Some(x.clone()),
-                                (
-                                     Condition::Bool(BoolCondition {
-                                        should_equal_to: old,
-                                        ..
-                                    }),
-                                    Some(Condition::Bool(BoolCondition {
-                                        should_equal_to: new,
-                                        ..
-                                    })),
-                                ) => {
-                                    if old == new {
-                                        Some(Condition::Bool(BoolCondition {
-                                            var_name: var_name.to_owned(),
-                                            should_equal_to: *old,
-                                        }))
-                                    } else {
-                                        None
-                                    }
-                                }
-                                (
-                                    Condition::Interval(IntervalCondition {
-                                        interval: old, ..
-                                    }),
-                                    Some(Condition::Interval(IntervalCondition {
-                                        interval: new,
-                                        ..
-                                    })),
-                                ) => old.intersect(new).map(|intersection| {
-                                    Condition::Interval(IntervalCondition {
-                                        interval: intersection,
-                                        var_name: var_name.to_owned(),
-                                    })
-                                }),
-                                (x, y) => panic!("Mismatched types in predicate! Variable {var_name} has both a boolean and an interval condition! {x:#?} and {y:#?}")
-                            };
-                            if let Some(to_insert) = to_insert {
-                                ntuple.insert(var_name.to_owned(), to_insert);
-                            }
-                            ntuple
-                        })
-                    },
-                )
-                .map(|x| x.into_iter().map(|(var_name, condition)| {
-                    let variable = variables
-                        .iter()
-                        .find(|variable| var_name.as_str() == variable.var_name)
-                        // TODO: This should be an actual error in a Result type
-                        .unwrap_or_else(|| {
-                            panic!("Undefined variable: {var_name}")
-                        });
-                    (
-                        var_name,
-                        convert_condition(variable, condition),
-                    )
-                }))
-                .map(|x| NTupleInput {inputs: x.collect() })
+                .map(move |ntuple| (predicate_id, ntuple))
         })
         .collect()
 }
 
-pub fn ir_to_ntuple(
-    Feature {
-        variables,
-        predicates,
-    }: &Feature,
-) -> Vec<NTupleInput> {
-    predicates
-        .clone()
+/// The pairs of predicate indices `(redundant, subsuming)`, where every tuple of the redundant predicate is
+/// subsumed by a tuple of the other predicate, so it only adds more test cases.
+///
+/// If two predicates result in the same tuples, only the later one is reported as redundant.
+/// Predicates without any tuples, like the ones with conflicting conditions, are not reported.
+pub fn find_redundant_predicates(feature: &Feature) -> Vec<(PredicateId, PredicateId)> {
+    let tuples_per_predicate = feature
+        .predicates
         .iter()
-        .flat_map(|predicate| convert_predicate_to_ntuple(variables, predicate))
-        .collect::<Vec<_>>()
-        .uniques()
+        .map(|predicate| convert_predicate_to_ntuple(feature, predicate))
+        .collect::<Vec<_>>();
+
+    let is_subsumed = |this: &[NTupleInput], that: &[NTupleInput]| {
+        this.iter()
+            .all(|ntuple| that.iter().any(|other| ntuple.is_subsumed_by(other)))
+    };
+
+    tuples_per_predicate
+        .iter()
+        .enumerate()
+        .filter(|(_, tuples)| !tuples.is_empty())
+        .flat_map(|(i, tuples)| {
+            tuples_per_predicate
+                .iter()
+                .enumerate()
+                .filter(move |(j, other_tuples)| {
+                    i != *j
+                        && is_subsumed(tuples, other_tuples)
+                        && (i > *j || !is_subsumed(other_tuples, tuples))
+                })
+                .map(move |(j, _)| (i, j))
+        })
+        .collect()
+}
+
+/// A predicate which can never be satisfied, because every one of its conjunctions has conflicting conditions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsatisfiablePredicate {
+    pub predicate_id: PredicateId,
+    /// The conflicting pair of conditions for each conjunction of the predicate, in disjunctive normal form.
+    /// The first condition is the intersection of the earlier conditions of the variable, the second is the one it conflicts with.
+    pub conflicts: Vec<(Condition, Condition)>,
+}
+
+/// The predicates which have no tuples, because their conditions conflict, like `x > 10 && x < 5`.
+///
+/// These are usually mistakes in the spec, they don't generate any test cases.
+pub fn find_unsatisfiable_predicates(feature: &Feature) -> Vec<UnsatisfiablePredicate> {
+    feature
+        .predicates
+        .iter()
+        .enumerate()
+        .filter_map(|(predicate_id, predicate)| {
+            let conflicts = predicate
+                .disjunctive_normal_form()
+                .into_iter()
+                .map(|conditions| {
+                    merge_conjunction(conditions)
+                        .err()
+                        .map(|conflict| *conflict)
+                })
+                .collect::<Option<Vec<_>>>()?;
+
+            Some(UnsatisfiablePredicate {
+                predicate_id,
+                conflicts,
+            })
+        })
+        .collect()
+}
+
+/// A variable with an interval condition or in a relation, but without a precision in its type, like `flag > 5` for `var flag: bool`.
+/// Converting the feature to tuples would panic on it.
+#[derive(Error, Clone, Debug, PartialEq)]
+#[error("The variable {var_name} of type {var_type:?} has no precision, but it has an interval condition in the predicates {predicate_ids:?}")]
+pub struct PrecisionError {
+    pub var_name: String,
+    pub var_type: Type,
+    /// The predicates with an interval condition for the variable, in ascending order
+    pub predicate_ids: Vec<PredicateId>,
+}
+
+/// The names of the variables in the visited interval conditions
+#[derive(Default)]
+struct IntervalVariables(HashSet<String>);
+
+impl Visitor for IntervalVariables {
+    fn visit_interval_condition(&mut self, condition: &IntervalCondition) {
+        self.0.insert(condition.var_name.clone());
+    }
+
+    fn visit_relation_condition(&mut self, condition: &RelationCondition) {
+        self.0.insert(condition.left.clone());
+        self.0.insert(condition.right.clone());
+    }
+}
+
+/// Checks that every variable with an interval condition has a precision, so the feature can be converted to tuples.
+///
+/// Returns every offending variable, in their declaration order.
+pub fn check_precisions(feature: &Feature) -> Result<(), Vec<PrecisionError>> {
+    let interval_variables_per_predicate = feature
+        .predicates
+        .iter()
+        .map(|predicate| {
+            let mut collector = IntervalVariables::default();
+            walk_predicate(&mut collector, predicate);
+            collector.0
+        })
+        .collect::<Vec<_>>();
+
+    let errors = feature
+        .variables
+        .iter()
+        .filter(|variable| variable.var_type.get_precision().is_none())
+        .filter_map(|variable| {
+            let predicate_ids = interval_variables_per_predicate
+                .iter()
+                .enumerate()
+                .filter(|(_, var_names)| var_names.contains(&variable.var_name))
+                .map(|(predicate_id, _)| predicate_id)
+                .collect::<Vec<_>>();
+
+            (!predicate_ids.is_empty()).then(|| PrecisionError {
+                var_name: variable.var_name.clone(),
+                var_type: variable.var_type.clone(),
+                predicate_ids,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors),
+    }
+}
+
+/// A bool or enum condition which doesn't match the type of its variable.
+/// Converting the feature to tuples would panic on it, or generate values the variable can't have.
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum ConditionTypeError {
+    #[error("The variable {var_name} of type {var_type:?} is not a bool, but it's compared to a bool in the predicate {predicate_id}")]
+    NotBool {
+        var_name: String,
+        var_type: Type,
+        predicate_id: PredicateId,
+    },
+    #[error("The variable {var_name} of type {var_type:?} is not an enum, but it has an enum condition in the predicate {predicate_id}")]
+    NotEnum {
+        var_name: String,
+        var_type: Type,
+        predicate_id: PredicateId,
+    },
+    #[error("The enum variable {var_name} has no value {value}, but it's used in the predicate {predicate_id}")]
+    UndeclaredValue {
+        var_name: String,
+        value: String,
+        predicate_id: PredicateId,
+    },
+}
+
+/// The visited bool and enum conditions, in their order
+#[derive(Default)]
+struct BoolAndEnumConditions(Vec<Condition>);
+
+impl Visitor for BoolAndEnumConditions {
+    fn visit_bool_condition(&mut self, condition: &BoolCondition) {
+        self.0.push(Condition::Bool(condition.clone()));
+    }
+
+    fn visit_enum_condition(&mut self, condition: &EnumCondition) {
+        self.0.push(Condition::Enum(condition.clone()));
+    }
+}
+
+/// Checks that the bool conditions are for bool variables, and the enum conditions are for enum variables with their declared values.
+///
+/// The conditions of the undefined variables are left out, see `Feature::undefined_variables`.
+/// Returns every offending condition, in the order of the predicates and their conditions.
+pub fn check_condition_types(feature: &Feature) -> Result<(), Vec<ConditionTypeError>> {
+    let mut errors = Vec::new();
+
+    for (predicate_id, predicate) in feature.predicates.iter().enumerate() {
+        let mut collector = BoolAndEnumConditions::default();
+        walk_predicate(&mut collector, predicate);
+
+        for condition in collector.0 {
+            let Some(variable) = feature.variable(condition.get_variable()) else {
+                continue;
+            };
+            let var_name = variable.var_name.clone();
+
+            match (condition, &variable.var_type) {
+                (Condition::Bool(_), Type::Bool) => {}
+                (Condition::Bool(_), var_type) => errors.push(ConditionTypeError::NotBool {
+                    var_name,
+                    var_type: var_type.clone(),
+                    predicate_id,
+                }),
+                (Condition::Enum(condition), Type::Enum(declared_values)) => errors.extend(
+                    condition
+                        .values
+                        .into_iter()
+                        .filter(|value| !declared_values.contains(value))
+                        .map(|value| ConditionTypeError::UndeclaredValue {
+                            var_name: var_name.clone(),
+                            value,
+                            predicate_id,
+                        }),
+                ),
+                (_, var_type) => errors.push(ConditionTypeError::NotEnum {
+                    var_name,
+                    var_type: var_type.clone(),
+                    predicate_id,
+                }),
+            }
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        check_condition_types, check_precisions, find_redundant_predicates,
+        find_unsatisfiable_predicates, ir_to_ntuple, ir_to_tagged_ntuples, ConditionTypeError,
+        PrecisionError, UnsatisfiablePredicate, Warning,
+    };
+    use crate::{
+        dto::{
+            tests::{bool_input, create_ntuple_input},
+            BoolDTO, BoolExpression, Input, IntervalDTO,
+        },
+        export::ntuples_to_json,
+        interval::{
+            test::{int, multiint},
+            MultiInterval,
+        },
+        ir::{coverage::ClassOverlap, BoolCondition, Condition, IntervalCondition},
+        parser::{ast::Type, parse_gpt_to_ir},
+    };
+
+    /// Compares `actual` with the checked in golden file in the `snapshots` directory.
+    /// Run the tests with the `UPDATE_SNAPSHOTS` environment variable set to overwrite the golden file instead.
+    fn assert_snapshot(file_name: &str, actual: &str) {
+        let path = format!(
+            "{}/src/ir/snapshots/{file_name}",
+            env!("CARGO_MANIFEST_DIR")
+        );
+
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(&path, actual).unwrap();
+            return;
+        }
+
+        let expected = std::fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("Golden file {path} can't be read: {err}"));
+        assert_eq!(
+            actual, expected,
+            "The result differs from the golden file {path}, set UPDATE_SNAPSHOTS to update it"
+        );
+    }
+
+    #[test]
+    fn test_ir_to_ntuple_snapshot() {
+        let (_, features) = parse_gpt_to_ir(include_str!("snapshots/ir_to_ntuple.gpt")).unwrap();
+        let feature = &features[0];
+
+        let (ntuples, _) = ir_to_ntuple(feature);
+        let json = serde_json::to_string_pretty(&ntuples_to_json(feature, &ntuples)).unwrap();
+
+        assert_snapshot("ir_to_ntuple.json", &format!("{json}\n"));
+    }
+
+    #[test]
+    fn test_find_redundant_predicates() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var x: int
+            var flag: bool
+
+            if(x in [0, 100])
+            if(x in [10, 20])
+            if(flag == true)
+            if(x in [0, 100])
+            if(x in [50, 200])
+            if(x < 0 || x in [30, 40])
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            find_redundant_predicates(&features[0]),
+            vec![(1, 0), (1, 3), (3, 0)]
+        );
+    }
+
+    #[test]
+    fn test_find_unsatisfiable_predicates() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var x: int
+            var flag: bool
+
+            if(x > 10 && x < 5)
+            if(x > 10 || x > 20)
+            if(flag == true && x < 0 && flag == false)
+            if((x > 10 && x < 5) || x in [0, 1])
+            if(x in [0, 10] && x in [5, 20] && x > 15)
+        "#,
+        )
+        .unwrap();
+        let interval_cond = |interval: &str| {
+            Condition::Interval(IntervalCondition {
+                var_name: "x".to_owned(),
+                interval: multiint(interval),
+            })
+        };
+        let bool_cond = |should_equal_to: bool| {
+            Condition::Bool(BoolCondition {
+                var_name: "flag".to_owned(),
+                should_equal_to,
+            })
+        };
+
+        assert_eq!(
+            find_unsatisfiable_predicates(&features[0]),
+            vec![
+                UnsatisfiablePredicate {
+                    predicate_id: 0,
+                    conflicts: vec![(interval_cond("(10, Inf)"), interval_cond("(-Inf, 5)"))],
+                },
+                UnsatisfiablePredicate {
+                    predicate_id: 2,
+                    conflicts: vec![(bool_cond(true), bool_cond(false))],
+                },
+                UnsatisfiablePredicate {
+                    predicate_id: 4,
+                    conflicts: vec![(interval_cond("[5, 10]"), interval_cond("(15, Inf)"))],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ir_to_ntuple_enum() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var status: enum {active, closed, on_hold}
+            var x: int
+
+            if(status in {closed, active} && x > 0)
+            if(status not in {active})
+            if(!(status in {active, closed}) && status not in {on_hold})
+            if(status in {active} && status in {closed})
+        "#,
+        )
+        .unwrap();
+
+        let tuples = ir_to_tagged_ntuples(&features[0])
+            .into_iter()
+            .map(|(predicate_id, ntuple)| (predicate_id, ntuple.to_string()))
+            .collect::<Vec<_>>();
+
+        // The values are in the declared order
+        assert_eq!(
+            tuples,
+            vec![
+                (0, "status ∈ {active, closed}, x ∈ (0, Inf)".to_owned()),
+                (1, "status ∈ {closed, on_hold}, x = *".to_owned()),
+                (2, "status ∈ {}, x = *".to_owned()),
+            ]
+        );
+        assert!(ir_to_ntuple(&features[0]).0[2].is_unsatisfiable());
+        assert_eq!(
+            find_unsatisfiable_predicates(&features[0])
+                .iter()
+                .map(|predicate| predicate.predicate_id)
+                .collect::<Vec<_>>(),
+            vec![3]
+        );
+    }
+
+    #[test]
+    fn test_ir_to_ntuple_conflicting_conditions() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var x: int
+
+            if(x > 10 && x < 5)
+            if(x > 10 || x > 20)
+        "#,
+        )
+        .unwrap();
+
+        // The conflicting conjunctions, like the second one of `x > 10 || x > 20`, don't have a tuple
+        let tuples = ir_to_tagged_ntuples(&features[0])
+            .into_iter()
+            .map(|(predicate_id, ntuple)| (predicate_id, ntuple.to_string()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            tuples,
+            vec![
+                (1, "x ∈ (10, Inf)".to_owned()),
+                (1, "x ∈ (20, Inf)".to_owned()),
+                (1, "x ∈ (10, 20]".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ir_to_tagged_ntuples() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var x: int
+
+            if(x < 0 || x > 100)
+            if(x in [0, 100])
+            if(x < 0)
+        "#,
+        )
+        .unwrap();
+        let x_input = |interval: &str| {
+            create_ntuple_input(vec![(
+                "x",
+                Input::Interval(IntervalDTO {
+                    interval: multiint(interval),
+                    precision: 1.0,
+                    is_constant: false,
+                    is_integer: true,
+                }),
+            )])
+        };
+
+        let result = ir_to_tagged_ntuples(&features[0]);
+
+        assert_eq!(
+            result,
+            vec![
+                (0, x_input("(-Inf, 0)")),
+                (0, x_input("(100, Inf)")),
+                (1, x_input("[0, 100]")),
+                (2, x_input("(-Inf, 0)")),
+            ]
+        );
+        assert_eq!(
+            ir_to_ntuple(&features[0]).0,
+            vec![
+                x_input("(-Inf, 0)"),
+                x_input("(100, Inf)"),
+                x_input("[0, 100]")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ir_to_ntuple_with_domain() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var age: int in [0, 150]
+
+            if(age < 0)
+            if(age not in [18, 65])
+        "#,
+        )
+        .unwrap();
+        let age_input = |interval: &str| {
+            Input::Interval(IntervalDTO {
+                interval: multiint(interval),
+                precision: 1.0,
+                is_constant: false,
+                is_integer: true,
+            })
+        };
+
+        let (result, _) = ir_to_ntuple(&features[0]);
+
+        assert_eq!(
+            result,
+            vec![
+                create_ntuple_input(vec![("age", age_input(""))]),
+                create_ntuple_input(vec![("age", age_input("[0, 18) (65, 150]"))]),
+            ]
+        );
+        assert!(result[0].is_unsatisfiable());
+        assert!(!result[1].is_unsatisfiable());
+    }
+
+    #[test]
+    fn test_ir_to_ntuple_or_keyword() {
+        let ntuples = |input: &str| {
+            let (_, features) = parse_gpt_to_ir(input).unwrap();
+            ir_to_ntuple(&features[0]).0
+        };
+
+        let with_keywords = ntuples(
+            r#"
+            var x: int
+            var flag: bool
+
+            if(x > 5 AND x < 10)
+            if(flag OR x == 0)
+        "#,
+        );
+        let with_symbols = ntuples(
+            r#"
+            var x: int
+            var flag: bool
+
+            if(x > 5 && x < 10)
+            if(flag == true || x == 0)
+        "#,
+        );
+
+        let x = |interval: &str| {
+            Input::Interval(IntervalDTO {
+                interval: multiint(interval),
+                precision: 1.0,
+                is_constant: false,
+                is_integer: true,
+            })
+        };
+
+        assert_eq!(with_keywords, with_symbols);
+        // The AND is a single tuple, the OR is expanded into multiple tuples, like `||`
+        assert_eq!(
+            with_keywords,
+            vec![
+                create_ntuple_input(vec![("x", x("(5, 10)")), ("flag", Input::Any)]),
+                create_ntuple_input(vec![("x", Input::Any), ("flag", bool_input(true))]),
+                create_ntuple_input(vec![("x", x("[0, 0]")), ("flag", bool_input(false))]),
+                create_ntuple_input(vec![("x", x("[0, 0]")), ("flag", Input::Any)]),
+                create_ntuple_input(vec![
+                    ("x", x("(-Inf, 0) (0, Inf)")),
+                    ("flag", bool_input(true)),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ir_to_ntuple_or_of_ands() {
+        let ntuples = |input: &str| {
+            let (_, features) = parse_gpt_to_ir(input).unwrap();
+            ir_to_ntuple(&features[0]).0
+        };
+
+        let and_binds_tighter = ntuples(
+            r#"
+            var x: int
+            var y: int
+            var f: bool
+
+            if(x > 5 OR y < 0 AND f)
+        "#,
+        );
+        let grouped_ands = ntuples(
+            r#"
+            var a: bool
+            var b: bool
+            var c: bool
+            var d: bool
+            var e: bool
+
+            if(a OR (b AND c) OR (d AND e))
+        "#,
+        );
+
+        let int = |interval: &str| {
+            Input::Interval(IntervalDTO {
+                interval: multiint(interval),
+                precision: 1.0,
+                is_constant: false,
+                is_integer: true,
+            })
+        };
+
+        // The AND binds tighter than the OR, so `y < 0 AND f` is one disjunct
+        assert_eq!(
+            and_binds_tighter,
+            vec![
+                create_ntuple_input(vec![
+                    ("x", int("(5, Inf)")),
+                    ("y", Input::Any),
+                    ("f", Input::Any),
+                ]),
+                create_ntuple_input(vec![
+                    ("x", Input::Any),
+                    ("y", int("(-Inf, 0)")),
+                    ("f", bool_input(true)),
+                ]),
+            ]
+        );
+        assert_eq!(
+            grouped_ands,
+            vec![
+                create_ntuple_input(vec![
+                    ("a", bool_input(true)),
+                    ("b", Input::Any),
+                    ("c", Input::Any),
+                    ("d", Input::Any),
+                    ("e", Input::Any),
+                ]),
+                create_ntuple_input(vec![
+                    ("a", Input::Any),
+                    ("b", bool_input(true)),
+                    ("c", bool_input(true)),
+                    ("d", Input::Any),
+                    ("e", Input::Any),
+                ]),
+                create_ntuple_input(vec![
+                    ("a", Input::Any),
+                    ("b", Input::Any),
+                    ("c", Input::Any),
+                    ("d", bool_input(true)),
+                    ("e", bool_input(true)),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ir_to_ntuple_defaults() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var x: int = 7
+            var y: int
+            var flag: bool = true
+
+            if(x < 5)
+            if(y > 0)
+        "#,
+        )
+        .unwrap();
+
+        let default_flag = Input::Bool(BoolDTO {
+            expression: BoolExpression::IsTrue,
+            bool_val: true,
+            is_constant: true,
+        });
+
+        assert_eq!(
+            ir_to_ntuple(&features[0]).0,
+            vec![
+                create_ntuple_input(vec![
+                    (
+                        "x",
+                        Input::Interval(IntervalDTO {
+                            interval: multiint("(-Inf, 5)"),
+                            precision: 1.0,
+                            is_constant: false,
+                            is_integer: true,
+                        })
+                    ),
+                    ("y", Input::Any),
+                    ("flag", default_flag.clone()),
+                ]),
+                create_ntuple_input(vec![
+                    (
+                        "x",
+                        Input::Interval(IntervalDTO {
+                            interval: MultiInterval::new_closed_point(7.0),
+                            precision: 1.0,
+                            is_constant: true,
+                            is_integer: true,
+                        })
+                    ),
+                    (
+                        "y",
+                        Input::Interval(IntervalDTO {
+                            interval: multiint("(0, Inf)"),
+                            precision: 1.0,
+                            is_constant: false,
+                            is_integer: true,
+                        })
+                    ),
+                    ("flag", default_flag),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ir_to_ntuple_warnings() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var x: int in [0, 100]
+            var flag: bool = true
+
+            if(x in [-10, 20] && flag == false)
+            if(x >= 0)
+            if(x > 50 && flag == true)
+            if(x > 100)
+        "#,
+        )
+        .unwrap();
+
+        let (ntuples, warnings) = ir_to_ntuple(&features[0]);
+
+        assert_eq!(ntuples.len(), 4);
+        assert_eq!(
+            warnings,
+            vec![
+                Warning::OutsideOfDomain {
+                    predicate_id: 0,
+                    var_name: "x".to_owned(),
+                    interval: multiint("[-10, 20]"),
+                    domain: int("[0, 100]"),
+                },
+                Warning::DefaultedVariable {
+                    predicate_id: 1,
+                    var_name: "flag".to_owned(),
+                },
+                Warning::WholeDomain {
+                    predicate_id: 1,
+                    var_name: "x".to_owned(),
+                },
+                Warning::DefaultedVariable {
+                    predicate_id: 3,
+                    var_name: "flag".to_owned(),
+                },
+                Warning::OutsideOfDomain {
+                    predicate_id: 3,
+                    var_name: "x".to_owned(),
+                    interval: multiint("(100, Inf)"),
+                    domain: int("[0, 100]"),
+                },
+                Warning::OverlappingClasses(ClassOverlap {
+                    first: 0,
+                    second: 1,
+                    var_name: "x".to_owned(),
+                    region: multiint("[0, 20]"),
+                }),
+                Warning::OverlappingClasses(ClassOverlap {
+                    first: 1,
+                    second: 2,
+                    var_name: "x".to_owned(),
+                    region: multiint("(50, 100]"),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ir_to_ntuple_no_warnings() {
+        let (_, features) = parse_gpt_to_ir(
+            "var x: int in [0, 100]\nvar flag: bool\nif(x < 50)\nif(x >= 50 && flag == true)",
+        )
+        .unwrap();
+
+        assert_eq!(ir_to_ntuple(&features[0]).1, vec![]);
+    }
+
+    #[test]
+    fn test_check_precisions() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var x: int
+            var flag: bool
+            var status: enum {active, closed}
+            var unused: bool
+
+            if(flag > 5 && x < 10)
+            if(x in [0, 10] && status in {active})
+            if(status < 1 || flag in [0, 1])
+            if(unused == true)
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            check_precisions(&features[0]),
+            Err(vec![
+                PrecisionError {
+                    var_name: "flag".to_owned(),
+                    var_type: Type::Bool,
+                    predicate_ids: vec![0, 2],
+                },
+                PrecisionError {
+                    var_name: "status".to_owned(),
+                    var_type: Type::Enum(vec!["active".to_owned(), "closed".to_owned()]),
+                    predicate_ids: vec![2],
+                },
+            ])
+        );
+
+        let (_, features) = parse_gpt_to_ir(
+            "var x: int
+var flag: bool
+if(x < 10 && flag == true)",
+        )
+        .unwrap();
+        assert_eq!(check_precisions(&features[0]), Ok(()));
+    }
+
+    #[test]
+    fn test_check_condition_types() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var x: int
+            var flag: bool
+            var status: enum {active, closed}
+
+            if(x in {a, b} && flag == true)
+            if(status == true)
+            if(status in {active, deleted} || status not in {archived})
+            if(flag in {active})
+        "#,
+        )
+        .unwrap();
+
+        let enum_type = Type::Enum(vec!["active".to_owned(), "closed".to_owned()]);
+        assert_eq!(
+            check_condition_types(&features[0]),
+            Err(vec![
+                ConditionTypeError::NotEnum {
+                    var_name: "x".to_owned(),
+                    var_type: Type::Integer,
+                    predicate_id: 0,
+                },
+                ConditionTypeError::NotBool {
+                    var_name: "status".to_owned(),
+                    var_type: enum_type,
+                    predicate_id: 1,
+                },
+                ConditionTypeError::UndeclaredValue {
+                    var_name: "status".to_owned(),
+                    value: "deleted".to_owned(),
+                    predicate_id: 2,
+                },
+                ConditionTypeError::UndeclaredValue {
+                    var_name: "status".to_owned(),
+                    value: "archived".to_owned(),
+                    predicate_id: 2,
+                },
+                ConditionTypeError::NotEnum {
+                    var_name: "flag".to_owned(),
+                    var_type: Type::Bool,
+                    predicate_id: 3,
+                },
+            ])
+        );
+
+        let (_, features) = parse_gpt_to_ir(
+            "var flag: bool\nvar status: enum {active, closed}\nif(flag == false && status not in {closed})",
+        )
+        .unwrap();
+        assert_eq!(check_condition_types(&features[0]), Ok(()));
+    }
 }
@@ -1,20 +1,33 @@
-use std::fmt;
+use std::{collections::HashSet, fmt};
 
 use itertools::Itertools;
 
 use crate::{
-    interval::MultiInterval,
-    parser::ast::{BoolOp, Type},
+    dto::IntervalDTO,
+    interval::{Boundary, Intersectable, Interval, MultiInterval},
+    parser::ast::{BinaryOp, BoolOp, Literal, Span, Type},
     util::{ContinousSublistsFromFirst, UniquesVec},
 };
 
 pub mod ast_to_ir;
+pub mod canonicalize;
+pub mod coverage;
+pub mod diff;
 pub mod ir_to_ntuple;
+pub mod partition;
+pub mod visit;
+
+use ir_to_ntuple::PredicateId;
+use visit::{walk_predicate, Visitor};
 
 #[derive(PartialEq, Clone, Debug)]
 pub struct Variable {
     pub var_name: String,
     pub var_type: Type,
+    /// The values the variable can have, every condition of the variable is restricted to it
+    pub domain: Option<Interval>,
+    /// The value used when a predicate doesn't constrain the variable
+    pub default: Option<Literal>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -23,23 +36,101 @@ pub struct BoolCondition {
     pub should_equal_to: bool,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct IntervalCondition {
     pub var_name: String,
     pub interval: MultiInterval,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+/// The variable has to be one of the `values`, or none of them if `should_be_in` is false
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnumCondition {
+    pub var_name: String,
+    pub values: Vec<String>,
+    pub should_be_in: bool,
+}
+
+/// A comparison of two numeric variables, like `start <= end`.
+///
+/// The tuples can't express it, because their variables are independent,
+/// so it's only checked on the generated test cases: by `generate` with `GenConfig::honor_relations`,
+/// by `generate_tests_for_gpt_input` and by `pairwise`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelationCondition {
+    pub left: String,
+    pub binary_op: BinaryOp,
+    pub right: String,
+}
+
+impl RelationCondition {
+    /// Whether there are values in `left` and `right` satisfying the relation.
+    ///
+    /// Only the values on the precision grid of the variables are considered, see `Interval::quantize`.
+    /// Example: `start <= end` can be satisfied by `start in [5, 10]` and `end in [0, 5]`, with `start = end = 5`,
+    /// but not by `start in (5, 10]` and `end in [0, 5.5]` for integers, because the smallest `start` is `6`.
+    pub fn is_satisfiable(&self, left: &IntervalDTO, right: &IntervalDTO) -> bool {
+        let on_grid = |dto: &IntervalDTO| {
+            MultiInterval::from_intervals(
+                dto.interval
+                    .intervals
+                    .iter()
+                    .map(|interval| interval.quantize(dto.precision))
+                    .collect(),
+            )
+        };
+        let (left, right) = (&on_grid(left), &on_grid(right));
+
+        let (Some(left_hull), Some(right_hull)) =
+            (left.bounding_interval(), right.bounding_interval())
+        else {
+            return false;
+        };
+
+        // `a < b` is possible if the lowest `a` is below the highest `b`,
+        // `a <= b` also if they are the same value, and it's in both of them
+        let can_be_less = |lo: &Interval, hi: &Interval, or_equal: bool| {
+            lo.lo < hi.hi
+                || (or_equal
+                    && lo.lo == hi.hi
+                    && lo.lo_boundary == Boundary::Closed
+                    && hi.hi_boundary == Boundary::Closed)
+        };
+
+        match self.binary_op {
+            BinaryOp::LessThan => can_be_less(&left_hull, &right_hull, false),
+            BinaryOp::LessThanEqualTo => can_be_less(&left_hull, &right_hull, true),
+            BinaryOp::GreaterThan => can_be_less(&right_hull, &left_hull, false),
+            BinaryOp::GreaterThanEqualTo => can_be_less(&right_hull, &left_hull, true),
+            BinaryOp::Equal => left.intersects_with(right),
+            BinaryOp::NotEqual => !(left.is_single_point() && left == right),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Condition {
     Bool(BoolCondition),
     Interval(IntervalCondition),
+    Enum(EnumCondition),
+    Relation(RelationCondition),
 }
 
 impl Condition {
+    /// The variable of the condition, the left one for a relation
     pub fn get_variable(&self) -> &str {
         match self {
             Self::Bool(cond) => cond.var_name.as_str(),
             Self::Interval(cond) => cond.var_name.as_str(),
+            Self::Enum(cond) => cond.var_name.as_str(),
+            Self::Relation(cond) => cond.left.as_str(),
+        }
+    }
+
+    /// Whether the condition is about the variable, a relation is about both of its variables
+    pub fn mentions(&self, var_name: &str) -> bool {
+        match self {
+            Self::Relation(cond) => cond.left == var_name || cond.right == var_name,
+            _ => self.get_variable() == var_name,
         }
     }
 
@@ -53,6 +144,14 @@ impl Condition {
                 var_name: cond.var_name.clone(),
                 interval: cond.interval.complement(),
             }),
+            Self::Enum(cond) => Self::Enum(EnumCondition {
+                should_be_in: !cond.should_be_in,
+                ..cond.clone()
+            }),
+            Self::Relation(cond) => Self::Relation(RelationCondition {
+                binary_op: cond.binary_op.negated(),
+                ..cond.clone()
+            }),
         }
     }
 }
@@ -67,7 +166,68 @@ impl fmt::Display for Condition {
             Self::Interval(IntervalCondition { var_name, interval }) => {
                 write!(f, "{var_name} in {interval}")
             }
+            Self::Enum(cond) => write!(f, "{cond}"),
+            Self::Relation(cond) => write!(f, "{cond}"),
+        }
+    }
+}
+
+/// Like `start <= end`
+impl fmt::Display for RelationCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.left, self.binary_op, self.right)
+    }
+}
+
+impl EnumCondition {
+    /// The condition satisfied by the values satisfying both conditions.
+    /// Returns `None` if there are no such values, like for `x in {a}` and `x not in {a}`.
+    ///
+    /// Example: `x in {a, b, c}` and `x not in {b}` is `x in {a, c}`
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let (values, should_be_in) = match (self.should_be_in, other.should_be_in) {
+            (true, true) => (filter_values(&self.values, &other.values, true), true),
+            (true, false) => (filter_values(&self.values, &other.values, false), true),
+            (false, true) => (filter_values(&other.values, &self.values, false), true),
+            (false, false) => {
+                let mut values = self.values.clone();
+                values.extend(filter_values(&other.values, &self.values, false));
+                (values, false)
+            }
+        };
+
+        if should_be_in && values.is_empty() {
+            return None;
         }
+
+        Some(Self {
+            var_name: self.var_name.clone(),
+            values,
+            should_be_in,
+        })
+    }
+}
+
+/// The `values` which are in `others`, or the ones which are not in them if `keep_common` is false
+fn filter_values(values: &[String], others: &[String], keep_common: bool) -> Vec<String> {
+    values
+        .iter()
+        .filter(|value| others.contains(value) == keep_common)
+        .cloned()
+        .collect()
+}
+
+/// Like `status in {active, closed}` or `status not in {active, closed}`
+impl fmt::Display for EnumCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = if self.should_be_in { "in" } else { "not in" };
+
+        write!(
+            f,
+            "{} {operator} {{{}}}",
+            self.var_name,
+            self.values.join(", ")
+        )
     }
 }
 
@@ -171,6 +331,61 @@ impl Predicate {
     pub fn conjunction_of_conditions(&self) -> Vec<Vec<Condition>> {
         self.reduce().to_ands()
     }
+
+    /// The predicate as a disjunction of conjunctions, by distributing the ands over the ors.
+    ///
+    /// Unlike `conjunction_of_conditions`, the conjunctions are not made mutually exclusive.
+    pub fn disjunctive_normal_form(&self) -> Vec<Vec<Condition>> {
+        match self {
+            Self::Negated(pred) => pred.as_ref().negated().disjunctive_normal_form(),
+            Self::Expression(cond) => vec![vec![cond.clone()]],
+            Self::Group {
+                left,
+                right,
+                operator: BoolOp::And,
+            } => left
+                .disjunctive_normal_form()
+                .into_iter()
+                .cartesian_product(right.disjunctive_normal_form())
+                .map(|(mut left, mut right)| {
+                    left.append(&mut right);
+                    left
+                })
+                .collect(),
+            Self::Group {
+                left,
+                right,
+                operator: BoolOp::Or,
+            } => {
+                let mut disjuncts = left.disjunctive_normal_form();
+                disjuncts.append(&mut right.disjunctive_normal_form());
+                disjuncts
+            }
+        }
+    }
+
+    /// Creates a predicate, which is the conjunction of all the conditions.
+    fn from_conjunction(conditions: Vec<Condition>) -> Option<Self> {
+        conditions
+            .into_iter()
+            .map(Self::Expression)
+            .reduce(|left, right| Self::Group {
+                left: Box::new(left),
+                right: Box::new(right),
+                operator: BoolOp::And,
+            })
+    }
+}
+
+/// The negation of the predicate in disjunctive normal form, each returned predicate is a conjunction of conditions.
+///
+/// Example: `!(x in [0, 10] && flag == true)` becomes `x in (-Inf, 0) (10, Inf)` and `flag == false`
+pub fn negate_predicate(pred: &Predicate) -> Vec<Predicate> {
+    pred.negated()
+        .disjunctive_normal_form()
+        .into_iter()
+        .filter_map(Predicate::from_conjunction)
+        .collect()
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -200,36 +415,22 @@ impl ReducedPredicate {
                 conjugated_conditions,
                 sub_ors,
             }) => {
-                if sub_ors.is_empty() {
-                    vec![conjugated_conditions.clone()]
-                } else {
-                    let mut result = Vec::new();
-
-                    let ands_from_ors: Vec<Vec<Vec<Condition>>> = sub_ors
-                        .iter()
-                        .map(|ors| Self::Or(ors.clone()).to_ands())
-                        .collect();
-
-                    for (a, b) in ands_from_ors.iter().tuple_combinations() {
-                        let mut asd = a
+                // Every sub or is distributed over the conjunctions, so `a && (b || c)` becomes `a && b` and `a && c`,
+                // a single sub or too, like in `(x > 5 || x < 0) && flag`
+                sub_ors
+                    .iter()
+                    .map(|ors| Self::Or(ors.clone()).to_ands())
+                    .fold(vec![conjugated_conditions.clone()], |conjunctions, ands| {
+                        conjunctions
                             .iter()
-                            .cartesian_product(b.iter())
-                            .map(|(x, y)| {
-                                let mut z = x.clone();
-                                z.append(&mut y.clone());
-                                z
+                            .cartesian_product(ands.iter())
+                            .map(|(conjunction, and)| {
+                                let mut conjunction = conjunction.clone();
+                                conjunction.extend(and.iter().cloned());
+                                conjunction
                             })
-                            .collect::<Vec<Vec<Condition>>>();
-
-                        for x in &mut asd {
-                            let mut base = conjugated_conditions.clone();
-                            base.append(x);
-                            result.push(base);
-                        }
-                    }
-
-                    result
-                }
+                            .collect()
+                    })
             }
             Self::Or(Ors {
                 disjuncted_conditions,
@@ -265,40 +466,14 @@ impl ReducedPredicate {
                     .collect::<Vec<Vec<Condition>>>()
                     .uniques(); // TODO: Itertools::unique would be betetr, but that requires Eq and Hash
 
-                if sub_ands.is_empty() {
-                    all_possible_anded_form_of_the_or
-                } else {
-                    let ands_from_ands: Vec<Vec<Vec<Condition>>> = sub_ands
-                        .iter()
-                        .map(|and| Self::And(and.clone()).to_ands())
-                        .collect();
-
-                    all_possible_anded_form_of_the_or
-                        .iter()
-                        .flat_map(|conjugated_conditions| {
-                            let mut result = Vec::new();
-                            for (a, b) in ands_from_ands.iter().tuple_combinations() {
-                                let mut asd = a
-                                    .iter()
-                                    .cartesian_product(b.iter())
-                                    .map(|(x, y)| {
-                                        let mut z = x.clone();
-                                        z.append(&mut y.clone());
-                                        z
-                                    })
-                                    .collect::<Vec<Vec<Condition>>>();
-
-                                for x in &mut asd {
-                                    let mut base = conjugated_conditions.clone();
-                                    base.append(x);
-                                    result.push(base);
-                                }
-                            }
-
-                            result
-                        })
-                        .collect()
-                }
+                // Every sub-AND is one more disjunct of the OR, like `y < 0 AND f` in `x > 5 OR y < 0 AND f`
+                sub_ands
+                    .iter()
+                    .map(|and| Self::And(and.clone()).to_ands())
+                    .fold(all_possible_anded_form_of_the_or, |mut disjuncts, ands| {
+                        disjuncts.extend(ands);
+                        disjuncts
+                    })
             }
         }
     }
@@ -339,6 +514,168 @@ impl ReducedPredicate {
 pub struct Feature {
     pub variables: Vec<Variable>,
     pub predicates: Vec<Predicate>,
+    /// Where the parts of the feature were parsed from, `None` if it wasn't parsed, or was changed since
+    pub spans: Option<FeatureSpans>,
+}
+
+/// The spans of the parts of a feature in the input, indexed like the feature.
+///
+/// The spans are kept next to the IR, instead of in the nodes, so the same condition in two places is still equal.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct FeatureSpans {
+    /// The declarations of the variables
+    pub variables: Vec<Span>,
+    /// The `if`, `else if` or `else` each predicate comes from, the innermost one for the nested ones
+    pub predicates: Vec<Span>,
+    /// The conditions of each predicate, in the order they are visited, see [`visit::walk_predicate`]
+    pub conditions: Vec<Vec<Span>>,
+}
+
+impl FeatureSpans {
+    /// Moves every span with the function of the offsets, see [`Span::map`]
+    pub fn map(self, f: impl Fn(usize) -> usize) -> Self {
+        let map_all = |spans: Vec<Span>| spans.into_iter().map(|span| span.map(&f)).collect();
+
+        Self {
+            variables: map_all(self.variables),
+            predicates: map_all(self.predicates),
+            conditions: self.conditions.into_iter().map(map_all).collect(),
+        }
+    }
+}
+
+/// A condition for a variable which is not declared in the feature
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UndefinedVariable {
+    pub var_name: String,
+    pub predicate_id: PredicateId,
+    /// The span of the condition, if the feature has spans
+    pub span: Option<Span>,
+}
+
+impl UndefinedVariable {
+    /// The error message, quoting the condition from the `input` the feature was parsed from, if it has a span
+    pub fn describe(&self, input: &str) -> String {
+        match self.span {
+            Some(span) => format!(
+                "Undefined variable `{}` at {}",
+                self.var_name,
+                span.quote(input)
+            ),
+            None => format!(
+                "Undefined variable `{}` in predicate {}",
+                self.var_name, self.predicate_id
+            ),
+        }
+    }
+}
+
+impl Feature {
+    /// The declared variable with the name, if there is one.
+    pub fn variable(&self, var_name: &str) -> Option<&Variable> {
+        self.variables
+            .iter()
+            .find(|variable| variable.var_name == var_name)
+    }
+
+    /// The variables the predicate has conditions for, in the order they were declared.
+    ///
+    /// Returns `None` if there is no predicate at the index, or the predicate has a condition for an undefined variable,
+    /// see [`Feature::undefined_variables`].
+    pub fn variables_in_predicate(&self, idx: PredicateId) -> Option<Vec<&Variable>> {
+        let mut collector = VariableNames::default();
+        walk_predicate(&mut collector, self.predicates.get(idx)?);
+
+        if collector
+            .0
+            .iter()
+            .any(|var_name| self.variable(var_name).is_none())
+        {
+            return None;
+        }
+
+        Some(
+            self.variables
+                .iter()
+                .filter(|variable| collector.0.contains(&variable.var_name))
+                .collect(),
+        )
+    }
+
+    /// The conditions mentioning a variable which is not declared, in the order of the predicates and their conditions.
+    pub fn undefined_variables(&self) -> Vec<UndefinedVariable> {
+        self.predicates
+            .iter()
+            .enumerate()
+            .flat_map(|(predicate_id, predicate)| {
+                let mut collector = Conditions::default();
+                walk_predicate(&mut collector, predicate);
+
+                let spans = self
+                    .spans
+                    .as_ref()
+                    .and_then(|spans| spans.conditions.get(predicate_id));
+
+                collector
+                    .0
+                    .into_iter()
+                    .enumerate()
+                    .flat_map(move |(condition_id, condition)| {
+                        let span = spans.and_then(|spans| spans.get(condition_id)).copied();
+                        let var_names = match condition {
+                            Condition::Relation(cond) => vec![cond.left, cond.right],
+                            _ => vec![condition.get_variable().to_owned()],
+                        };
+
+                        var_names
+                            .into_iter()
+                            .map(move |var_name| UndefinedVariable {
+                                var_name,
+                                predicate_id,
+                                span,
+                            })
+                    })
+            })
+            .filter(|undefined| self.variable(&undefined.var_name).is_none())
+            .collect()
+    }
+
+    /// The indices of the predicates which have a condition for the variable, in ascending order.
+    pub fn predicates_for_variable(&self, var_name: &str) -> Vec<PredicateId> {
+        self.predicates
+            .iter()
+            .enumerate()
+            .filter(|(_, predicate)| {
+                let mut collector = VariableNames::default();
+                walk_predicate(&mut collector, predicate);
+                collector.0.contains(var_name)
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+}
+
+/// The names of the variables in the visited conditions
+#[derive(Default)]
+struct VariableNames(HashSet<String>);
+
+impl Visitor for VariableNames {
+    fn visit_condition(&mut self, condition: &Condition) {
+        self.0.insert(condition.get_variable().to_owned());
+        if let Condition::Relation(cond) = condition {
+            self.0.insert(cond.right.clone());
+        }
+    }
+}
+
+/// The visited conditions, in their order
+#[derive(Default)]
+struct Conditions(Vec<Condition>);
+
+impl Visitor for Conditions {
+    fn visit_condition(&mut self, condition: &Condition) {
+        self.0.push(condition.clone());
+    }
 }
 
 impl fmt::Display for Predicate {
@@ -348,6 +685,8 @@ impl fmt::Display for Predicate {
             Self::Expression(cond) => match cond {
                 Condition::Bool(cond) => write!(f, "{} == {}", cond.var_name, cond.should_equal_to),
                 Condition::Interval(cond) => write!(f, "{} in {}", cond.var_name, cond.interval),
+                Condition::Enum(cond) => write!(f, "{cond}"),
+                Condition::Relation(cond) => write!(f, "{cond}"),
             },
             Self::Group {
                 left,
@@ -377,9 +716,24 @@ impl fmt::Debug for Predicate {
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
+    use rstest::rstest;
 
-    use super::{Condition, IntervalCondition, Predicate};
-    use crate::{interval::test::multiint, parser::ast::BoolOp};
+    use super::{
+        negate_predicate, BoolCondition, Condition, EnumCondition, IntervalCondition, Predicate,
+        RelationCondition,
+    };
+    use crate::{
+        dto::{
+            tests::{bool_input, create_ntuple_input},
+            Input, IntervalDTO,
+        },
+        interval::test::multiint,
+        ir::ir_to_ntuple::ir_to_ntuple,
+        parser::{
+            ast::{BinaryOp, BoolOp, Span},
+            parse_gpt_to_ir,
+        },
+    };
 
     fn cond<'a>(var_name: &'a str, interval: &'a str) -> Condition {
         Condition::Interval(IntervalCondition {
@@ -400,6 +754,241 @@ mod test {
         })
     }
 
+    fn or(left: Box<Predicate>, right: Box<Predicate>) -> Box<Predicate> {
+        Box::new(Predicate::Group {
+            left,
+            right,
+            operator: BoolOp::Or,
+        })
+    }
+
+    fn bool_cond(var_name: &str, should_equal_to: bool) -> Condition {
+        Condition::Bool(BoolCondition {
+            var_name: var_name.to_owned(),
+            should_equal_to,
+        })
+    }
+
+    fn bool_expr(var_name: &str, should_equal_to: bool) -> Box<Predicate> {
+        Box::new(Predicate::Expression(bool_cond(var_name, should_equal_to)))
+    }
+
+    fn enum_cond(values: &[&str], should_be_in: bool) -> EnumCondition {
+        EnumCondition {
+            var_name: "status".to_owned(),
+            values: values.iter().map(|&value| value.to_owned()).collect(),
+            should_be_in,
+        }
+    }
+
+    #[test]
+    fn test_enum_condition_intersect() {
+        let in_abc = enum_cond(&["a", "b", "c"], true);
+        let in_cb = enum_cond(&["c", "b"], true);
+        let not_in_b = enum_cond(&["b"], false);
+        let not_in_d = enum_cond(&["d"], false);
+
+        assert_eq!(in_abc.intersect(&in_cb), Some(enum_cond(&["b", "c"], true)));
+        assert_eq!(
+            in_abc.intersect(&not_in_b),
+            Some(enum_cond(&["a", "c"], true))
+        );
+        assert_eq!(
+            not_in_b.intersect(&in_abc),
+            Some(enum_cond(&["a", "c"], true))
+        );
+        assert_eq!(
+            not_in_b.intersect(&not_in_d),
+            Some(enum_cond(&["b", "d"], false))
+        );
+        assert_eq!(not_in_b.intersect(&not_in_b), Some(not_in_b.clone()));
+        assert_eq!(enum_cond(&["b"], true).intersect(&not_in_b), None);
+        assert_eq!(enum_cond(&["a"], true).intersect(&in_cb), None);
+        assert_eq!(not_in_b.to_string(), "status not in {b}");
+        assert_eq!(in_abc.to_string(), "status in {a, b, c}");
+    }
+
+    fn relation(binary_op: BinaryOp) -> RelationCondition {
+        RelationCondition {
+            left: "start".to_owned(),
+            binary_op,
+            right: "end".to_owned(),
+        }
+    }
+
+    #[rstest]
+    #[case(BinaryOp::LessThan, "[5, 10]", "[0, 5]", 0.1, false)]
+    #[case(BinaryOp::LessThan, "[5, 10]", "[0, 5.5]", 0.1, true)]
+    #[case(BinaryOp::LessThanEqualTo, "[5, 10]", "[0, 5]", 0.1, true)]
+    #[case(BinaryOp::LessThanEqualTo, "(5, 10]", "[0, 5]", 0.1, false)]
+    #[case(BinaryOp::LessThanEqualTo, "[5, 10]", "[0, 5)", 0.1, false)]
+    #[case(BinaryOp::GreaterThan, "[0, 5]", "[5, 10]", 0.1, false)]
+    #[case(BinaryOp::GreaterThanEqualTo, "[0, 5]", "[5, 10]", 0.1, true)]
+    #[case(BinaryOp::GreaterThanEqualTo, "(-Inf, 0)", "[0, Inf)", 0.1, false)]
+    #[case(BinaryOp::Equal, "[0, 5] [8, 9]", "(5, 8)", 0.1, false)]
+    #[case(BinaryOp::Equal, "[0, 5] [8, 9]", "(5, 8]", 0.1, true)]
+    #[case(BinaryOp::NotEqual, "[5, 5]", "[5, 5]", 0.1, false)]
+    #[case(BinaryOp::NotEqual, "[5, 5]", "[5, 6]", 0.1, true)]
+    #[case(BinaryOp::LessThan, "", "[0, 5]", 0.1, false)]
+    #[case(BinaryOp::LessThanEqualTo, "(5, 10]", "[0, 5.5]", 0.1, true)]
+    #[case(BinaryOp::LessThanEqualTo, "(5, 10]", "[0, 5.5]", 1.0, false)]
+    #[case(BinaryOp::Equal, "(5, 6)", "[5, 6]", 1.0, false)]
+    #[case(BinaryOp::NotEqual, "[5, 5.5]", "[4.5, 5]", 1.0, false)]
+    fn test_relation_condition_is_satisfiable(
+        #[case] binary_op: BinaryOp,
+        #[case] start: &str,
+        #[case] end: &str,
+        #[case] precision: f32,
+        #[case] expected: bool,
+    ) {
+        let relation = relation(binary_op);
+        let dto = |interval: &str| IntervalDTO {
+            interval: multiint(interval),
+            precision,
+            is_constant: false,
+            is_integer: precision == 1.0,
+        };
+
+        assert_eq!(
+            relation.is_satisfiable(&dto(start), &dto(end)),
+            expected,
+            "{relation} with start ∈ {start} and end ∈ {end} and the precision of {precision}"
+        );
+    }
+
+    #[test]
+    fn test_relation_condition() {
+        let (_, features) = parse_gpt_to_ir(
+            "var start: int\nvar end: int\nif(start <= end)\nif(!(start <= end || start == 10))",
+        )
+        .unwrap();
+        let predicates = &features[0].predicates;
+        let condition = Condition::Relation(relation(BinaryOp::LessThanEqualTo));
+
+        assert_eq!(predicates[0], Predicate::Expression(condition.clone()));
+        assert_eq!(predicates[0].to_string(), "start <= end");
+        assert!(condition.mentions("start") && condition.mentions("end"));
+        assert!(!condition.mentions("x"));
+        assert_eq!(
+            predicates[1].disjunctive_normal_form(),
+            vec![vec![
+                Condition::Relation(relation(BinaryOp::GreaterThan)),
+                cond("start", "(-Inf, 10) (10, Inf)"),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_disjunctive_normal_form() {
+        let predicate = and(
+            or(expr("x", "[0,0]"), expr("y", "[0,0]")),
+            or(expr("x", "[1,1]"), bool_expr("flag", true)),
+        );
+
+        assert_eq!(
+            predicate.disjunctive_normal_form(),
+            vec![
+                vec![cond("x", "[0,0]"), cond("x", "[1,1]")],
+                vec![cond("x", "[0,0]"), bool_cond("flag", true)],
+                vec![cond("y", "[0,0]"), cond("x", "[1,1]")],
+                vec![cond("y", "[0,0]"), bool_cond("flag", true)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disjunctive_normal_form_of_parsed_groups() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var x: int
+            var y: int
+            var flag: bool
+
+            if((x > 5 OR x < 0) AND flag)
+            if(((x > 5 OR x < 0) AND (flag OR y == 1)) OR y > 10)
+        "#,
+        )
+        .unwrap();
+        let predicates = &features[0].predicates;
+
+        // AND is distributed over the parenthesized OR
+        assert_eq!(
+            predicates[0].disjunctive_normal_form(),
+            vec![
+                vec![cond("x", "(5, Inf)"), bool_cond("flag", true)],
+                vec![cond("x", "(-Inf, 0)"), bool_cond("flag", true)],
+            ]
+        );
+
+        assert_eq!(
+            predicates[1].disjunctive_normal_form(),
+            vec![
+                vec![cond("x", "(5, Inf)"), bool_cond("flag", true)],
+                vec![cond("x", "(5, Inf)"), cond("y", "[1, 1]")],
+                vec![cond("x", "(-Inf, 0)"), bool_cond("flag", true)],
+                vec![cond("x", "(-Inf, 0)"), cond("y", "[1, 1]")],
+                vec![cond("y", "(10, Inf)")],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ir_to_ntuple_of_parsed_groups() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var x: int
+            var flag: bool
+
+            if((x > 5 OR x < 0) AND flag)
+        "#,
+        )
+        .unwrap();
+        let x = |interval: &str| {
+            Input::Interval(IntervalDTO {
+                interval: multiint(interval),
+                precision: 1.0,
+                is_constant: false,
+                is_integer: true,
+            })
+        };
+
+        // Every conjunction of the distributed AND becomes a tuple
+        assert_eq!(
+            ir_to_ntuple(&features[0]).0,
+            vec![
+                create_ntuple_input(vec![("x", x("(5, Inf)")), ("flag", bool_input(true))]),
+                create_ntuple_input(vec![("x", x("(-Inf, 0)")), ("flag", bool_input(true))]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_negate_predicate() {
+        // !(x && flag) == !x || !flag
+        assert_eq!(
+            negate_predicate(&and(expr("x", "[0, 10]"), bool_expr("flag", true))),
+            vec![*expr("x", "(-Inf, 0) (10, Inf)"), *bool_expr("flag", false)]
+        );
+
+        // !(x || flag) == !x && !flag
+        assert_eq!(
+            negate_predicate(&or(expr("x", "[0, 10]"), bool_expr("flag", true))),
+            vec![*and(
+                expr("x", "(-Inf, 0) (10, Inf)"),
+                bool_expr("flag", false)
+            )]
+        );
+
+        // !!(x || flag) == x || flag
+        assert_eq!(
+            negate_predicate(&Predicate::Negated(or(
+                expr("x", "[0, 10]"),
+                bool_expr("flag", true)
+            ))),
+            vec![*expr("x", "[0, 10]"), *bool_expr("flag", true)]
+        );
+    }
+
     // #[test]
     // fn test_to_ands() {
     //     let predicate = Predicate::Group {
@@ -492,4 +1081,129 @@ mod test {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_feature_queries() {
+        let (_, features) = parse_gpt_to_ir(
+            r#"
+            var x: int
+            var flag: bool
+            var y: int
+            var unused: int
+
+            if(y > 0 && !(x < 5 || x > 10))
+            if(flag == true)
+            if(x == 1 || (flag == false && x == 2))
+        "#,
+        )
+        .unwrap();
+        let feature = &features[0];
+
+        let var_names = |idx: usize| {
+            feature
+                .variables_in_predicate(idx)
+                .unwrap()
+                .into_iter()
+                .map(|variable| variable.var_name.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(var_names(0), vec!["x", "y"]);
+        assert_eq!(var_names(1), vec!["flag"]);
+        assert_eq!(var_names(2), vec!["x", "flag"]);
+
+        assert_eq!(feature.predicates_for_variable("x"), vec![0, 2]);
+        assert_eq!(feature.predicates_for_variable("flag"), vec![1, 2]);
+        assert_eq!(
+            feature.predicates_for_variable("unused"),
+            Vec::<usize>::new()
+        );
+        assert_eq!(
+            feature.predicates_for_variable("undefined"),
+            Vec::<usize>::new()
+        );
+
+        assert_eq!(
+            feature
+                .variable("y")
+                .map(|variable| variable.var_name.as_str()),
+            Some("y")
+        );
+        assert!(feature.variable("undefined").is_none());
+    }
+
+    #[test]
+    fn test_variables_in_predicate_undefined() {
+        let (_, features) = parse_gpt_to_ir("var x: int\nif(x > 0 && z < 5)").unwrap();
+
+        assert_eq!(features[0].variables_in_predicate(0), None);
+        assert_eq!(features[0].variables_in_predicate(1), None);
+    }
+
+    #[test]
+    fn test_feature_spans() {
+        let input = r#"
+            var x: int
+            var flag: bool = true // comment
+
+            if(x > 0 && flag) {
+                if(x < 10)
+            } else if (x < -5)
+            else
+        "#;
+        let (_, features) = parse_gpt_to_ir(input).unwrap();
+        let spans = features[0].spans.clone().unwrap();
+        let snippets = |spans: &[Span]| {
+            spans
+                .iter()
+                .map(|span| span.snippet(input))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            snippets(&spans.variables),
+            vec!["var x: int", "var flag: bool = true"]
+        );
+        assert_eq!(
+            snippets(&spans.predicates),
+            vec!["if(x < 10)", "else if (x < -5)", "else", "else"]
+        );
+        assert_eq!(
+            spans
+                .conditions
+                .iter()
+                .map(|conditions| snippets(conditions))
+                .collect::<Vec<_>>(),
+            vec![
+                vec!["x > 0", "flag", "x < 10"],
+                vec!["x > 0", "flag", "x < 10", "x < -5"],
+                vec!["x > 0", "flag", "x < 10"],
+                vec!["x > 0", "flag", "x < 10", "x < -5"],
+            ]
+        );
+        assert_eq!(spans.predicates[1].line_column(input), (7, 15));
+    }
+
+    #[test]
+    fn test_undefined_variables() {
+        let input = "var x: int\nif(x > 0 && z < 5)\nif(x <= w)";
+        let (_, mut features) = parse_gpt_to_ir(input).unwrap();
+
+        let undefined = features[0].undefined_variables();
+        assert_eq!(
+            undefined
+                .iter()
+                .map(|undefined| undefined.describe(input))
+                .collect::<Vec<_>>(),
+            vec![
+                "Undefined variable `z` at line 2, column 13: `z < 5`",
+                "Undefined variable `w` at line 3, column 4: `x <= w`",
+            ]
+        );
+
+        features[0].spans = None;
+        assert_eq!(
+            features[0].undefined_variables()[1].describe(input),
+            "Undefined variable `w` in predicate 1"
+        );
+    }
 }
@@ -0,0 +1,118 @@
+use itertools::Itertools;
+
+use super::IntervalCondition;
+use crate::interval::{Boundary, Intersectable, Interval, MultiInterval};
+
+/// The pieces of `(-Inf, Inf)` cut at every cut point, every cut point is a piece on its own.
+///
+/// Example: the cut points `0` and `10` result in `(-Inf, 0) [0, 0] (0, 10) [10, 10] (10, Inf)`
+fn elementary_pieces(cut_points: &[f32]) -> Vec<Interval> {
+    let mut pieces = Vec::new();
+    let mut lo = f32::NEG_INFINITY;
+
+    for &point in cut_points {
+        pieces.push(Interval {
+            lo_boundary: Boundary::Open,
+            lo,
+            hi: point,
+            hi_boundary: Boundary::Open,
+        });
+        pieces.push(Interval::new_closed_point(point));
+        lo = point;
+    }
+
+    pieces.push(Interval {
+        lo_boundary: Boundary::Open,
+        lo,
+        hi: f32::INFINITY,
+        hi_boundary: Boundary::Open,
+    });
+
+    pieces
+}
+
+/// Partitions the domain of a variable into equivalence classes, based on all the conditions mentioning the variable.
+///
+/// Every condition is either true or false for all the values of a class,
+/// and neighbouring classes differ in at least one condition, so the partitioning is minimal.
+///
+/// Example: `x in [0, 10)` and `x in [5, 20]` partition the domain into `(-Inf, 0) [0, 5) [5, 10) [10, 20] (20, Inf)`
+pub fn partition_variable(conditions: &[IntervalCondition]) -> Vec<Interval> {
+    let cut_points = conditions
+        .iter()
+        .flat_map(|condition| &condition.interval.intervals)
+        .flat_map(|interval| [interval.lo_endpoint(), interval.hi_endpoint()])
+        .filter(|endpoint| endpoint.value.is_finite())
+        .sorted_unstable()
+        .map(|endpoint| endpoint.value)
+        .dedup()
+        .collect::<Vec<_>>();
+
+    // A piece is either fully inside or fully outside of every condition, because the pieces are cut at every endpoint
+    let truth_values = |piece: &Interval| -> Vec<bool> {
+        let piece = MultiInterval::from_interval(*piece);
+        conditions
+            .iter()
+            .map(|condition| condition.interval.intersects_with(&piece))
+            .collect()
+    };
+
+    let mut classes: Vec<(Interval, Vec<bool>)> = Vec::new();
+
+    for piece in elementary_pieces(&cut_points) {
+        // The first and last pieces can be empty, like (-Inf, -Inf) if there is no cut point
+        if piece.is_empty() {
+            continue;
+        }
+
+        let piece_truth_values = truth_values(&piece);
+
+        match classes.last_mut() {
+            // Neighbouring pieces always border each other, so they can be merged into one interval
+            Some((class, class_truth_values)) if *class_truth_values == piece_truth_values => {
+                class.hi = piece.hi;
+                class.hi_boundary = piece.hi_boundary;
+            }
+            _ => classes.push((piece, piece_truth_values)),
+        }
+    }
+
+    classes.into_iter().map(|(class, _)| class).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::partition_variable;
+    use crate::{
+        interval::test::{int, multiint},
+        ir::IntervalCondition,
+    };
+
+    #[rstest]
+    #[case::no_conditions(vec![], vec!["(-Inf, Inf)"])]
+    #[case::single_condition(vec!["[0, 10)"], vec!["(-Inf, 0)", "[0, 10)", "[10, Inf)"])]
+    #[case::overlapping(
+        vec!["[0, 10)", "[5, 20]"],
+        vec!["(-Inf, 0)", "[0, 5)", "[5, 10)", "[10, 20]", "(20, Inf)"],
+    )]
+    #[case::same_boundaries(vec!["[0, 10]", "(0, 10)"], vec!["(-Inf, 0)", "[0, 0]", "(0, 10)", "[10, 10]", "(10, Inf)"])]
+    #[case::single_point(vec!["[5, 5]"], vec!["(-Inf, 5)", "[5, 5]", "(5, Inf)"])]
+    #[case::infinite(vec!["(-Inf, 0)", "(0, Inf)"], vec!["(-Inf, 0)", "[0, 0]", "(0, Inf)"])]
+    #[case::multi_interval(vec!["(-Inf, 0) [10, 20]"], vec!["(-Inf, 0)", "[0, 10)", "[10, 20]", "(20, Inf)"])]
+    #[case::whole_domain(vec!["(-Inf, Inf)"], vec!["(-Inf, Inf)"])]
+    fn test_partition_variable(#[case] conditions: Vec<&str>, #[case] expected: Vec<&str>) {
+        let conditions = conditions
+            .into_iter()
+            .map(|interval| IntervalCondition {
+                var_name: "x".to_owned(),
+                interval: multiint(interval),
+            })
+            .collect::<Vec<_>>();
+        let expected = expected.into_iter().map(int).collect::<Vec<_>>();
+
+        assert_eq!(partition_variable(&conditions), expected);
+    }
+}
@@ -0,0 +1,152 @@
+//! Traversal of the IR for custom analyses, without walking the nested predicates by hand.
+//!
+//! Every `visit_` method does nothing by default, except the ones with children, which call the matching `walk_` function.
+//! Overriding a method with children, and not calling its `walk_` function, skips its children.
+
+use super::{
+    BoolCondition, Condition, EnumCondition, Feature, IntervalCondition, Predicate,
+    RelationCondition, Variable,
+};
+
+pub trait Visitor {
+    fn visit_variable(&mut self, _variable: &Variable) {}
+
+    /// Called for the predicates of the feature, and for every sub-predicate of them
+    fn visit_predicate(&mut self, predicate: &Predicate) {
+        walk_predicate(self, predicate);
+    }
+
+    fn visit_condition(&mut self, condition: &Condition) {
+        walk_condition(self, condition);
+    }
+
+    fn visit_bool_condition(&mut self, _condition: &BoolCondition) {}
+
+    fn visit_interval_condition(&mut self, _condition: &IntervalCondition) {}
+
+    fn visit_enum_condition(&mut self, _condition: &EnumCondition) {}
+
+    fn visit_relation_condition(&mut self, _condition: &RelationCondition) {}
+}
+
+/// Visits the variables, then the predicates of the feature, in their order
+pub fn walk_feature<V: Visitor + ?Sized>(visitor: &mut V, feature: &Feature) {
+    for variable in &feature.variables {
+        visitor.visit_variable(variable);
+    }
+
+    for predicate in &feature.predicates {
+        visitor.visit_predicate(predicate);
+    }
+}
+
+/// Visits the sub-predicates, the left one first, or the condition of an expression
+pub fn walk_predicate<V: Visitor + ?Sized>(visitor: &mut V, predicate: &Predicate) {
+    match predicate {
+        Predicate::Negated(pred) => visitor.visit_predicate(pred),
+        Predicate::Expression(cond) => visitor.visit_condition(cond),
+        Predicate::Group { left, right, .. } => {
+            visitor.visit_predicate(left);
+            visitor.visit_predicate(right);
+        }
+    }
+}
+
+pub fn walk_condition<V: Visitor + ?Sized>(visitor: &mut V, condition: &Condition) {
+    match condition {
+        Condition::Bool(cond) => visitor.visit_bool_condition(cond),
+        Condition::Interval(cond) => visitor.visit_interval_condition(cond),
+        Condition::Enum(cond) => visitor.visit_enum_condition(cond),
+        Condition::Relation(cond) => visitor.visit_relation_condition(cond),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{walk_feature, walk_predicate, Visitor};
+    use crate::{
+        interval::MultiInterval,
+        ir::{BoolCondition, IntervalCondition, Predicate, Variable},
+        parser::{ast::Type, parse_gpt_to_ir},
+    };
+
+    const INPUT: &str = r#"
+        var x: int
+        var flag: bool
+        var y: num(0.1)
+
+        if(x > 0 && flag == true)
+        if(!(x in [0, 10] || y < 5) && flag == false)
+    "#;
+
+    #[derive(Default)]
+    struct Collector {
+        integer_variables: Vec<String>,
+        intervals: Vec<(String, MultiInterval)>,
+        bools: Vec<bool>,
+        predicates: usize,
+    }
+
+    impl Visitor for Collector {
+        fn visit_variable(&mut self, variable: &Variable) {
+            if variable.var_type == Type::Integer {
+                self.integer_variables.push(variable.var_name.clone());
+            }
+        }
+
+        fn visit_predicate(&mut self, predicate: &Predicate) {
+            self.predicates += 1;
+            walk_predicate(self, predicate);
+        }
+
+        fn visit_interval_condition(&mut self, condition: &IntervalCondition) {
+            self.intervals
+                .push((condition.var_name.clone(), condition.interval.clone()));
+        }
+
+        fn visit_bool_condition(&mut self, condition: &BoolCondition) {
+            self.bools.push(condition.should_equal_to);
+        }
+    }
+
+    #[test]
+    fn test_walk_feature() {
+        let (_, features) = parse_gpt_to_ir(INPUT).unwrap();
+
+        let mut collector = Collector::default();
+        walk_feature(&mut collector, &features[0]);
+
+        assert_eq!(collector.integer_variables, vec!["x"]);
+        assert_eq!(
+            collector
+                .intervals
+                .iter()
+                .map(|(var_name, interval)| format!("{var_name} in {interval}"))
+                .collect::<Vec<_>>(),
+            vec!["x in (0, Inf)", "x in [0, 10]", "y in (-Inf, 5)"]
+        );
+        assert_eq!(collector.bools, vec![true, false]);
+        // 2 top level predicates, 3 in the first one, 6 in the second one
+        assert_eq!(collector.predicates, 9);
+    }
+
+    #[test]
+    fn test_skip_children() {
+        struct TopLevelOnly(usize);
+
+        impl Visitor for TopLevelOnly {
+            fn visit_predicate(&mut self, _predicate: &Predicate) {
+                self.0 += 1;
+            }
+        }
+
+        let (_, features) = parse_gpt_to_ir(INPUT).unwrap();
+
+        let mut visitor = TopLevelOnly(0);
+        walk_feature(&mut visitor, &features[0]);
+
+        assert_eq!(visitor.0, 2);
+    }
+}
@@ -18,24 +18,36 @@
 )]
 
 use dto::NTupleSingleInterval;
-use ir::Feature;
+use export::ntuples_to_json;
+use ir::{
+    ir_to_ntuple::{check_condition_types, check_precisions, ir_to_ntuple},
+    Feature,
+};
 use nom::{error::convert_error, Err};
-use parser::parse_gpt_to_features;
 use prelude::{GPTError, Result};
 use test_case_generator::generate_test_cases_for_multiple_features;
 
 use crate::parser::parse_gpt_to_ir;
 
+pub mod binary;
 pub mod bva;
+pub mod combination;
 pub mod dto;
+pub mod export;
 pub mod graph_reduction;
 pub mod interval;
-mod ir;
+pub mod ir;
 pub mod parser;
 pub mod prelude;
+pub mod prioritize;
+pub mod shrink;
 pub mod test_case_generator;
 pub mod test_value_generator;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 mod util;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 pub fn and_reduce_gpt_input(input: &str) -> Result<Vec<Feature>> {
     let (_, ir) = parse_gpt_to_ir(input).map_err(|error| match error {
@@ -43,17 +55,187 @@ pub fn and_reduce_gpt_input(input: &str) -> Result<Vec<Feature>> {
         Err::Incomplete(err) => GPTError::UnknownParseError(format!("{err:?}")),
     })?;
 
+    for feature in &ir {
+        let undefined_variables = feature.undefined_variables();
+        if !undefined_variables.is_empty() {
+            return Err(GPTError::TypeError(
+                undefined_variables
+                    .iter()
+                    .map(|undefined| undefined.describe(input))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ));
+        }
+
+        check_precisions(feature).map_err(|errors| {
+            GPTError::TypeError(
+                errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )
+        })?;
+
+        check_condition_types(feature).map_err(|errors| {
+            GPTError::TypeError(
+                errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )
+        })?;
+    }
+
     Ok(ir)
 }
 
 pub fn generate_tests_for_gpt_input(input: &str) -> Result<Vec<NTupleSingleInterval>> {
-    let (_, features) = parse_gpt_to_features(input).map_err(|error| match error {
-        Err::Error(err) | Err::Failure(err) => GPTError::ParseError(convert_error(input, err)),
-        Err::Incomplete(err) => GPTError::UnknownParseError(format!("{err:?}")),
-    })?;
-    log::warn!("Inputs: {:#?}", features);
+    let features = and_reduce_gpt_input(input)?;
     let test_cases = generate_test_cases_for_multiple_features(&features)
         .map_err(|err| GPTError::IntervalError(format!("{err:?}")))?;
 
     Ok(test_cases)
 }
+
+/// Parses the input and returns the n-tuples of every feature as a JSON array string, see [`ntuples_to_json`].
+pub fn ntuples_json_for_gpt_input(input: &str) -> Result<String> {
+    let features = and_reduce_gpt_input(input)?;
+
+    let json = serde_json::Value::Array(
+        features
+            .iter()
+            .map(|feature| ntuples_to_json(feature, &ir_to_ntuple(feature).0))
+            .collect(),
+    );
+
+    Ok(json.to_string())
+}
+
+/// Parses the input and returns the warnings of every feature as a JSON array string, see [`ir_to_ntuple`].
+/// Each feature has an array of the warning messages, so they can be shown next to the spec.
+pub fn warnings_json_for_gpt_input(input: &str) -> Result<String> {
+    let features = and_reduce_gpt_input(input)?;
+
+    let json = serde_json::Value::Array(
+        features
+            .iter()
+            .map(|feature| {
+                ir_to_ntuple(feature)
+                    .1
+                    .iter()
+                    .map(|warning| serde_json::Value::String(warning.to_string()))
+                    .collect()
+            })
+            .collect(),
+    );
+
+    Ok(json.to_string())
+}
+
+/// Generates the test cases for the input as a JSON array string.
+/// The intervals are serialized in their string form, like `"(-Inf, 10]"`, so infinities survive `JSON.parse`.
+pub fn generate_tests_json_for_gpt_input(input: &str) -> Result<String> {
+    let test_cases = generate_tests_for_gpt_input(input)?;
+
+    serde_json::to_string(&test_cases).map_err(|err| GPTError::SerializationError(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        generate_tests_for_gpt_input, generate_tests_json_for_gpt_input,
+        ntuples_json_for_gpt_input, warnings_json_for_gpt_input,
+    };
+    use crate::{dto::Output, prelude::GPTError};
+
+    #[test]
+    fn test_ntuples_json_for_gpt_input() {
+        let json = ntuples_json_for_gpt_input("var x: int\nif(x < 10)").unwrap();
+
+        assert_eq!(
+            json,
+            r#"[[{"x":{"max":10.0,"maxInclusive":false,"min":"-Inf","minInclusive":false}}]]"#
+        );
+    }
+
+    #[test]
+    fn test_generate_tests_json_for_gpt_input() {
+        let json = generate_tests_json_for_gpt_input("var x: int\nif(x < 10)").unwrap();
+        let test_cases: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+
+        assert!(test_cases.contains(&serde_json::json!({ "x": "(-Inf, 9]" })));
+        assert!(ntuples_json_for_gpt_input("var x: int\nif(x <").is_err());
+    }
+
+    #[test]
+    fn test_generated_tests_honor_relations() {
+        let input =
+            "var start: int\nvar end: int\nif(start <= end && start in [0, 10] && end in [0, 10])";
+        let test_cases = generate_tests_for_gpt_input(input).unwrap();
+
+        assert!(!test_cases.is_empty());
+        for test_case in &test_cases {
+            if let (Some(Output::Interval(start)), Some(Output::Interval(end))) =
+                (test_case.get("start"), test_case.get("end"))
+            {
+                assert!(start.lo <= end.hi, "start ∈ {start} and end ∈ {end}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_warnings_json_for_gpt_input() {
+        let json = warnings_json_for_gpt_input(
+            "var x: int in [0, 10]\nif(x > 20)\nif(x in [0, 5])\nif(x >= 3)",
+        )
+        .unwrap();
+
+        assert_eq!(
+            json,
+            r#"[["predicate 0: x ∈ (20, Inf) is outside of the domain [0, 10]","predicates 1 and 2 both cover x ∈ [3, 5]"]]"#
+        );
+    }
+
+    #[test]
+    fn test_condition_type_is_checked() {
+        for input in [
+            "var x: int\nif(x in {a, b})",
+            "var s: enum {a, b}\nif(s == true)",
+            "var s: enum {a, b}\nif(s in {c})",
+            "var x: int\nif(x > 0)\nif(x == true)",
+        ] {
+            assert!(matches!(
+                generate_tests_json_for_gpt_input(input),
+                Err(GPTError::TypeError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_undefined_variable_is_an_error() {
+        let input = "var x: int\nif(x > 0)\nif(x < 0 &&  y > 5)";
+
+        assert_eq!(
+            ntuples_json_for_gpt_input(input).unwrap_err().to_string(),
+            "Type error: Undefined variable `y` at line 3, column 14: `y > 5`"
+        );
+    }
+
+    #[test]
+    fn test_missing_precision_is_an_error() {
+        let input = "var flag: bool\nif(flag > 5)";
+
+        assert!(matches!(
+            ntuples_json_for_gpt_input(input),
+            Err(GPTError::TypeError(_))
+        ));
+        assert!(matches!(
+            generate_tests_json_for_gpt_input(input),
+            Err(GPTError::TypeError(_))
+        ));
+    }
+}
@@ -1,20 +1,94 @@
-use crate::interval::MultiInterval;
+use std::fmt;
 
-#[derive(PartialEq, Debug, Clone, Copy)]
+use nom::Offset;
+
+use crate::interval::{Interval, MultiInterval};
+
+/// A byte range of the input, where a part of it was parsed from, for pointing at it in diagnostics
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// The span of `source` in `input`. The `source` has to be a slice of `input`, like the sources in the AST.
+    pub fn of(input: &str, source: &str) -> Self {
+        let start = input.offset(source);
+
+        Self {
+            start,
+            end: start + source.len(),
+        }
+    }
+
+    /// Moves the span with the function of the offsets, like when the text before it was edited
+    pub fn map(self, f: impl Fn(usize) -> usize) -> Self {
+        Self {
+            start: f(self.start),
+            end: f(self.end),
+        }
+    }
+
+    /// The text of the span in the input it was made from
+    pub fn snippet<'a>(&self, input: &'a str) -> &'a str {
+        &input[self.start..self.end]
+    }
+
+    /// The line and column where the span starts, both starting from 1. The column is counted in characters.
+    pub fn line_column(&self, input: &str) -> (usize, usize) {
+        let before = &input[..self.start];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+
+        (
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+        )
+    }
+
+    /// Quotes the snippet with its position for an error message, like ``line 3, column 4: `x > 5` ``
+    pub fn quote(&self, input: &str) -> String {
+        let (line, column) = self.line_column(input);
+
+        format!("line {line}, column {column}: `{}`", self.snippet(input))
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
 pub enum Type {
     Bool,
     Integer,
-    Float { precision: f32 },
+    Float {
+        precision: f32,
+    },
+    /// A number with a fixed number of decimal places, like `decimal(2)` for the precision of `0.01`
+    Decimal {
+        places: u8,
+    },
+    /// One of the declared values, like `enum {active, closed}`
+    Enum(Vec<String>),
 }
 
 impl Type {
-    pub const fn get_precision(&self) -> Option<f32> {
+    pub fn get_precision(&self) -> Option<f32> {
         match self {
-            Self::Bool => None,
+            Self::Bool | Self::Enum(_) => None,
             Self::Integer => Some(1.0),
             Self::Float { precision } => Some(*precision),
+            // Dividing by the exact power of ten, so 2 places is the same f32 as the literal `0.01`
+            Self::Decimal { places } => Some(1.0 / 10f32.powi(i32::from(*places))),
         }
     }
+
+    pub const fn is_integer(&self) -> bool {
+        matches!(self, Self::Integer)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Literal {
+    Bool(bool),
+    Number(f32),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -47,6 +121,35 @@ impl BinaryOp {
             Self::NotEqual => Self::NotEqual,
         }
     }
+
+    /// The `BinaryOp` which holds exactly when this one doesn't.
+    ///
+    /// Example: !(x < y) == x >= y
+    pub const fn negated(&self) -> Self {
+        match self {
+            Self::LessThan => Self::GreaterThanEqualTo,
+            Self::GreaterThan => Self::LessThanEqualTo,
+            Self::LessThanEqualTo => Self::GreaterThan,
+            Self::GreaterThanEqualTo => Self::LessThan,
+            Self::Equal => Self::NotEqual,
+            Self::NotEqual => Self::Equal,
+        }
+    }
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self {
+            Self::LessThan => "<",
+            Self::GreaterThan => ">",
+            Self::LessThanEqualTo => "<=",
+            Self::GreaterThanEqualTo => ">=",
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+        };
+
+        write!(f, "{op}")
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -81,18 +184,36 @@ pub struct BinaryCondition<'a> {
     pub constant: f32,
     pub binary_op: BinaryOp,
 }
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct IntervalCondition<'a> {
     pub var_name: &'a str,
     pub interval_op: IntervalOp,
     pub interval: MultiInterval,
 }
 
+/// A condition on an enum variable, like `status in {active, closed}`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EnumCondition<'a> {
+    pub var_name: &'a str,
+    pub interval_op: IntervalOp,
+    pub values: Vec<&'a str>,
+}
+
+/// A comparison of two numeric variables, like `start <= end`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RelationCondition<'a> {
+    pub left: &'a str,
+    pub binary_op: BinaryOp,
+    pub right: &'a str,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Condition<'a> {
     Bool(BoolCondition<'a>),
     Binary(BinaryCondition<'a>),
     Interval(IntervalCondition<'a>),
+    Enum(EnumCondition<'a>),
+    Relation(RelationCondition<'a>),
 }
 
 #[derive(PartialEq, Debug)]
@@ -103,12 +224,18 @@ pub struct FeatureNode<'a> {
 
 #[derive(PartialEq, Debug)]
 pub struct VarNode<'a> {
+    /// The whole declaration, like `var x: int in [0, 10]`
+    pub source: &'a str,
     pub var_name: &'a str,
     pub var_type: Type,
+    pub domain: Option<Interval>,
+    pub default: Option<Literal>,
 }
 
 #[derive(PartialEq, Debug)]
 pub struct IfNode<'a> {
+    /// The `if` with its conditions, like `if(x > 0)`, without the body
+    pub source: &'a str,
     pub conditions: ConditionsNode<'a>,
     pub body: Option<Vec<IfNode<'a>>>,
     pub else_if: Vec<ElseIfNode<'a>>,
@@ -117,19 +244,24 @@ pub struct IfNode<'a> {
 
 #[derive(PartialEq, Debug)]
 pub struct ElseIfNode<'a> {
+    /// The `else if` with its conditions, like `else if(x > 0)`, without the body
+    pub source: &'a str,
     pub conditions: ConditionsNode<'a>,
     pub body: Vec<IfNode<'a>>,
 }
 
 #[derive(PartialEq, Debug)]
 pub struct ElseNode<'a> {
+    /// The `else` keyword
+    pub source: &'a str,
     pub body: Vec<IfNode<'a>>,
 }
 
 #[derive(PartialEq, Debug)]
 pub enum ConditionsNode<'a> {
     Negated(Box<ConditionsNode<'a>>),
-    Expression(Condition<'a>),
+    /// The condition and its source, like `x > 0`
+    Expression(Condition<'a>, &'a str),
     Group {
         operator: BoolOp,
         left: Box<ConditionsNode<'a>>,
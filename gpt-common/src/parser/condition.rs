@@ -1,18 +1,18 @@
 use nom::{
     branch::alt,
-    combinator::{cut, map, value},
+    combinator::{cut, map, not, value},
     error::context,
-    sequence::tuple,
+    sequence::{terminated, tuple},
 };
 
 use super::{
     ast::{
         BinaryCondition, BoolCondition, BoolOp, Condition, ConditionsNode, ConstantPosition,
-        IntervalCondition,
+        EnumCondition, EqOp, IntervalCondition, RelationCondition,
     },
     interval,
-    primitives::{binary_op, boolean, eq_op, interval_op, number, var_name},
-    utils::{parenthesized, token, token_lit},
+    primitives::{binary_op, boolean, enum_values, eq_op, interval_op, keyword, number, var_name},
+    utils::{parenthesized, token, token_lit, with_source},
     IResult,
 };
 
@@ -46,6 +46,25 @@ fn condition_bool_rhs(input: &str) -> IResult<Condition> {
     ))
 }
 
+/// A bool variable on its own, like `flag`, which is the same as `flag == true`
+fn condition_bool_shorthand(input: &str) -> IResult<'_, Condition<'_>> {
+    let is_operator = alt((
+        value((), binary_op),
+        value((), eq_op),
+        value((), interval_op),
+    ));
+    let (input, var_name) = terminated(token(var_name), not(is_operator))(input)?;
+
+    Ok((
+        input,
+        Condition::Bool(BoolCondition {
+            var_name,
+            constant: true,
+            eq_op: EqOp::Equal,
+        }),
+    ))
+}
+
 fn condition_binary_lhs(input: &str) -> IResult<Condition> {
     map(
         tuple((token(number), token(binary_op), token(var_name))),
@@ -87,6 +106,33 @@ fn condition_interval(input: &str) -> IResult<Condition> {
     )(input)
 }
 
+fn condition_enum(input: &str) -> IResult<'_, Condition<'_>> {
+    map(
+        tuple((token(var_name), token(interval_op), token(enum_values))),
+        |(var_name, interval_op, values)| {
+            Condition::Enum(EnumCondition {
+                var_name,
+                interval_op,
+                values,
+            })
+        },
+    )(input)
+}
+
+/// Two variables compared to each other, like `start <= end`
+fn condition_relation(input: &str) -> IResult<'_, Condition<'_>> {
+    map(
+        tuple((token(var_name), token(binary_op), token(var_name))),
+        |(left, binary_op, right)| {
+            Condition::Relation(RelationCondition {
+                left,
+                binary_op,
+                right,
+            })
+        },
+    )(input)
+}
+
 fn condition(input: &str) -> IResult<Condition> {
     context(
         "condition",
@@ -96,14 +142,17 @@ fn condition(input: &str) -> IResult<Condition> {
             condition_bool_lhs,
             condition_bool_rhs,
             condition_interval,
+            condition_enum,
+            condition_relation,
+            condition_bool_shorthand,
         )),
     )(input)
 }
 
 fn raw_expression(input: &str) -> IResult<ConditionsNode> {
-    let (input, condition) = token(condition)(input)?;
+    let (input, (source, condition)) = with_source(token(condition))(input)?;
 
-    Ok((input, ConditionsNode::Expression(condition)))
+    Ok((input, ConditionsNode::Expression(condition, source)))
 }
 
 fn negated(input: &str) -> IResult<ConditionsNode> {
@@ -120,11 +169,19 @@ fn expression(input: &str) -> IResult<ConditionsNode> {
     )(input)
 }
 
+fn and_op(input: &str) -> IResult<'_, BoolOp> {
+    value(BoolOp::And, alt((token_lit("&&"), token(keyword("AND")))))(input)
+}
+
+fn or_op(input: &str) -> IResult<'_, BoolOp> {
+    value(BoolOp::Or, alt((token_lit("||"), token(keyword("OR")))))(input)
+}
+
 fn and_condition(input: &str) -> IResult<ConditionsNode> {
     alt((
         |input| {
             let (input, left) = expression(input)?;
-            let (input, op) = value(BoolOp::And, token_lit("&&"))(input)?;
+            let (input, op) = and_op(input)?;
             let (input, right) = and_condition(input)?;
 
             Ok((
@@ -144,7 +201,7 @@ fn or_condition(input: &str) -> IResult<ConditionsNode> {
     alt((
         |input| {
             let (input, left) = and_condition(input)?;
-            let (input, op) = value(BoolOp::Or, token_lit("||"))(input)?;
+            let (input, op) = or_op(input)?;
             let (input, right) = or_condition(input)?;
 
             Ok((
@@ -161,6 +218,8 @@ fn or_condition(input: &str) -> IResult<ConditionsNode> {
     ))(input)
 }
 
+/// The conditions joined by `&&` (or `AND`) and `||` (or `OR`).
+/// `&&` binds tighter than `||`, so `a || b && c` is `a || (b && c)`, parentheses can be used for grouping.
 pub fn conditions(input: &str) -> IResult<ConditionsNode> {
     context("conditions", alt((or_condition, and_condition, expression)))(input)
 }
@@ -283,6 +342,36 @@ mod tests {
         assert!(condition_bool_rhs("x ==").is_err());
     }
 
+    #[test]
+    fn test_condition_bool_shorthand() {
+        assert_eq!(
+            condition_bool_shorthand("flag"),
+            Ok((
+                "",
+                Condition::Bool(BoolCondition {
+                    var_name: "flag",
+                    constant: true,
+                    eq_op: EqOp::Equal
+                })
+            ))
+        );
+        assert_eq!(
+            condition_bool_shorthand("flag OR x"),
+            Ok((
+                "OR x",
+                Condition::Bool(BoolCondition {
+                    var_name: "flag",
+                    constant: true,
+                    eq_op: EqOp::Equal
+                })
+            ))
+        );
+        assert!(condition_bool_shorthand("x < 5").is_err());
+        assert!(condition_bool_shorthand("x == true").is_err());
+        assert!(condition_bool_shorthand("x in [0, 10]").is_err());
+        assert!(condition_bool_shorthand("true").is_err());
+    }
+
     #[test]
     fn test_condition_binary_lhs() {
         assert_eq!(
@@ -420,6 +509,63 @@ mod tests {
         assert!(condition_interval(" in [0, 10]").is_err());
     }
 
+    #[test]
+    fn test_condition_enum() {
+        assert_eq!(
+            condition_enum("status in {active, closed}"),
+            Ok((
+                "",
+                Condition::Enum(EnumCondition {
+                    var_name: "status",
+                    interval_op: IntervalOp::In,
+                    values: vec!["active", "closed"],
+                })
+            ))
+        );
+        assert_eq!(
+            condition_enum("status not in{closed}) && x"),
+            Ok((
+                ") && x",
+                Condition::Enum(EnumCondition {
+                    var_name: "status",
+                    interval_op: IntervalOp::NotIn,
+                    values: vec!["closed"],
+                })
+            ))
+        );
+        assert!(condition_enum("status in [0, 1]").is_err());
+        assert!(condition_enum("status == {active}").is_err());
+    }
+
+    #[test]
+    fn test_condition_relation() {
+        assert_eq!(
+            condition_relation("start <= end"),
+            Ok((
+                "",
+                Condition::Relation(RelationCondition {
+                    left: "start",
+                    binary_op: BinaryOp::LessThanEqualTo,
+                    right: "end",
+                })
+            ))
+        );
+        assert_eq!(
+            condition_relation("x!=y)"),
+            Ok((
+                ")",
+                Condition::Relation(RelationCondition {
+                    left: "x",
+                    binary_op: BinaryOp::NotEqual,
+                    right: "y",
+                })
+            ))
+        );
+        assert!(condition_relation("x < 10").is_err());
+        assert!(condition_relation("x == true").is_err());
+        assert!(condition_relation("x in y").is_err());
+    }
+
     #[test]
     fn test_condition() {
         assert_eq!(
@@ -480,6 +626,22 @@ mod tests {
                 })
             ))
         );
+        assert_eq!(
+            condition("x > y"),
+            Ok((
+                "",
+                Condition::Relation(RelationCondition {
+                    left: "x",
+                    binary_op: BinaryOp::GreaterThan,
+                    right: "y",
+                })
+            ))
+        );
+        assert_eq!(
+            condition("x == true"),
+            condition_bool_rhs("x == true"),
+            "a bool literal is not a variable"
+        );
     }
 
     #[test]
@@ -499,15 +661,18 @@ mod tests {
 
         assert_eq!(
             conditions("true == x"),
-            Ok(("", ConditionsNode::Expression(x_eq_true.clone())))
+            Ok((
+                "",
+                ConditionsNode::Expression(x_eq_true.clone(), "true == x")
+            ))
         );
         assert_eq!(
             conditions("true == x && 0 > y"),
             Ok((
                 "",
                 ConditionsNode::Group {
-                    left: Box::new(ConditionsNode::Expression(x_eq_true.clone())),
-                    right: Box::new(ConditionsNode::Expression(y_greater_0.clone())),
+                    left: Box::new(ConditionsNode::Expression(x_eq_true.clone(), "true == x")),
+                    right: Box::new(ConditionsNode::Expression(y_greater_0.clone(), "0 > y")),
                     operator: BoolOp::And,
                 }
             ))
@@ -517,10 +682,10 @@ mod tests {
             Ok((
                 "asd",
                 ConditionsNode::Group {
-                    left: Box::new(ConditionsNode::Expression(x_eq_true.clone())),
+                    left: Box::new(ConditionsNode::Expression(x_eq_true.clone(), "true == x")),
                     right: Box::new(ConditionsNode::Group {
-                        left: Box::new(ConditionsNode::Expression(y_greater_0.clone())),
-                        right: Box::new(ConditionsNode::Expression(y_greater_0.clone())),
+                        left: Box::new(ConditionsNode::Expression(y_greater_0.clone(), "0 > y")),
+                        right: Box::new(ConditionsNode::Expression(y_greater_0.clone(), "0 > y")),
                         operator: BoolOp::And,
                     }),
                     operator: BoolOp::And,
@@ -533,11 +698,11 @@ mod tests {
                 "asd",
                 ConditionsNode::Group {
                     left: Box::new(ConditionsNode::Group {
-                        left: Box::new(ConditionsNode::Expression(x_eq_true.clone())),
-                        right: Box::new(ConditionsNode::Expression(y_greater_0.clone())),
+                        left: Box::new(ConditionsNode::Expression(x_eq_true.clone(), "true == x")),
+                        right: Box::new(ConditionsNode::Expression(y_greater_0.clone(), "0 > y")),
                         operator: BoolOp::And,
                     }),
-                    right: Box::new(ConditionsNode::Expression(y_greater_0.clone())),
+                    right: Box::new(ConditionsNode::Expression(y_greater_0.clone(), "0 > y")),
                     operator: BoolOp::And,
                 }
             ))
@@ -547,16 +712,31 @@ mod tests {
             Ok((
                 "asd",
                 ConditionsNode::Group {
-                    left: Box::new(ConditionsNode::Expression(x_eq_true.clone())),
+                    left: Box::new(ConditionsNode::Expression(x_eq_true.clone(), "true == x")),
                     right: Box::new(ConditionsNode::Group {
-                        left: Box::new(ConditionsNode::Expression(y_greater_0.clone())),
-                        right: Box::new(ConditionsNode::Expression(y_greater_0.clone())),
+                        left: Box::new(ConditionsNode::Expression(y_greater_0.clone(), "0 > y")),
+                        right: Box::new(ConditionsNode::Expression(y_greater_0.clone(), "0 > y")),
                         operator: BoolOp::And,
                     }),
                     operator: BoolOp::Or,
                 }
             ))
         );
+        assert_eq!(
+            conditions("true == x OR 0 > y AND 0 > y    asd"),
+            conditions("true == x || 0 > y && 0 > y    asd"),
+        );
+        assert_eq!(
+            conditions("x OR 0 > y"),
+            Ok((
+                "",
+                ConditionsNode::Group {
+                    left: Box::new(ConditionsNode::Expression(x_eq_true.clone(), "x")),
+                    right: Box::new(ConditionsNode::Expression(y_greater_0.clone(), "0 > y")),
+                    operator: BoolOp::Or,
+                }
+            ))
+        );
         // TODO: Add a bunch more tests for testing good precedence detection and stuff
         assert!(conditions("").is_err());
         assert!(conditions("true == x &&").is_err());
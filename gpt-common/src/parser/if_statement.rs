@@ -8,23 +8,25 @@ use nom::{
 use super::{
     ast::{ElseIfNode, ElseNode, IfNode},
     condition::conditions,
-    utils::token_lit,
+    utils::{source_until, token_lit},
     IResult,
 };
 
 fn else_if_statement(input: &str) -> IResult<ElseIfNode> {
-    context("else if statement", |input| {
-        let (input, _) = token_lit("else")(input)?;
+    context("else if statement", |start| {
+        let (input, _) = token_lit("else")(start)?;
         let (input, _) = token_lit("if")(input)?;
         let (input, _) = token_lit("(")(input)?;
         let (input, conditions) = conditions(input)?;
         let (input, _) = token_lit(")")(input)?;
+        let source = source_until(start, input);
         let (input, body) = opt(map(
             tuple((token_lit("{"), many0(if_statement), token_lit("}"))),
             |(_, body, _)| body,
         ))(input)?;
 
         let else_if_node = ElseIfNode {
+            source,
             conditions,
             body: body.unwrap_or_default(),
         };
@@ -34,8 +36,9 @@ fn else_if_statement(input: &str) -> IResult<ElseIfNode> {
 }
 
 fn else_statement(input: &str) -> IResult<ElseNode> {
-    context("else statement", |input| {
-        let (input, _) = token_lit("else")(input)?;
+    context("else statement", |start| {
+        let (input, _) = token_lit("else")(start)?;
+        let source = source_until(start, input);
         let (input, if_statements) = opt(|input| {
             let (input, _) = token_lit("{")(input)?;
             let (input, if_statements) = many0(if_statement)(input)?;
@@ -45,6 +48,7 @@ fn else_statement(input: &str) -> IResult<ElseNode> {
         })(input)?;
 
         let else_node = ElseNode {
+            source,
             body: if_statements.unwrap_or_default(),
         };
 
@@ -53,12 +57,13 @@ fn else_statement(input: &str) -> IResult<ElseNode> {
 }
 
 pub fn if_statement(input: &str) -> IResult<IfNode> {
-    context("if statement", |input| {
-        let (input, _) = token_lit("if")(input)?;
+    context("if statement", |start| {
+        let (input, _) = token_lit("if")(start)?;
         cut(|input| {
             let (input, _) = token_lit("(")(input)?;
             let (input, conditions) = conditions(input)?;
             let (input, _) = token_lit(")")(input)?;
+            let source = source_until(start, input);
             let (input, body) = opt(map(
                 tuple((token_lit("{"), many0(if_statement), token_lit("}"))),
                 |(_, body, _)| body,
@@ -68,6 +73,7 @@ pub fn if_statement(input: &str) -> IResult<IfNode> {
             let (input, else_statement) = opt(else_statement)(input)?;
 
             let if_node = IfNode {
+                source,
                 body,
                 conditions,
                 else_if: else_if_statements.unwrap_or_default(),
@@ -102,19 +108,24 @@ mod tests {
             Ok((
                 "qwe",
                 IfNode {
+                    source: "if (x >= 5 && y in (0, 10))",
                     conditions: conditions("x >= 5 && y in (0, 10)").unwrap().1,
                     body: Some(vec![IfNode {
+                        source: "if (x == true)",
                         conditions: conditions("x == true").unwrap().1,
                         body: None,
                         else_if: vec![],
                         else_node: None
                     }]),
                     else_if: vec![ElseIfNode {
+                        source: "else if (x < 4 && y > 6)",
                         conditions: conditions("x < 4 && y > 6").unwrap().1,
                         body: vec![]
                     }],
                     else_node: Some(ElseNode {
+                        source: "else",
                         body: vec![IfNode {
+                            source: "if (x != false)",
                             conditions: conditions("x != false").unwrap().1,
                             body: None,
                             else_if: vec![],
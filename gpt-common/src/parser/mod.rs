@@ -6,12 +6,15 @@ mod primitives;
 mod utils;
 mod var_declaration;
 
+use std::ops::Range;
+
 use ast::RootNode;
 use nom::{
     branch::alt,
-    combinator::{eof, map},
-    error::VerboseError,
+    combinator::{all_consuming, eof, map},
+    error::{convert_error, VerboseError},
     multi::many1,
+    Err, Offset,
 };
 
 pub use self::primitives::interval;
@@ -20,7 +23,10 @@ use self::{
     utils::{token, whitespace},
 };
 use super::dto::NTupleInput;
-use crate::ir;
+use crate::{
+    ir,
+    prelude::{GPTError, Result},
+};
 
 type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
 
@@ -42,25 +48,402 @@ fn root(input: &str) -> IResult<RootNode> {
 }
 
 pub fn parse_gpt_to_ir(input: &str) -> IResult<Vec<ir::Feature>> {
-    let (input, ast) = root(input)?;
-    let ir_features = ir::ast_to_ir::convert_ast_to_ir(&ast);
+    let (rest, ast) = root(input)?;
+    let ir_features = ir::ast_to_ir::convert_ast_to_ir(&ast, input);
 
-    Ok((input, ir_features))
+    Ok((rest, ir_features))
 }
 
 pub fn parse_gpt_to_features(input: &str) -> IResult<Vec<Vec<NTupleInput>>> {
-    let (input, ast) = root(input)?;
-    let ir_features = ir::ast_to_ir::convert_ast_to_ir(&ast);
+    let (rest, ast) = root(input)?;
+    let ir_features = ir::ast_to_ir::convert_ast_to_ir(&ast, input);
     let ntuples_for_features = ir_features
         .iter()
-        .map(ir::ir_to_ntuple::ir_to_ntuple)
+        .map(|feature| ir::ir_to_ntuple::ir_to_ntuple(feature).0)
         .collect();
 
-    Ok((input, ntuples_for_features))
+    Ok((rest, ntuples_for_features))
+}
+
+fn to_gpt_error(input: &str, error: Err<VerboseError<&str>>) -> GPTError {
+    match error {
+        Err::Error(err) | Err::Failure(err) => GPTError::ParseError(convert_error(input, err)),
+        Err::Incomplete(err) => GPTError::UnknownParseError(format!("{err:?}")),
+    }
+}
+
+/// Skips to the start of the next line beginning with `[`, where the next feature probably starts.
+fn skip_to_next_feature(input: &str) -> &str {
+    let mut rest = input;
+
+    while let Some(line_end) = rest.find('\n') {
+        rest = &rest[line_end + 1..];
+
+        if rest.trim_start_matches([' ', '\t', '\r']).starts_with('[') {
+            return rest;
+        }
+    }
+
+    // The end of the input, not a new `""`, so the offsets can still be calculated from it
+    &rest[rest.len()..]
+}
+
+/// A feature parsed by [`parse_features_with_spans`], with the byte range of its source in the input.
+///
+/// The span of a feature includes the whitespace and comments after it, so the spans of the features are contiguous.
+pub struct SpannedFeature {
+    pub span: Range<usize>,
+    pub feature: Result<ir::Feature>,
+}
+
+impl SpannedFeature {
+    /// Moves the feature and the spans inside of it with the function of the offsets
+    fn map_offsets(self, f: impl Fn(usize) -> usize) -> Self {
+        Self {
+            span: f(self.span.start)..f(self.span.end),
+            feature: self.feature.map(|feature| ir::Feature {
+                spans: feature.spans.map(|spans| spans.map(&f)),
+                ..feature
+            }),
+        }
+    }
+}
+
+/// Parses the features of `input`, which is a part of `source`, like the re-parsed region in [`reparse_features`].
+///
+/// The spans and the parse errors are relative to `source`, so they are the same as if the whole `source` was parsed.
+fn spanned_features_iter<'a>(
+    source: &'a str,
+    input: &'a str,
+) -> impl Iterator<Item = SpannedFeature> + 'a {
+    let mut remaining = input;
+    let mut is_done = false;
+    let offset = move |rest: &str| source.offset(rest);
+    let input_end = offset(input) + input.len();
+
+    std::iter::from_fn(move || {
+        if is_done {
+            return None;
+        }
+
+        let rest = match whitespace(remaining) {
+            Ok((rest, ())) => rest,
+            Err(err) => {
+                is_done = true;
+                return Some(SpannedFeature {
+                    span: offset(remaining)..input_end,
+                    feature: Err(to_gpt_error(source, err)),
+                });
+            }
+        };
+
+        if rest.is_empty() {
+            is_done = true;
+            return None;
+        }
+
+        // A single feature without the brackets
+        if !rest.starts_with('[') {
+            is_done = true;
+            return Some(SpannedFeature {
+                span: offset(rest)..input_end,
+                feature: all_consuming(token(feature_body))(rest)
+                    .map(|(_, feature_node)| {
+                        ir::ast_to_ir::traverse_feature_node(&feature_node, source)
+                    })
+                    .map_err(|err| to_gpt_error(source, err)),
+            });
+        }
+
+        let start = offset(rest);
+        let feature = match token(feature)(rest) {
+            Ok((rest, feature_node)) => {
+                remaining = rest;
+                Ok(ir::ast_to_ir::traverse_feature_node(&feature_node, source))
+            }
+            Err(err) => {
+                remaining = skip_to_next_feature(rest);
+                Err(to_gpt_error(source, err))
+            }
+        };
+
+        Some(SpannedFeature {
+            span: start..offset(remaining),
+            feature,
+        })
+    })
+}
+
+/// Parses the features one by one, each feature is parsed only when the iterator gets to it.
+///
+/// The parse errors are isolated to the feature they are in, after an error the parsing continues with the next feature,
+/// which is the next line starting with `[`. A single feature without brackets is parsed as a whole.
+pub fn parse_features_iter(input: &str) -> impl Iterator<Item = Result<ir::Feature>> + '_ {
+    spanned_features_iter(input, input).map(|spanned| spanned.feature)
+}
+
+/// Parses the features like [`parse_features_iter`], and remembers where each of them is in the input,
+/// so they can be re-parsed with [`reparse_features`] after an edit.
+pub fn parse_features_with_spans(input: &str) -> Vec<SpannedFeature> {
+    spanned_features_iter(input, input).collect()
+}
+
+/// Re-parses only the features touched by an edit, instead of the whole input, like after a keystroke in an editor.
+///
+/// The edit replaced the `edited` byte range of the previous input with `inserted_len` bytes, resulting in `new_input`.
+/// The features before the edit are kept, the ones after it are kept with their spans shifted,
+/// and the source of the touched features is parsed again. An edit between features re-parses the gap around it.
+///
+/// This is coarse: if the edit moves where the features begin, like removing a `]`,
+/// the result can differ from parsing `new_input` from scratch, until a later edit touches the affected features.
+pub fn reparse_features(
+    mut previous: Vec<SpannedFeature>,
+    edited: Range<usize>,
+    inserted_len: usize,
+    new_input: &str,
+) -> Vec<SpannedFeature> {
+    let touches_edit = |span: &Range<usize>| span.start <= edited.end && edited.start <= span.end;
+
+    let Some(first_touched) = previous
+        .iter()
+        .position(|spanned| touches_edit(&spanned.span) || spanned.span.start > edited.end)
+    else {
+        // The edit is after every feature
+        let region_start = previous.last().map_or(0, |spanned| spanned.span.end);
+        return reparse_region(previous, region_start, new_input.len(), new_input);
+    };
+    let after_touched = previous[first_touched..]
+        .iter()
+        .position(|spanned| !touches_edit(&spanned.span))
+        .map_or(previous.len(), |position| first_touched + position);
+
+    let region_start = if first_touched == after_touched {
+        // Only the gap before the next feature is touched
+        first_touched
+            .checked_sub(1)
+            .map_or(0, |previous_index| previous[previous_index].span.end)
+    } else {
+        previous[first_touched].span.start.min(edited.start)
+    };
+
+    // The features after the edit start after `edited.end`, so shifting them can't underflow
+    let shift = |offset: usize| offset + inserted_len - edited.len();
+    let region_end = previous
+        .get(after_touched)
+        .map_or(new_input.len(), |next| shift(next.span.start));
+
+    let after = previous
+        .split_off(after_touched)
+        .into_iter()
+        .map(|spanned| spanned.map_offsets(shift))
+        .collect::<Vec<_>>();
+    previous.truncate(first_touched);
+
+    let mut result = reparse_region(previous, region_start, region_end, new_input);
+    result.extend(after);
+    result
+}
+
+/// Parses the `region_start..region_end` part of the input, and appends the features in it to `before`.
+fn reparse_region(
+    mut before: Vec<SpannedFeature>,
+    region_start: usize,
+    region_end: usize,
+    new_input: &str,
+) -> Vec<SpannedFeature> {
+    let Some(region) = new_input.get(region_start..region_end) else {
+        return parse_features_with_spans(new_input);
+    };
+
+    before.extend(spanned_features_iter(new_input, region));
+    before
 }
 
 #[cfg(test)]
 mod tests {
+    use pretty_assertions::assert_eq;
+
+    use std::ops::Range;
+
+    use rstest::rstest;
+
+    use super::{
+        parse_features_iter, parse_features_with_spans, parse_gpt_to_features, reparse_features,
+        SpannedFeature,
+    };
+    use crate::{
+        dto::NTupleInput,
+        ir::{ir_to_ntuple::ir_to_ntuple, FeatureSpans},
+        prelude::GPTError,
+    };
+
+    #[test]
+    fn test_parse_features_iter() {
+        let input = r#"
+            [
+                var x: int
+                if(x < 0)
+            ]
+            [
+                var y: int
+                if(y <
+            ]
+            [
+                var z: bool
+                if(z == true)
+            ]
+        "#;
+
+        let results = parse_features_iter(input).collect::<Vec<_>>();
+
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[1], Err(GPTError::ParseError(_))));
+
+        let features = results
+            .into_iter()
+            .filter_map(Result::ok)
+            .map(|feature| ir_to_ntuple(&feature).0)
+            .collect::<Vec<_>>();
+        let (_, expected) = parse_gpt_to_features(
+            r#"
+            [
+                var x: int
+                if(x < 0)
+            ]
+            [
+                var z: bool
+                if(z == true)
+            ]
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(features, expected);
+    }
+
+    #[test]
+    fn test_parse_features_iter_without_brackets() {
+        let input = r#"
+            var x: int
+            if(x < 0)
+        "#;
+
+        let features = parse_features_iter(input)
+            .map(|feature| ir_to_ntuple(&feature.unwrap()).0)
+            .collect::<Vec<_>>();
+        let (_, expected) = parse_gpt_to_features(input).unwrap();
+
+        assert_eq!(features, expected);
+    }
+
+    #[test]
+    fn test_parse_features_iter_empty() {
+        assert_eq!(parse_features_iter("").count(), 0);
+        assert_eq!(parse_features_iter("  // nothing here\n").count(), 0);
+    }
+
+    const SPANNED_INPUT: &str = "\n[\n  var x: int\n  if(x < 0)\n]\n[\n  var y: int\n  if(y > 10)\n]\n[ var z: bool\n  if(z == true) ]\n";
+
+    type SpansAndNTuples = (
+        Range<usize>,
+        std::result::Result<(Vec<NTupleInput>, Option<FeatureSpans>), String>,
+    );
+
+    /// The parse errors are compared by their messages, which contain their line numbers
+    fn spans_and_ntuples(features: Vec<SpannedFeature>) -> Vec<SpansAndNTuples> {
+        features
+            .into_iter()
+            .map(|spanned| {
+                let ntuples = spanned
+                    .feature
+                    .map(|feature| (ir_to_ntuple(&feature).0, feature.spans))
+                    .map_err(|err| err.to_string());
+                (spanned.span, ntuples)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_features_with_spans() {
+        let features = parse_features_with_spans(SPANNED_INPUT);
+
+        let sources = features
+            .iter()
+            .map(|spanned| &SPANNED_INPUT[spanned.span.clone()])
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            sources,
+            vec![
+                "[\n  var x: int\n  if(x < 0)\n]\n",
+                "[\n  var y: int\n  if(y > 10)\n]\n",
+                "[ var z: bool\n  if(z == true) ]\n",
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::inside_feature("if(y > 10)", "if(y >= 100)")]
+    #[case::breaking_feature("if(y > 10)", "if(y >")]
+    #[case::first_feature("var x: int", "var x: num(0.1)")]
+    #[case::last_feature("z == true", "z == false")]
+    #[case::leading_whitespace("\n[\n  var x", "  // comment\n\n[\n  var x")]
+    #[case::new_feature_at_the_end(
+        "if(z == true) ]\n",
+        "if(z == true) ]\n[ var w: int\n if(w < 1) ]\n"
+    )]
+    #[case::removed_feature("[\n  var y: int\n  if(y > 10)\n]\n", "")]
+    #[case::new_feature_between("]\n[ var z", "]\n[ var w: int\n if(w < 1) ]\n[ var z")]
+    #[case::breaking_last_feature("if(z == true)", "if(z ==")]
+    fn test_reparse_features(#[case] old: &str, #[case] new: &str) {
+        let start = SPANNED_INPUT.find(old).unwrap();
+        let edited = start..start + old.len();
+        let new_input = SPANNED_INPUT.replacen(old, new, 1);
+
+        let result = reparse_features(
+            parse_features_with_spans(SPANNED_INPUT),
+            edited,
+            new.len(),
+            &new_input,
+        );
+
+        assert_eq!(
+            spans_and_ntuples(result),
+            spans_and_ntuples(parse_features_with_spans(&new_input))
+        );
+    }
+
+    #[test]
+    fn test_comments_and_blank_lines() {
+        let without_comments = r#"
+            var age: int
+            var vip: bool
+            if(age >= 18)
+            if(vip == true && age < 65)
+        "#;
+        let with_comments = r#"
+            # Customer data
+
+            var age: int // in years
+            var vip: bool # loyalty program member
+
+
+            # age must be adult
+            if(age >= 18)
+
+            // Seniors get a different discount
+            if(vip == true && age < 65) # not a senior
+            /* trailing
+               multi line comment */
+        "#;
+
+        let (_, expected) = parse_gpt_to_features(without_comments).unwrap();
+        let (remaining, result) = parse_gpt_to_features(with_comments).unwrap();
+
+        assert_eq!(remaining, "");
+        assert_eq!(result, expected);
+    }
+
     #[test]
     #[ignore = "todo"]
     fn test_root() {
@@ -1,4 +1,4 @@
-use std::{collections::HashSet, num::ParseIntError};
+use std::collections::HashSet;
 
 use nom::{
     branch::alt,
@@ -8,15 +8,15 @@ use nom::{
         is_alphabetic, is_alphanumeric,
         streaming::char,
     },
-    combinator::{complete, cut, fail, map, map_res, opt, recognize, value},
+    combinator::{complete, cut, fail, map, map_res, not, opt, recognize, value},
     error::context,
-    multi::many0,
-    sequence::tuple,
+    multi::{many0, separated_list1},
+    sequence::{terminated, tuple},
 };
 
 use super::{
     ast::{BinaryOp, EqOp, IntervalOp},
-    utils::token,
+    utils::{token, token_lit},
     IResult,
 };
 use crate::interval::{Boundary, MultiInterval};
@@ -31,14 +31,9 @@ pub fn float(input: &str) -> IResult<f32> {
     )(input)
 }
 
+/// A whole number, parsed directly as an `f32`, so it does not overflow like an `i32` would.
 pub fn int(input: &str) -> IResult<f32> {
-    map_res(
-        recognize(tuple((opt(char('-')), digit1))),
-        |x: &str| -> Result<f32, ParseIntError> {
-            let int = x.parse::<i32>()?;
-            Ok(int as f32)
-        },
-    )(input)
+    map_res(recognize(tuple((opt(char('-')), digit1))), str::parse)(input)
 }
 
 pub fn infinity(input: &str) -> IResult<f32> {
@@ -128,6 +123,19 @@ pub fn interval(input: &str) -> IResult<MultiInterval> {
     )(input)
 }
 
+/// The values of an enum, like `{active, closed}`. The values follow the same rules as the variable names.
+pub fn enum_values(input: &str) -> IResult<'_, Vec<&str>> {
+    context("enum values", |input| {
+        let (input, _) = token_lit("{")(input)?;
+        cut(|input| {
+            let (input, values) = separated_list1(token_lit(","), token(var_name))(input)?;
+            let (input, _) = tag("}")(input)?;
+
+            Ok((input, values))
+        })(input)
+    })(input)
+}
+
 fn parse_alphabetic(input: &str) -> IResult<char> {
     let (i, c) = anychar(input)?;
     if is_alphabetic(c as u8) {
@@ -147,7 +155,17 @@ fn parse_alphanumberic_or_underscore(input: &str) -> IResult<char> {
 }
 
 fn keywords() -> HashSet<&'static str> {
-    HashSet::from(["if", "else", "true", "false"])
+    HashSet::from(["if", "else", "true", "false", "AND", "OR"])
+}
+
+/// A keyword, which can't be directly followed by a character of a variable name, so `ORDER` is not the keyword `OR`
+pub fn keyword(keyword: &'static str) -> impl FnMut(&str) -> IResult<()> {
+    move |input| {
+        value(
+            (),
+            terminated(tag(keyword), not(parse_alphanumberic_or_underscore)),
+        )(input)
+    }
 }
 
 pub fn var_name(input: &str) -> IResult<&str> {
@@ -188,6 +206,7 @@ mod tests {
         assert_eq!(int("123.123"), Ok((".123", 123.0)));
         assert_eq!(int("123.123000000"), Ok((".123000000", 123.0)));
         assert_eq!(int("123."), Ok((".", 123.0)));
+        assert_eq!(int("-3000000000"), Ok(("", -3e9)));
         assert!(int("other").is_err());
     }
 
@@ -251,6 +270,19 @@ mod tests {
         assert!(interval_op("other").is_err());
     }
 
+    #[test]
+    fn test_enum_values() {
+        assert_eq!(enum_values("{a}"), Ok(("", vec!["a"])));
+        assert_eq!(
+            enum_values("{ active,closed , on_hold } if"),
+            Ok((" if", vec!["active", "closed", "on_hold"]))
+        );
+        assert!(enum_values("{}").is_err());
+        assert!(enum_values("{a, b").is_err());
+        assert!(enum_values("{a, 1}").is_err());
+        assert!(enum_values("[a, b]").is_err());
+    }
+
     #[test]
     fn test_binary_op() {
         assert_eq!(binary_op("<="), Ok(("", BinaryOp::LessThanEqualTo)));
@@ -316,5 +348,18 @@ mod tests {
         assert!(var_name("else").is_err());
         assert!(var_name("true").is_err());
         assert!(var_name("false").is_err());
+        assert!(var_name("AND").is_err());
+        assert!(var_name("OR").is_err());
+        assert_eq!(var_name("ORDER"), Ok(("", "ORDER")));
+    }
+
+    #[test]
+    fn test_keyword() {
+        assert_eq!(keyword("OR")("OR x"), Ok((" x", ())));
+        assert_eq!(keyword("OR")("OR(x"), Ok(("(x", ())));
+        assert_eq!(keyword("AND")("AND"), Ok(("", ())));
+        assert!(keyword("OR")("ORDER").is_err());
+        assert!(keyword("OR")("OR_x").is_err());
+        assert!(keyword("OR")("or").is_err());
     }
 }
@@ -2,7 +2,7 @@ use nom::{
     branch::alt,
     bytes::complete::{tag, take_until},
     character::complete::char,
-    combinator::{complete, cut, value},
+    combinator::{all_consuming, complete, cut, value},
     error::context,
     multi::many0,
     sequence::{delimited, terminated, tuple},
@@ -11,13 +11,13 @@ use nom::{
 use super::IResult;
 
 /// Parses a line cumment until the end of line, including \n.
-/// Line comment starts with //
+/// Line comment starts with // or #
 fn line_comment(input: &str) -> IResult<()> {
     context(
         "line comment",
         value(
             (), // Output is thrown away.
-            tuple((tag("//"), cut(take_until("\n")), tag("\n"))),
+            tuple((alt((tag("//"), tag("#"))), cut(take_until("\n")), tag("\n"))),
         ),
     )(input)
 }
@@ -53,6 +53,31 @@ pub fn token_lit(literal: &'static str) -> impl FnMut(&str) -> IResult<()> {
     move |input| value((), token(tag(literal)))(input)
 }
 
+/// The source parsed from `input` until `rest`, without the whitespace and comments after it.
+pub fn source_until<'a>(input: &'a str, rest: &str) -> &'a str {
+    let consumed = &input[..input.len() - rest.len()];
+
+    // The first position from where only whitespace follows, a comment can't be cut in half
+    let end = consumed
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .find(|&idx| all_consuming(whitespace)(&consumed[idx..]).is_ok())
+        .unwrap_or(consumed.len());
+
+    &consumed[..end]
+}
+
+/// Runs the parser, and also returns the source it parsed, see [`source_until`].
+pub fn with_source<'a, T>(
+    mut parser: impl FnMut(&'a str) -> IResult<T>,
+) -> impl FnMut(&'a str) -> IResult<(&'a str, T)> {
+    move |input| {
+        let (rest, output) = parser(input)?;
+
+        Ok((rest, (source_until(input, rest), output)))
+    }
+}
+
 pub fn parenthesized<'a, T>(
     mut parser: impl FnMut(&'a str) -> IResult<T>,
 ) -> impl FnMut(&'a str) -> IResult<T> {
@@ -75,6 +100,11 @@ mod tests {
     #[case("// asd \r\n foo", Some(" foo"))]
     #[case("//\n", Some(""))]
     #[case("//\nfoo", Some("foo"))]
+    #[case("# asd \nfoo", Some("foo"))]
+    #[case("#asd\r\n foo", Some(" foo"))]
+    #[case("#\n", Some(""))]
+    #[case("# asd // qwe \nfoo", Some("foo"))]
+    #[case("# asd", None)]
     #[case("", None)]
     #[case("foo", None)]
     fn test_line_comment(#[case] input: &str, #[case] expected: Option<&str>) {
@@ -117,10 +147,24 @@ mod tests {
     #[case("/* asd qwe \n \t *///asd\n/*asd*/", Some(""))]
     #[case("/*asd*/ = 8", Some("= 8"))]
     #[case("// asd /* \n foo", Some("foo"))]
+    #[case("\n\n# asd\n\n  # qwe\n\t\n", Some(""))]
+    #[case("# asd\n// qwe\n/* zxc */ foo", Some("foo"))]
     fn test_whitespace(#[case] input: &str, #[case] expected: Option<&str>) {
         match expected {
             Some(expected) => assert_eq!(whitespace(input), Ok((expected, ()))),
             None => assert!(whitespace(input).is_err()),
         }
     }
+
+    #[rstest]
+    #[case("foo bar", "foo", "bar")]
+    #[case("foo  // comment\n/* and another */ bar", "foo", "bar")]
+    #[case("foo", "foo", "")]
+    #[case("foo # not //closed \n", "foo", "")]
+    fn test_with_source(#[case] input: &str, #[case] source: &str, #[case] rest: &str) {
+        assert_eq!(
+            with_source(token(tag("foo")))(input),
+            Ok((rest, (source, "foo")))
+        );
+    }
 }
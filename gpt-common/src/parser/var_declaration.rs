@@ -1,15 +1,17 @@
 use nom::{
     branch::alt,
-    combinator::{complete, cut},
+    character::complete::u8,
+    combinator::{complete, cut, fail, map, opt, verify},
     error::context,
 };
 
 use super::{
-    ast::{Type, VarNode},
-    primitives::{float, var_name},
-    utils::{token, token_lit},
+    ast::{Literal, Type, VarNode},
+    primitives::{boolean, enum_values, float, interval, keyword, number, var_name},
+    utils::{token, token_lit, with_source},
     IResult,
 };
+use crate::interval::Interval;
 
 fn parse_float_type(input: &str) -> IResult<Type> {
     let (input, _) = token_lit("num")(input)?;
@@ -22,6 +24,35 @@ fn parse_float_type(input: &str) -> IResult<Type> {
     })(input)
 }
 
+/// An `f32` has about 7 significant decimal digits, the precision of more places would be rounded away
+const MAX_DECIMAL_PLACES: u8 = 7;
+
+/// Parses the number of decimal places, like `decimal(2)`, at most [`MAX_DECIMAL_PLACES`]
+fn parse_decimal_type(input: &str) -> IResult<'_, Type> {
+    let (input, _) = token_lit("decimal")(input)?;
+    let (input, _) = token_lit("(")(input)?;
+    cut(|input| {
+        let (input, places) = context(
+            "decimal places",
+            verify(token(u8), |places| *places <= MAX_DECIMAL_PLACES),
+        )(input)?;
+        let (input, _) = token_lit(")")(input)?;
+
+        Ok((input, Type::Decimal { places }))
+    })(input)
+}
+
+/// Parses the declared values of an enum, like `enum {active, closed}`
+fn parse_enum_type(input: &str) -> IResult<'_, Type> {
+    let (input, _) = token(keyword("enum"))(input)?;
+    let (input, values) = cut(token(enum_values))(input)?;
+
+    Ok((
+        input,
+        Type::Enum(values.into_iter().map(str::to_owned).collect()),
+    ))
+}
+
 fn parse_bool_type(input: &str) -> IResult<Type> {
     let (input, _) = token_lit("bool")(input)?;
 
@@ -48,25 +79,255 @@ fn parse_type(input: &str) -> IResult<Type> {
             parse_int_type,
             complete(parse_float_type),
             parse_simple_num_type,
+            parse_decimal_type,
+            parse_enum_type,
         )),
     )(input)
 }
 
+/// Parses the domain after the type, like `in [0, 150]`. Only numeric variables can have a domain.
+fn parse_domain(var_type: Type) -> impl FnMut(&str) -> IResult<Interval> {
+    move |input| {
+        context("domain", |input| {
+            let (input, _) = token(keyword("in"))(input)?;
+            cut(|input| match &var_type {
+                Type::Bool | Type::Enum(_) => fail(input),
+                // `interval` always parses a single interval
+                Type::Integer | Type::Float { .. } | Type::Decimal { .. } => {
+                    map(token(interval), |domain| domain.intervals[0])(input)
+                }
+            })(input)
+        })(input)
+    }
+}
+
+/// Parses the default value after the type, like `= 7` or `= true`. The literal has to match the type of the variable,
+/// so the default of an `int` has to be a whole number.
+fn parse_default(var_type: Type) -> impl FnMut(&str) -> IResult<Literal> {
+    move |input| {
+        context("default value", |input| {
+            let (input, _) = token_lit("=")(input)?;
+            cut(|input| match &var_type {
+                Type::Bool => map(token(boolean), Literal::Bool)(input),
+                Type::Enum(_) => fail(input),
+                Type::Integer => map(
+                    verify(token(number), |default: &f32| default.fract() == 0.0),
+                    Literal::Number,
+                )(input),
+                Type::Float { .. } | Type::Decimal { .. } => {
+                    map(token(number), Literal::Number)(input)
+                }
+            })(input)
+        })(input)
+    }
+}
+
 pub fn var_declaration(input: &str) -> IResult<VarNode> {
     context("var declaration", |input| {
-        let (input, _) = token_lit("var")(input)?;
-        cut(|input| {
-            let (input, var_name) = token(var_name)(input)?;
-            let (input, _) = token_lit(":")(input)?;
-            let (input, var_type) = token(parse_type)(input)?;
+        let (input, (source, (var_name, var_type, domain, default))) = with_source(|input| {
+            let (input, _) = token_lit("var")(input)?;
+            cut(|input| {
+                let (input, var_name) = token(var_name)(input)?;
+                let (input, _) = token_lit(":")(input)?;
+                let (input, var_type) = token(parse_type)(input)?;
+                let (input, domain) = opt(parse_domain(var_type.clone()))(input)?;
+                let (input, default) = opt(parse_default(var_type.clone()))(input)?;
 
-            Ok((input, VarNode { var_name, var_type }))
-        })(input)
+                Ok((input, (var_name, var_type, domain, default)))
+            })(input)
+        })(input)?;
+
+        Ok((
+            input,
+            VarNode {
+                source,
+                var_name,
+                var_type,
+                domain,
+                default,
+            },
+        ))
     })(input)
 }
 
 #[cfg(test)]
 mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::interval::test::int;
+
+    #[test]
+    fn test_var_declaration_with_default() {
+        assert_eq!(
+            var_declaration("var x: int = 7"),
+            Ok((
+                "",
+                VarNode {
+                    source: "var x: int = 7",
+                    var_name: "x",
+                    var_type: Type::Integer,
+                    domain: None,
+                    default: Some(Literal::Number(7.0)),
+                }
+            ))
+        );
+        assert_eq!(
+            var_declaration("var price: num(0.1)=-2.5 if"),
+            Ok((
+                "if",
+                VarNode {
+                    source: "var price: num(0.1)=-2.5",
+                    var_name: "price",
+                    var_type: Type::Float { precision: 0.1 },
+                    domain: None,
+                    default: Some(Literal::Number(-2.5)),
+                }
+            ))
+        );
+        assert_eq!(
+            var_declaration("var flag: bool = false"),
+            Ok((
+                "",
+                VarNode {
+                    source: "var flag: bool = false",
+                    var_name: "flag",
+                    var_type: Type::Bool,
+                    domain: None,
+                    default: Some(Literal::Bool(false)),
+                }
+            ))
+        );
+        assert_eq!(
+            var_declaration("var flag: bool"),
+            Ok((
+                "",
+                VarNode {
+                    source: "var flag: bool",
+                    var_name: "flag",
+                    var_type: Type::Bool,
+                    domain: None,
+                    default: None,
+                }
+            ))
+        );
+        assert!(var_declaration("var flag: bool = 7").is_err());
+        assert!(var_declaration("var x: int = true").is_err());
+        assert!(var_declaration("var x: int =").is_err());
+        assert!(var_declaration("var x: int = 2.5").is_err());
+    }
+
+    #[test]
+    fn test_var_declaration_with_domain() {
+        assert_eq!(
+            var_declaration("var age: int in [0, 150]"),
+            Ok((
+                "",
+                VarNode {
+                    source: "var age: int in [0, 150]",
+                    var_name: "age",
+                    var_type: Type::Integer,
+                    domain: Some(int("[0, 150]")),
+                    default: None,
+                }
+            ))
+        );
+        assert_eq!(
+            var_declaration("var price: num(0.1) in (0, Inf) = 2.5 if"),
+            Ok((
+                "if",
+                VarNode {
+                    source: "var price: num(0.1) in (0, Inf) = 2.5",
+                    var_name: "price",
+                    var_type: Type::Float { precision: 0.1 },
+                    domain: Some(int("(0, Inf)")),
+                    default: Some(Literal::Number(2.5)),
+                }
+            ))
+        );
+        assert_eq!(
+            var_declaration("var index: int if"),
+            Ok((
+                "if",
+                VarNode {
+                    source: "var index: int",
+                    var_name: "index",
+                    var_type: Type::Integer,
+                    domain: None,
+                    default: None,
+                }
+            ))
+        );
+        assert!(var_declaration("var flag: bool in [0, 1]").is_err());
+        assert!(var_declaration("var x: int in").is_err());
+        assert!(var_declaration("var x: int in [10, 0]").is_err());
+    }
+
+    #[test]
+    fn test_parse_decimal_type() {
+        assert_eq!(
+            parse_decimal_type("decimal(2) in"),
+            Ok(("in", Type::Decimal { places: 2 }))
+        );
+        assert_eq!(
+            parse_decimal_type("decimal ( 0 )"),
+            Ok(("", Type::Decimal { places: 0 }))
+        );
+        assert_eq!(Type::Decimal { places: 2 }.get_precision(), Some(0.01));
+        assert_eq!(Type::Decimal { places: 0 }.get_precision(), Some(1.0));
+        assert!(parse_decimal_type("decimal(0.5)").is_err());
+        assert!(parse_decimal_type("decimal(-1)").is_err());
+        assert!(parse_decimal_type("decimal").is_err());
+        assert_eq!(
+            parse_decimal_type("decimal(7)"),
+            Ok(("", Type::Decimal { places: 7 }))
+        );
+        assert!(parse_decimal_type("decimal(8)").is_err());
+        assert!(parse_decimal_type("decimal(60)").is_err());
+        assert_eq!(
+            var_declaration("var price: decimal(2) in [0, 100] = 9.99"),
+            Ok((
+                "",
+                VarNode {
+                    source: "var price: decimal(2) in [0, 100] = 9.99",
+                    var_name: "price",
+                    var_type: Type::Decimal { places: 2 },
+                    domain: Some(int("[0, 100]")),
+                    default: Some(Literal::Number(9.99)),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_enum_type() {
+        assert_eq!(
+            parse_enum_type("enum {active, closed} if"),
+            Ok((
+                "if",
+                Type::Enum(vec!["active".to_owned(), "closed".to_owned()])
+            ))
+        );
+        assert_eq!(Type::Enum(vec!["a".to_owned()]).get_precision(), None);
+        assert!(parse_enum_type("enum").is_err());
+        assert!(parse_enum_type("enumerated {a}").is_err());
+        assert_eq!(
+            var_declaration("var status: enum {active, closed}"),
+            Ok((
+                "",
+                VarNode {
+                    source: "var status: enum {active, closed}",
+                    var_name: "status",
+                    var_type: Type::Enum(vec!["active".to_owned(), "closed".to_owned()]),
+                    domain: None,
+                    default: None,
+                }
+            ))
+        );
+        assert!(var_declaration("var status: enum {active} in [0, 1]").is_err());
+        assert!(var_declaration("var status: enum {active} = active").is_err());
+    }
+
     #[test]
     #[ignore = "todo"]
     fn test_parse_float_type() {
@@ -11,6 +11,12 @@ pub enum GPTError {
     #[error("Interval error in logic: {0}")]
     IntervalError(String),
 
+    #[error("Type error: {0}")]
+    TypeError(String),
+
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+
     #[error("Unknown error")]
     Unknown,
 }
@@ -0,0 +1,193 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    dto::{Input, NTupleInput},
+    interval::{MultiInterval, Unionable},
+};
+
+/// The values covered by the already picked tuples, for each variable
+#[derive(Default)]
+struct Coverage {
+    intervals: HashMap<String, MultiInterval>,
+    values: HashMap<String, HashSet<String>>,
+}
+
+/// The bool and enum values of the input, as strings, so they can be stored together
+fn discrete_values(input: &Input) -> Vec<String> {
+    match input {
+        Input::Bool(bool_dto) => vec![bool_dto.bool_val.to_string()],
+        Input::Enum(enum_dto) => enum_dto.values.clone(),
+        Input::Interval(_) | Input::Any => Vec::new(),
+    }
+}
+
+impl Coverage {
+    /// The part of the interval which is not covered yet
+    fn uncovered(&self, var_name: &str, interval: &MultiInterval) -> MultiInterval {
+        match self.intervals.get(var_name) {
+            Some(covered) => interval.difference(covered),
+            None => interval.clone(),
+        }
+    }
+
+    /// The total width of the newly covered intervals, and the count of the newly covered values,
+    /// which are the bool and enum values, and the intervals without a width, like `[5, 5]`.
+    fn gain(&self, tuple: &NTupleInput) -> (f32, usize) {
+        tuple
+            .inputs
+            .iter()
+            .fold((0.0, 0), |(width, values), (var_name, input)| match input {
+                Input::Interval(interval_dto) => {
+                    let uncovered = self.uncovered(var_name, &interval_dto.interval);
+                    let uncovered_width = uncovered.total_width();
+
+                    (
+                        width + uncovered_width,
+                        values + usize::from(uncovered_width == 0.0 && !uncovered.is_empty()),
+                    )
+                }
+                _ => {
+                    let covered = self.values.get(var_name);
+                    let new_values = discrete_values(input)
+                        .iter()
+                        .filter(|value| !covered.is_some_and(|covered| covered.contains(*value)))
+                        .count();
+
+                    (width, values + new_values)
+                }
+            })
+    }
+
+    fn add(&mut self, tuple: &NTupleInput) {
+        for (var_name, input) in &tuple.inputs {
+            match input {
+                Input::Interval(interval_dto) => {
+                    let covered = self
+                        .intervals
+                        .entry(var_name.clone())
+                        .or_insert_with(MultiInterval::new_empty);
+                    *covered = covered.union(&interval_dto.interval);
+                }
+                _ => self
+                    .values
+                    .entry(var_name.clone())
+                    .or_default()
+                    .extend(discrete_values(input)),
+            }
+        }
+    }
+}
+
+/// Orders the tuples so every prefix covers as much of the domains as possible, for when only the first few tests can be run.
+///
+/// It is a greedy set cover: the next tuple is always the one adding the widest uncovered intervals to the already picked ones,
+/// summed over the variables. Ties are broken by the count of the newly covered bool and enum values and single points,
+/// then by the original order.
+pub fn prioritize(tuples: Vec<NTupleInput>) -> Vec<NTupleInput> {
+    let mut remaining = tuples;
+    let mut prioritized = Vec::with_capacity(remaining.len());
+    let mut coverage = Coverage::default();
+
+    while !remaining.is_empty() {
+        let (best_idx, _) = remaining
+            .iter()
+            .map(|tuple| coverage.gain(tuple))
+            .enumerate()
+            .fold((0, (-1.0, 0)), |best, (idx, gain)| {
+                if gain > best.1 {
+                    (idx, gain)
+                } else {
+                    best
+                }
+            });
+
+        let tuple = remaining.remove(best_idx);
+        coverage.add(&tuple);
+        prioritized.push(tuple);
+    }
+
+    prioritized
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::prioritize;
+    use crate::dto::{
+        tests::{bool_input, create_ntuple_input, interval_input},
+        NTupleInput,
+    };
+
+    fn x_tuple(interval: &str) -> NTupleInput {
+        create_ntuple_input(vec![("x", interval_input(interval))])
+    }
+
+    #[test]
+    fn test_prioritize_widest_new_coverage_first() {
+        let tuples = vec![
+            x_tuple("[0, 5]"),
+            x_tuple("[0, 10]"),
+            x_tuple("[5, 5]"),
+            x_tuple("[20, 100]"),
+            x_tuple("[8, 30]"),
+        ];
+
+        assert_eq!(
+            prioritize(tuples),
+            vec![
+                x_tuple("[20, 100]"),
+                x_tuple("[8, 30]"),
+                x_tuple("[0, 10]"),
+                x_tuple("[0, 5]"),
+                x_tuple("[5, 5]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prioritize_across_variables() {
+        let tuple = |x: &str, y: &str, flag: bool| {
+            create_ntuple_input(vec![
+                ("x", interval_input(x)),
+                ("y", interval_input(y)),
+                ("flag", bool_input(flag)),
+            ])
+        };
+        let tuples = vec![
+            tuple("[0, 10]", "[0, 10]", true),
+            tuple("[0, 10]", "[0, 10]", false),
+            tuple("[0, 30]", "[0, 5]", true),
+            tuple("[10, 20]", "[0, 20]", true),
+        ];
+
+        assert_eq!(
+            prioritize(tuples),
+            vec![
+                tuple("[0, 30]", "[0, 5]", true),
+                tuple("[10, 20]", "[0, 20]", true),
+                tuple("[0, 10]", "[0, 10]", false),
+                tuple("[0, 10]", "[0, 10]", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prioritize_unbounded() {
+        let tuples = vec![
+            x_tuple("[0, 10]"),
+            x_tuple("(-Inf, 0)"),
+            x_tuple("(-Inf, 5)"),
+        ];
+
+        assert_eq!(
+            prioritize(tuples),
+            vec![
+                x_tuple("(-Inf, 0)"),
+                x_tuple("[0, 10]"),
+                x_tuple("(-Inf, 5)")
+            ]
+        );
+        assert_eq!(prioritize(Vec::new()), Vec::new());
+    }
+}
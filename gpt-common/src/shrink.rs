@@ -0,0 +1,228 @@
+use crate::{
+    dto::{BoolDTO, BoolExpression, Input, IntervalDTO, NTupleInput},
+    interval::{Interval, MultiInterval},
+    util::snap_to_precision,
+};
+
+fn with_input(tuple: &NTupleInput, var_name: &str, input: Input) -> NTupleInput {
+    let mut tuple = tuple.clone();
+    tuple.inputs.insert(var_name.to_owned(), input);
+    tuple
+}
+
+fn point_input(interval_dto: &IntervalDTO, value: f32) -> Input {
+    Input::Interval(IntervalDTO {
+        interval: MultiInterval::new_closed_point(value),
+        ..interval_dto.clone()
+    })
+}
+
+const fn flipped_input(bool_dto: &BoolDTO) -> Input {
+    Input::Bool(BoolDTO {
+        expression: match bool_dto.expression {
+            BoolExpression::IsTrue => BoolExpression::IsFalse,
+            BoolExpression::IsFalse => BoolExpression::IsTrue,
+        },
+        bool_val: !bool_dto.bool_val,
+        is_constant: bool_dto.is_constant,
+    })
+}
+
+/// The finite boundary of the interval closest to `value`, stepped inside for an open boundary. On a tie the lo wins.
+fn nearest_boundary(interval: &Interval, value: f32, precision: f32) -> Option<f32> {
+    [interval.lo, interval.hi]
+        .into_iter()
+        .filter(|boundary| boundary.is_finite())
+        .filter_map(|boundary| interval.nearest_inside(boundary, precision))
+        .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()))
+}
+
+/// The value of the variable closest to the nearest boundary, which still fails.
+/// Returns `None` if the tuple doesn't fail with any value tried.
+fn shrink_interval(
+    tuple: &NTupleInput,
+    var_name: &str,
+    interval_dto: &IntervalDTO,
+    still_fails: &impl Fn(&NTupleInput) -> bool,
+) -> Option<f32> {
+    let precision = interval_dto.precision;
+    let fails_at = |value: f32| {
+        still_fails(&with_input(
+            tuple,
+            var_name,
+            point_input(interval_dto, value),
+        ))
+    };
+
+    // Integer inputs are rounded first, so every tried value is a whole number
+    let interval = *interval_dto.rounded_interval().intervals.first()?;
+
+    // Starting from the middle of the interval, or from a boundary if the middle doesn't fail,
+    // because the failure might only happen close to one of the boundaries
+    let boundaries = [interval.lo, interval.hi]
+        .into_iter()
+        .filter(|boundary| boundary.is_finite())
+        .filter_map(|boundary| interval.nearest_inside(boundary, precision));
+    let mut failing = interval
+        .middle_value(precision)
+        .into_iter()
+        .chain(boundaries)
+        .find(|value| fails_at(*value))?;
+    let Some(mut passing) = nearest_boundary(&interval, failing, precision) else {
+        return Some(failing);
+    };
+
+    if fails_at(passing) {
+        return Some(passing);
+    }
+
+    // Bisecting between the boundary which passes, and the value which fails, until they are neighbours
+    loop {
+        let middle = snap_to_precision(passing + (failing / 2.0 - passing / 2.0), precision);
+        if (failing - passing).abs() <= precision || middle == passing || middle == failing {
+            break;
+        }
+
+        if fails_at(middle) {
+            failing = middle;
+        } else {
+            passing = middle;
+        }
+    }
+
+    Some(failing)
+}
+
+/// Minimizes a failing tuple, quickcheck-style, so it's easier to see which boundary causes the failure.
+///
+/// The variables are shrunk one by one, in the order of their names.
+/// Every numeric variable is replaced by the single value closest to the nearest boundary of its interval,
+/// for which `still_fails` holds, like `x ∈ [0, 1000]` becoming `x ∈ [300, 300]` if the failure starts at `300`.
+/// The search starts from the middle of the interval, and if that doesn't fail, from the boundaries.
+/// Every bool is flipped if the tuple still fails with the flipped value, which means the bool doesn't matter.
+/// The constant inputs and the variables where no tried value fails are left as they are.
+pub fn shrink(tuple: &NTupleInput, still_fails: impl Fn(&NTupleInput) -> bool) -> NTupleInput {
+    let mut var_names = tuple.inputs.keys().cloned().collect::<Vec<_>>();
+    var_names.sort_unstable();
+
+    var_names
+        .into_iter()
+        .fold(tuple.clone(), |shrunk, var_name| {
+            let candidate = match &shrunk.inputs[&var_name] {
+                Input::Interval(interval_dto) if !interval_dto.is_constant => {
+                    shrink_interval(&shrunk, &var_name, interval_dto, &still_fails).map(|value| {
+                        with_input(&shrunk, &var_name, point_input(interval_dto, value))
+                    })
+                }
+                Input::Bool(bool_dto) if !bool_dto.is_constant => {
+                    Some(with_input(&shrunk, &var_name, flipped_input(bool_dto)))
+                        .filter(|flipped| still_fails(flipped))
+                }
+                _ => None,
+            };
+
+            candidate.unwrap_or(shrunk)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::shrink;
+    use crate::dto::{
+        tests::{bool_input, create_ntuple_input, precise_interval_input},
+        NTupleInput, Value,
+    };
+
+    fn number(tuple: &NTupleInput, var_name: &str) -> f32 {
+        match tuple.inputs[var_name].pick_value(1.0) {
+            Some(Value::Number(num)) => num,
+            value => panic!("{var_name} should be a number, but it is {value:?}"),
+        }
+    }
+
+    fn boolean(tuple: &NTupleInput, var_name: &str) -> bool {
+        match tuple.inputs[var_name].pick_value(1.0) {
+            Some(Value::Bool(bool_val)) => bool_val,
+            value => panic!("{var_name} should be a bool, but it is {value:?}"),
+        }
+    }
+
+    #[test]
+    fn test_shrink_to_failure_boundary() {
+        let tuple =
+            create_ntuple_input(vec![("x", precise_interval_input("[0, 1000]", 1.0, true))]);
+
+        let result = shrink(&tuple, |tuple| number(tuple, "x") >= 300.0);
+
+        assert_eq!(
+            result,
+            create_ntuple_input(vec![("x", precise_interval_input("[300, 300]", 1.0, true))])
+        );
+    }
+
+    #[test]
+    fn test_shrink_to_interval_boundary() {
+        let tuple = create_ntuple_input(vec![
+            ("x", precise_interval_input("(0, 1000]", 0.1, false)),
+            ("y", precise_interval_input("[-50, 10]", 1.0, true)),
+            ("flag", bool_input(true)),
+        ]);
+
+        let result = shrink(&tuple, |_| true);
+
+        assert_eq!(
+            result,
+            create_ntuple_input(vec![
+                ("x", precise_interval_input("[0.1, 0.1]", 0.1, false)),
+                ("y", precise_interval_input("[-50, -50]", 1.0, true)),
+                ("flag", bool_input(false)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_shrink_keeps_what_matters() {
+        let tuple = create_ntuple_input(vec![
+            ("x", precise_interval_input("[0, 1000]", 1.0, true)),
+            ("flag", bool_input(true)),
+        ]);
+
+        // Fails only for big values, so the middle value of 500 doesn't fail, but the boundary of 1000 does
+        let result = shrink(&tuple, |tuple| {
+            boolean(tuple, "flag") && number(tuple, "x") > 900.0
+        });
+
+        assert_eq!(
+            result,
+            create_ntuple_input(vec![
+                ("x", precise_interval_input("[1000, 1000]", 1.0, true)),
+                ("flag", bool_input(true)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_shrink_from_lo_boundary() {
+        let tuple =
+            create_ntuple_input(vec![("x", precise_interval_input("[0, 1000]", 1.0, true))]);
+
+        let result = shrink(&tuple, |tuple| number(tuple, "x") < 10.0);
+
+        assert_eq!(
+            result,
+            create_ntuple_input(vec![("x", precise_interval_input("[0, 0]", 1.0, true))])
+        );
+    }
+
+    #[test]
+    fn test_shrink_without_failing_value() {
+        let tuple =
+            create_ntuple_input(vec![("x", precise_interval_input("[0, 1000]", 1.0, true))]);
+
+        let result = shrink(&tuple, |tuple| number(tuple, "x") == 700.0);
+
+        assert_eq!(result, tuple);
+    }
+}
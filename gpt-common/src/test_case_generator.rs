@@ -1,17 +1,27 @@
 use std::collections::HashMap;
 
+use itertools::Itertools;
+
 use crate::{
     bva::Bva,
     dto::{
-        BoolDTO, BoolExpression, Input, IntervalDTO, NTupleInput, NTupleOutput,
-        NTupleSingleInterval, Output,
+        BoolDTO, BoolExpression, BoundaryPointKind, EnumDTO, Input, IntervalDTO, NTupleInput,
+        NTupleOutput, NTupleSingleInterval, Output, Value,
+    },
+    interval::{Intersectable, Interval, IntervalError, MultiInterval},
+    ir::{
+        ir_to_ntuple::{ir_to_ntuple, ir_to_ntuples_with_relations},
+        Feature, RelationCondition, Variable,
     },
-    interval::{IntervalError, MultiInterval},
-    util::UniquesVec,
+    util::{snap_to_precision, SplitMix64, UniquesVec},
 };
 
+/// The test cases of every feature, the tuples are created with their relations by `ir_to_ntuples_with_relations`.
+///
+/// The test cases violating the relations of their tuple, like `start <= end`, are dropped,
+/// and the values are kept in the domains of the variables, see [`clamp_to_domains`].
 pub fn generate_test_cases_for_multiple_features(
-    features: &Vec<Vec<NTupleInput>>,
+    features: &[Feature],
 ) -> Result<Vec<NTupleSingleInterval>, IntervalError> {
     let mut res = Vec::new();
     for feature in features {
@@ -21,17 +31,57 @@ pub fn generate_test_cases_for_multiple_features(
     Ok(res)
 }
 
-fn generate_test_cases_for_feature(n_tuples: &[NTupleInput]) -> Vec<NTupleSingleInterval> {
+fn generate_test_cases_for_feature(feature: &Feature) -> Vec<NTupleSingleInterval> {
+    let n_tuples = ir_to_ntuples_with_relations(feature);
+    log::warn!("Inputs: {:#?}", n_tuples);
+
     let mut result_test_cases = Vec::new();
-    for ntuple in n_tuples {
-        let mut test_cases = generate_test_cases_for_inputs(&ntuple.clone());
+    for (ntuple, relations) in &n_tuples {
+        let mut test_cases = clamp_to_domains(generate_test_cases_for_inputs(ntuple), feature);
+        test_cases.retain(|test_case| {
+            satisfies_relations(&test_case_to_ntuple(ntuple, test_case), relations)
+        });
         result_test_cases.append(&mut test_cases);
     }
 
     result_test_cases
 }
 
+/// Rounds the intervals of integer variables to whole numbers, so no fractional test values are generated for them.
+fn round_integer_inputs(ntuple: &NTupleInput) -> NTupleInput {
+    let inputs = ntuple
+        .inputs
+        .iter()
+        .map(|(var_name, input)| {
+            let input = match input {
+                Input::Interval(dto) if dto.is_integer => Input::Interval(IntervalDTO {
+                    interval: dto.rounded_interval(),
+                    ..dto.clone()
+                }),
+                input => input.clone(),
+            };
+
+            (var_name.clone(), input)
+        })
+        .collect();
+
+    NTupleInput { inputs }
+}
+
+/// Leaves out the variables which can be anything, because a missing variable intersects with every value in the graph reduction.
+fn remove_wildcard_inputs(ntuple: &NTupleInput) -> NTupleInput {
+    let inputs = ntuple
+        .inputs
+        .iter()
+        .filter(|(_, input)| **input != Input::Any)
+        .map(|(var_name, input)| (var_name.clone(), input.clone()))
+        .collect();
+
+    NTupleInput { inputs }
+}
+
 fn generate_test_cases_for_inputs(inputs: &NTupleInput) -> Vec<NTupleSingleInterval> {
+    let inputs = &round_integer_inputs(&remove_wildcard_inputs(inputs));
     let mut modified_inputs = calc_in_on_inin(inputs);
     modified_inputs.extend(off_out(inputs));
 
@@ -43,12 +93,44 @@ fn generate_test_cases_for_inputs(inputs: &NTupleInput) -> Vec<NTupleSingleInter
         .collect()
 }
 
+/// Restricts the intervals of the test cases to the domains of their variables,
+/// and leaves out the test cases which have a variable with no value in its domain.
+///
+/// The off and out points can step over the domain, like `-1` for `age < 18` with `age in [0, 150]`.
+fn clamp_to_domains(
+    test_cases: Vec<NTupleSingleInterval>,
+    feature: &Feature,
+) -> Vec<NTupleSingleInterval> {
+    test_cases
+        .into_iter()
+        .filter_map(|test_case| {
+            test_case
+                .into_iter()
+                .map(|(var_name, output)| {
+                    let domain = feature
+                        .variable(&var_name)
+                        .and_then(|variable| variable.domain);
+                    let output = match (output, domain) {
+                        (Output::Interval(interval), Some(domain)) => {
+                            Output::Interval(interval.intersect(&domain)?)
+                        }
+                        (output, _) => output,
+                    };
+
+                    Some((var_name, output))
+                })
+                .collect()
+        })
+        .collect::<Vec<_>>()
+        .uniques()
+}
+
 /// Creates the cartesian product of all multiintervals in the `NTuple`.
 /// If a multiinterval would have multiple intervals, it creates an `NTuple` with all the possible single interval combinations.
 fn ntuple_multi_cartesian_product(ntuple: &NTupleOutput) -> Vec<NTupleSingleInterval> {
     if ntuple.outputs.iter().any(|(_, output)| match output {
         Output::MissingVariable => false,
-        Output::Bool(_) => false,
+        Output::Bool(_) | Output::Enum(_) => false,
         Output::Interval(interval) => interval.is_empty(),
     }) {
         return Vec::new();
@@ -65,6 +147,7 @@ fn ntuple_multi_cartesian_product(ntuple: &NTupleOutput) -> Vec<NTupleSingleInte
                 match output {
                     Output::MissingVariable => Output::MissingVariable,
                     Output::Bool(x) => Output::Bool(*x),
+                    Output::Enum(x) => Output::Enum(x.clone()),
                     Output::Interval(x) => Output::Interval(x.intervals[0]),
                 },
             )
@@ -79,7 +162,7 @@ fn ntuple_multi_cartesian_product(ntuple: &NTupleOutput) -> Vec<NTupleSingleInte
         let current = res.clone();
 
         match output {
-            Output::MissingVariable | Output::Bool(_) => (),
+            Output::MissingVariable | Output::Bool(_) | Output::Enum(_) => (),
             Output::Interval(interval) => {
                 let mut new = Vec::new();
                 for interval in interval.intervals.iter().skip(1) {
@@ -107,7 +190,11 @@ fn calc_in_on_inin(ntuple: &NTupleInput) -> Vec<NTupleOutput> {
             .iter()
             .map(|(var_name, input)| -> (String, Output<MultiInterval>) {
                 let output = match input {
+                    Input::Any => {
+                        unreachable!("The wildcard inputs are removed by remove_wildcard_inputs")
+                    }
                     Input::Bool(BoolDTO { bool_val, .. }) => Output::Bool(*bool_val),
+                    Input::Enum(enum_dto) => first_enum_value(enum_dto),
                     Input::Interval(IntervalDTO {
                         is_constant,
                         interval,
@@ -147,6 +234,14 @@ fn calc_in_on_inin(ntuple: &NTupleInput) -> Vec<NTupleOutput> {
     ]
 }
 
+/// Every enum value is tested separately, so the in, on and baseline test cases use the first one
+fn first_enum_value(enum_dto: &EnumDTO) -> Output<MultiInterval> {
+    enum_dto
+        .values
+        .first()
+        .map_or(Output::MissingVariable, |value| Output::Enum(value.clone()))
+}
+
 fn baseline(ntuple: &NTupleInput) -> NTupleOutput {
     let outputs = ntuple
         .inputs
@@ -154,7 +249,11 @@ fn baseline(ntuple: &NTupleInput) -> NTupleOutput {
         .into_iter()
         .map(|(var_name, input)| {
             let outputs = match input {
+                Input::Any => {
+                    unreachable!("The wildcard inputs are removed by remove_wildcard_inputs")
+                }
                 Input::Bool(BoolDTO { bool_val, .. }) => Output::Bool(bool_val),
+                Input::Enum(enum_dto) => first_enum_value(&enum_dto),
                 Input::Interval(IntervalDTO {
                     interval,
                     precision,
@@ -181,6 +280,20 @@ fn off_out(ntuple: &NTupleInput) -> Vec<NTupleOutput> {
         }
 
         match input {
+            Input::Any => unreachable!("The wildcard inputs are removed by remove_wildcard_inputs"),
+            // The rest of the values, and a value which doesn't satisfy the condition, if there is one
+            Input::Enum(EnumDTO {
+                values,
+                other_values,
+            }) => {
+                for value in values.iter().skip(1).chain(other_values.first()) {
+                    let mut base_enum = base.clone();
+                    base_enum
+                        .outputs
+                        .insert(i.clone(), Output::Enum(value.clone()));
+                    output.push(base_enum);
+                }
+            }
             Input::Bool(BoolDTO { expression, .. }) => match expression {
                 BoolExpression::IsTrue => {
                     let mut base_bool_true = base.clone();
@@ -234,23 +347,390 @@ fn off_out(ntuple: &NTupleInput) -> Vec<NTupleOutput> {
     output
 }
 
+/// How the values of the test cases are chosen by [`generate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strategy {
+    /// The in, on, off and out intervals around every boundary of every n-tuple
+    #[default]
+    BoundaryValue,
+    /// A single test case for every n-tuple, with the middle of every interval
+    Midpoint,
+    /// A single test case for every n-tuple, with a random value of every interval
+    Random,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GenConfig {
+    /// The seed of the random values, only used by `Strategy::Random`
+    pub seed: u64,
+    pub strategy: Strategy,
+    /// Drops the test cases which can't satisfy the relations between the variables of their predicate, like `start <= end`.
+    /// Otherwise the relations are ignored, and the variables are independent.
+    pub honor_relations: bool,
+}
+
+/// How many precision steps the random values can be from the finite side of an unbounded interval,
+/// or from `0` if both sides are unbounded.
+const UNBOUNDED_RANDOM_STEPS: f32 = 1000.0;
+
+fn random_value(interval: &Interval, precision: f32, rng: &mut SplitMix64) -> Option<f32> {
+    let span = UNBOUNDED_RANDOM_STEPS * precision;
+    let (lo, hi) = match (interval.lo.is_infinite(), interval.hi.is_infinite()) {
+        (true, true) => (-span, span),
+        (true, false) => (interval.hi - span, interval.hi),
+        (false, true) => (interval.lo, interval.lo + span),
+        (false, false) => (interval.lo, interval.hi),
+    };
+
+    // Interpolating instead of `lo + t * (hi - lo)`, so `[-f32::MAX, f32::MAX]` doesn't overflow
+    let t = rng.next_f32();
+    let value = lo * (1.0 - t) + hi * t;
+
+    interval.nearest_inside(snap_to_precision(value, precision), precision)
+}
+
+/// Replaces every interval of the tuple with a single value chosen by `pick`, the tuple is dropped if there is no value.
+/// The enums are pinned to their first value.
+/// The variables are visited in the order of their names, so a seeded `pick` always picks the same values.
+fn pin_values(
+    ntuple: &NTupleInput,
+    mut pick: impl FnMut(&MultiInterval, f32) -> Option<f32>,
+) -> Option<NTupleInput> {
+    let mut var_names = ntuple.inputs.keys().collect::<Vec<_>>();
+    var_names.sort_unstable();
+
+    let inputs = var_names
+        .into_iter()
+        .map(|var_name| {
+            let input = match &ntuple.inputs[var_name] {
+                Input::Interval(dto) => Input::Interval(IntervalDTO {
+                    interval: MultiInterval::new_closed_point(pick(
+                        &dto.rounded_interval(),
+                        dto.precision,
+                    )?),
+                    ..dto.clone()
+                }),
+                Input::Enum(dto) => Input::Enum(dto.pinned(dto.values.first()?)),
+                input => input.clone(),
+            };
+
+            Some((var_name.clone(), input))
+        })
+        .collect::<Option<_>>()?;
+
+    Some(NTupleInput { inputs })
+}
+
+/// Converts a generated test case back to an `NTupleInput`, the precision and the other metadata comes from `ntuple`.
+fn test_case_to_ntuple(ntuple: &NTupleInput, test_case: &NTupleSingleInterval) -> NTupleInput {
+    let inputs = ntuple
+        .inputs
+        .iter()
+        .map(|(var_name, input)| {
+            let input = match (input, test_case.get(var_name)) {
+                (Input::Interval(dto), Some(Output::Interval(interval))) => {
+                    Input::Interval(IntervalDTO {
+                        interval: MultiInterval::from_interval(*interval),
+                        ..dto.clone()
+                    })
+                }
+                (Input::Bool(dto), Some(Output::Bool(bool_val))) => Input::Bool(BoolDTO {
+                    expression: if *bool_val {
+                        BoolExpression::IsTrue
+                    } else {
+                        BoolExpression::IsFalse
+                    },
+                    bool_val: *bool_val,
+                    is_constant: dto.is_constant,
+                }),
+                (Input::Enum(dto), Some(Output::Enum(value))) => Input::Enum(dto.pinned(value)),
+                _ => Input::Any,
+            };
+
+            (var_name.clone(), input)
+        })
+        .collect();
+
+    NTupleInput { inputs }
+}
+
+/// Whether the values of the test case can satisfy all of the relations.
+/// A variable without an interval, like a wildcard, can be anything, so it satisfies every relation.
+pub(crate) fn satisfies_relations(
+    test_case: &NTupleInput,
+    relations: &[RelationCondition],
+) -> bool {
+    relations.iter().all(|relation| {
+        match (
+            test_case.inputs.get(&relation.left),
+            test_case.inputs.get(&relation.right),
+        ) {
+            (Some(Input::Interval(left)), Some(Input::Interval(right))) => {
+                relation.is_satisfiable(left, right)
+            }
+            _ => true,
+        }
+    })
+}
+
+/// Generates the test cases of a feature with one of the strategies.
+///
+/// The result is deterministic: the same feature and config always result in the same test cases in the same order,
+/// so it can be compared between runs, like in CI.
+pub fn generate(feature: &Feature, config: GenConfig) -> Vec<NTupleInput> {
+    let mut rng = SplitMix64::new(config.seed);
+
+    let ntuples = if config.honor_relations {
+        ir_to_ntuples_with_relations(feature)
+    } else {
+        ir_to_ntuple(feature)
+            .0
+            .into_iter()
+            .map(|ntuple| (ntuple, Vec::new()))
+            .collect()
+    };
+
+    let mut test_cases = ntuples
+        .iter()
+        .flat_map(|(ntuple, relations)| {
+            let test_cases = match config.strategy {
+                Strategy::BoundaryValue => {
+                    clamp_to_domains(generate_test_cases_for_inputs(ntuple), feature)
+                        .iter()
+                        .map(|test_case| test_case_to_ntuple(ntuple, test_case))
+                        .collect()
+                }
+                Strategy::Midpoint => pin_values(ntuple, |interval, precision| {
+                    interval
+                        .intervals
+                        .iter()
+                        .find_map(|interval| interval.middle_value(precision))
+                })
+                .into_iter()
+                .collect(),
+                Strategy::Random => pin_values(ntuple, |interval, precision| {
+                    let index = rng.next_u64() % interval.intervals.len().max(1) as u64;
+                    random_value(interval.intervals.get(index as usize)?, precision, &mut rng)
+                })
+                .into_iter()
+                .collect::<Vec<_>>(),
+            };
+
+            test_cases
+                .into_iter()
+                .filter(move |test_case| satisfies_relations(test_case, relations))
+        })
+        .collect::<Vec<_>>()
+        .uniques();
+
+    // The inputs are in a `HashMap`, so the order of the generated test cases can differ between runs
+    test_cases.sort_by_cached_key(ToString::to_string);
+
+    test_cases
+}
+
+/// Whether the system under test should accept or reject a test case of [`generate_with_negatives`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Expectation {
+    Accept,
+    Reject,
+}
+
+/// The test cases of a single variable of the tuple, every other variable is kept at the baseline value.
+///
+/// The accept cases are the boundary points inside the interval, the reject cases are the ones just outside of it,
+/// which are still in the domain of the variable.
+fn boundary_cases_of_variable(
+    baseline: &NTupleInput,
+    variable: &Variable,
+    input: &Input,
+) -> Vec<(NTupleInput, Expectation)> {
+    let with_input = |input: Input| {
+        let mut ntuple = baseline.clone();
+        ntuple.inputs.insert(variable.var_name.clone(), input);
+        ntuple
+    };
+
+    match input {
+        Input::Interval(dto) if !dto.is_constant => {
+            let dto = IntervalDTO {
+                interval: dto.rounded_interval(),
+                ..dto.clone()
+            };
+            let is_inside = |value: f32| {
+                dto.interval
+                    .intervals
+                    .iter()
+                    .any(|interval| interval.contains_point(value))
+            };
+            let is_in_domain = |value: f32| {
+                variable
+                    .domain
+                    .is_none_or(|domain| domain.contains_point(value))
+            };
+
+            dto.boundary_test_points()
+                .into_iter()
+                .filter_map(|point| {
+                    let expectation = match point.kind {
+                        BoundaryPointKind::Inside => Expectation::Accept,
+                        BoundaryPointKind::On if is_inside(point.value) => Expectation::Accept,
+                        BoundaryPointKind::On | BoundaryPointKind::Outside => Expectation::Reject,
+                    };
+                    if expectation == Expectation::Reject && !is_in_domain(point.value) {
+                        return None;
+                    }
+
+                    let input = Input::Interval(IntervalDTO {
+                        interval: MultiInterval::new_closed_point(point.value),
+                        ..dto.clone()
+                    });
+
+                    Some((with_input(input), expectation))
+                })
+                .collect()
+        }
+        Input::Enum(dto) => {
+            let pinned = |value: &String, expectation| {
+                (with_input(Input::Enum(dto.pinned(value))), expectation)
+            };
+
+            dto.values
+                .iter()
+                .map(|value| pinned(value, Expectation::Accept))
+                .chain(
+                    dto.other_values
+                        .iter()
+                        .map(|value| pinned(value, Expectation::Reject)),
+                )
+                .collect()
+        }
+        Input::Bool(dto) if !dto.is_constant => {
+            let flipped = Input::Bool(BoolDTO {
+                expression: match dto.expression {
+                    BoolExpression::IsTrue => BoolExpression::IsFalse,
+                    BoolExpression::IsFalse => BoolExpression::IsTrue,
+                },
+                bool_val: !dto.bool_val,
+                is_constant: false,
+            });
+
+            vec![
+                (with_input(input.clone()), Expectation::Accept),
+                (with_input(flipped), Expectation::Reject),
+            ]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Generates the positive and the negative test cases of every n-tuple of the feature.
+///
+/// The variables are tested one at a time, while the others are kept in the middle of their intervals.
+/// A test case is accepted if the value is on or inside the boundary of the interval,
+/// and rejected if it's just outside of it, but still in the domain of the variable, or if a bool is flipped.
+/// Every value of an enum is accepted, and every other declared value is rejected.
+/// Example: `x in [0, 10]` results in accepting `0`, `10` and rejecting `-1`, `11`.
+///
+/// A rejected test case of one n-tuple might be accepted by another n-tuple of the feature.
+/// The result is sorted, just like the result of [`generate`].
+pub fn generate_with_negatives(feature: &Feature) -> Vec<(NTupleInput, Expectation)> {
+    let mut test_cases = ir_to_ntuple(feature)
+        .0
+        .iter()
+        .flat_map(|ntuple| {
+            let Some(baseline) = pin_values(ntuple, |interval, precision| {
+                interval
+                    .intervals
+                    .iter()
+                    .find_map(|interval| interval.middle_value(precision))
+            }) else {
+                return Vec::new();
+            };
+
+            feature
+                .variables
+                .iter()
+                .filter_map(|variable| {
+                    let input = ntuple.inputs.get(&variable.var_name)?;
+                    Some(boundary_cases_of_variable(&baseline, variable, input))
+                })
+                .flatten()
+                .collect()
+        })
+        .collect::<Vec<_>>()
+        .uniques();
+
+    test_cases.sort_by_cached_key(|(ntuple, expectation)| (ntuple.to_string(), *expectation));
+
+    test_cases
+}
+
+/// Every combination of the boundary values of two interacting numeric variables, with the value of `a` first.
+///
+/// The boundary values are the `boundary_test_points` of the rounded intervals, so the On points and their neighbours.
+/// The independent boundary cases only move one variable at a time, these pairs also cover the corners.
+/// A variable without finite boundaries, like `(-Inf, Inf)`, is only paired with its representative value.
+///
+/// Example: `[0, 10]` and `x > 5` for two integer variables are paired like `(0, 6)`, `(0, 5)`, `(-1, 6)`, ... `(11, 5)`.
+pub fn boundary_pairs(a: &IntervalDTO, b: &IntervalDTO) -> Vec<(f32, f32)> {
+    let boundary_values = |dto: &IntervalDTO| {
+        let dto = IntervalDTO {
+            interval: dto.rounded_interval(),
+            ..dto.clone()
+        };
+        let points = dto
+            .boundary_test_points()
+            .into_iter()
+            .map(|point| point.value)
+            .collect::<Vec<_>>();
+
+        if points.is_empty() {
+            let precision = dto.precision;
+            Input::Interval(dto)
+                .pick_value(precision)
+                .as_ref()
+                .and_then(Value::as_number)
+                .into_iter()
+                .collect()
+        } else {
+            points
+        }
+    };
+
+    boundary_values(a)
+        .into_iter()
+        .cartesian_product(boundary_values(b))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
     use pretty_assertions::assert_eq;
+    use rstest::rstest;
     use Boundary::Open;
 
-    use super::{generate_test_cases_for_inputs, ntuple_multi_cartesian_product};
+    use super::{
+        boundary_pairs, generate, generate_test_cases_for_inputs,
+        generate_test_cases_for_multiple_features, generate_with_negatives,
+        ntuple_multi_cartesian_product, Expectation, GenConfig, Strategy,
+    };
     use crate::{
         dto::{
-            tests::{create_ntuple_input, create_ntuple_output, create_ntuple_single_interval},
-            BoolDTO, BoolExpression, Input, IntervalDTO, NTupleSingleInterval, Output,
+            tests::{
+                create_ntuple_input, create_ntuple_output, create_ntuple_single_interval,
+                precise_interval_input,
+            },
+            BoolDTO, BoolExpression, Input, IntervalDTO, NTupleInput, NTupleSingleInterval, Output,
         },
         interval::{
             test::{int, multiint},
             Boundary, Interval, MultiInterval,
         },
+        ir::Feature,
+        parser::{parse_gpt_to_features, parse_gpt_to_ir},
     };
 
     #[test]
@@ -449,6 +929,7 @@ mod tests {
                     interval: MultiInterval::new(Open, f32::NEG_INFINITY, 50.0, Open).unwrap(),
                     precision: 0.01,
                     is_constant: false,
+                    is_integer: false,
                 }),
             ),
         ]);
@@ -507,4 +988,365 @@ mod tests {
         assert!(result.iter().all(|x| expected.contains(x)));
         assert!(expected.iter().all(|x| result.contains(x)));
     }
+
+    #[test]
+    fn test_generate_test_cases_for_integer_inputs() {
+        let inputs = create_ntuple_input(vec![(
+            "x",
+            Input::Interval(IntervalDTO {
+                interval: MultiInterval::new(Open, f32::NEG_INFINITY, 7.5, Open).unwrap(),
+                precision: 1.0,
+                is_constant: false,
+                is_integer: true,
+            }),
+        )]);
+
+        let expected: Vec<NTupleSingleInterval> = vec![
+            // in
+            create_ntuple_single_interval(vec![(
+                "x",
+                Output::Interval(Interval::new_closed(f32::NEG_INFINITY, 7.0).unwrap()),
+            )]),
+            // on
+            create_ntuple_single_interval(vec![(
+                "x",
+                Output::Interval(Interval::new_closed_point(7.0)),
+            )]),
+            // inin
+            create_ntuple_single_interval(vec![(
+                "x",
+                Output::Interval(Interval::new_closed(f32::NEG_INFINITY, 6.0).unwrap()),
+            )]),
+            // off
+            create_ntuple_single_interval(vec![(
+                "x",
+                Output::Interval(Interval::new_closed_point(8.0)),
+            )]),
+            // out
+            create_ntuple_single_interval(vec![(
+                "x",
+                Output::Interval(Interval::new_closed(9.0, f32::INFINITY).unwrap()),
+            )]),
+        ];
+
+        let result = generate_test_cases_for_inputs(&inputs);
+
+        assert_eq!(result.len(), expected.len());
+        assert!(result.iter().all(|x| expected.contains(x)));
+        assert!(expected.iter().all(|x| result.contains(x)));
+    }
+
+    #[test]
+    fn test_generate_test_cases_leaves_out_wildcards() {
+        let inputs = create_ntuple_input(vec![
+            ("x", Input::Any),
+            (
+                "y",
+                Input::Bool(BoolDTO {
+                    expression: BoolExpression::IsTrue,
+                    bool_val: true,
+                    is_constant: false,
+                }),
+            ),
+        ]);
+
+        let result = generate_test_cases_for_inputs(&inputs);
+
+        assert!(!result.is_empty());
+        assert!(result.iter().all(|test_case| !test_case.contains_key("x")));
+    }
+
+    #[rstest]
+    #[case::less_than("x < 10", 9.0, 10.0)]
+    #[case::less_than_or_equal("x <= 10", 10.0, 11.0)]
+    #[case::greater_than("x > 10", 11.0, 10.0)]
+    fn test_generate_test_cases_for_integer_boundaries(
+        #[case] condition: &str,
+        #[case] on: f32,
+        #[case] off: f32,
+    ) {
+        let (_, features) =
+            parse_gpt_to_features(&format!("var x: int\n if({condition})")).unwrap();
+        let point = |value| {
+            create_ntuple_single_interval(vec![(
+                "x",
+                Output::Interval(Interval::new_closed_point(value)),
+            )])
+        };
+
+        let result = generate_test_cases_for_inputs(&features[0][0]);
+
+        assert!(
+            result.contains(&point(on)),
+            "{on} should be the on point in {result:?}"
+        );
+        assert!(
+            result.contains(&point(off)),
+            "{off} should be the off point in {result:?}"
+        );
+    }
+
+    fn feature(input: &str) -> Feature {
+        let (_, mut features) = parse_gpt_to_ir(input).unwrap();
+        features.remove(0)
+    }
+
+    #[test]
+    fn test_boundary_pairs() {
+        let dto = |interval: &str, precision: f32, is_integer: bool| IntervalDTO {
+            interval: multiint(interval),
+            precision,
+            is_constant: false,
+            is_integer,
+        };
+
+        assert_eq!(
+            boundary_pairs(&dto("[0, 10]", 1.0, true), &dto("(5, Inf)", 1.0, true)),
+            vec![
+                (0.0, 6.0),
+                (0.0, 5.0),
+                (-1.0, 6.0),
+                (-1.0, 5.0),
+                (10.0, 6.0),
+                (10.0, 5.0),
+                (11.0, 6.0),
+                (11.0, 5.0),
+            ]
+        );
+        assert_eq!(
+            boundary_pairs(&dto("(0, 1)", 0.5, false), &dto("(-Inf, Inf)", 1.0, true)),
+            vec![(0.0, 0.0), (0.5, 0.0), (1.0, 0.0)]
+        );
+        assert_eq!(
+            boundary_pairs(&dto("", 1.0, true), &dto("[0, 10]", 1.0, true)),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_generate_midpoint() {
+        let feature = feature(
+            r#"
+            var x: int
+            var y: num(0.1)
+            var flag: bool
+
+            if(x in [0, 10] && y in (0, 1) && flag == true)
+            if(x > 20)
+        "#,
+        );
+        let config = GenConfig {
+            strategy: Strategy::Midpoint,
+            ..GenConfig::default()
+        };
+
+        let result = generate(&feature, config);
+
+        assert_eq!(
+            result.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "flag = *, x ∈ [21, 21], y = *",
+                "flag = true, x ∈ [5, 5], y ∈ [0.5, 0.5]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_with_negatives() {
+        let feature = feature(
+            r#"
+            var x: int in [0, 100]
+            var flag: bool
+
+            if(x in [0, 10) && flag == true)
+        "#,
+        );
+
+        let result = generate_with_negatives(&feature)
+            .into_iter()
+            .map(|(ntuple, expectation)| (ntuple.to_string(), expectation))
+            .collect::<Vec<_>>();
+
+        // `x = -1` is not rejected, because it's outside of the domain
+        assert_eq!(
+            result,
+            vec![
+                ("flag = false, x ∈ [5, 5]".to_owned(), Expectation::Reject),
+                ("flag = true, x ∈ [0, 0]".to_owned(), Expectation::Accept),
+                ("flag = true, x ∈ [10, 10]".to_owned(), Expectation::Reject),
+                ("flag = true, x ∈ [5, 5]".to_owned(), Expectation::Accept),
+                ("flag = true, x ∈ [9, 9]".to_owned(), Expectation::Accept),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_random_is_reproducible() {
+        let feature = feature(
+            r#"
+            var x: int
+            var y: num(0.01)
+
+            if(x in [0, 1000] && y < 0)
+            if(x > 5000 || y in [10, 20])
+        "#,
+        );
+        let config = |seed| GenConfig {
+            seed,
+            strategy: Strategy::Random,
+            ..GenConfig::default()
+        };
+
+        let result = generate(&feature, config(42));
+
+        assert_eq!(result, generate(&feature, config(42)));
+        assert_ne!(result, generate(&feature, config(43)));
+        assert!(result.iter().all(|test_case| test_case.inputs.values().all(
+            |input| match input {
+                Input::Interval(dto) => dto.interval.is_single_point(),
+                input => *input == Input::Any,
+            }
+        )));
+    }
+
+    #[test]
+    fn test_boundary_values_stay_in_domain() {
+        let feature = feature(
+            r#"
+            var age: int in [0, 150]
+
+            if(age < 18)
+        "#,
+        );
+        let domain = multiint("[0, 150]");
+
+        let generated = generate(&feature, GenConfig::default());
+        assert!(!generated.is_empty());
+        for test_case in &generated {
+            let Some(Input::Interval(dto)) = test_case.inputs.get("age") else {
+                panic!("age should have an interval in {test_case}");
+            };
+            assert!(
+                dto.interval.is_subset_of(&domain),
+                "{test_case} should be inside the domain"
+            );
+        }
+
+        let test_cases = generate_test_cases_for_multiple_features(&[feature]).unwrap();
+        assert!(!test_cases.is_empty());
+        for test_case in &test_cases {
+            let Some(Output::Interval(interval)) = test_case.get("age") else {
+                panic!("age should have an interval in {test_case:?}");
+            };
+            assert!(
+                MultiInterval::from_interval(*interval).is_subset_of(&domain),
+                "{interval} should be inside the domain"
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_honoring_relations() {
+        let feature = feature(
+            r#"
+            var start: int in [0, 10]
+            var end: int in [0, 10]
+
+            if(start >= end && start > 5)
+        "#,
+        );
+        let config = |strategy, honor_relations| GenConfig {
+            strategy,
+            honor_relations,
+            ..GenConfig::default()
+        };
+        let to_strings = |test_cases: Vec<NTupleInput>| {
+            test_cases
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        // The relation does not change the tuple, only which values are kept
+        assert_eq!(
+            to_strings(generate(&feature, config(Strategy::Midpoint, false))),
+            vec!["end ∈ [5, 5], start ∈ [8, 8]"]
+        );
+        assert_eq!(
+            to_strings(generate(&feature, config(Strategy::Midpoint, true))),
+            vec!["end ∈ [5, 5], start ∈ [8, 8]"]
+        );
+
+        let independent = generate(&feature, config(Strategy::BoundaryValue, false));
+        let honored = generate(&feature, config(Strategy::BoundaryValue, true));
+        assert_eq!(independent.len(), 8);
+        assert_eq!(honored.len(), 7);
+        assert!(honored
+            .iter()
+            .all(|test_case| independent.contains(test_case)));
+
+        let honored = to_strings(honored);
+        assert!(honored.contains(&"end ∈ [10, 10], start ∈ [10, 10]".to_owned()));
+        assert!(!honored.contains(&"end ∈ [10, 10], start ∈ [6, 6]".to_owned()));
+        assert!(!honored.contains(&"end ∈ [11, 11], start ∈ [6, 10]".to_owned()));
+    }
+
+    #[test]
+    fn test_generate_enum() {
+        let feature = feature(
+            r#"
+            var status: enum {active, closed, on_hold}
+            var flag: bool
+
+            if(status in {active, closed} && flag == true)
+        "#,
+        );
+
+        let result = generate(&feature, GenConfig::default())
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        // Every value of the condition, and a value outside of it
+        assert_eq!(
+            result,
+            vec![
+                "flag = false, status ∈ {active}",
+                "flag = true, status ∈ {active}",
+                "flag = true, status ∈ {closed}",
+                "flag = true, status ∈ {on_hold}",
+            ]
+        );
+
+        let expectations = generate_with_negatives(&feature)
+            .into_iter()
+            .map(|(ntuple, expectation)| (ntuple.to_string(), expectation))
+            .collect::<Vec<_>>();
+
+        assert!(expectations.contains(&(
+            "flag = true, status ∈ {closed}".to_owned(),
+            Expectation::Accept
+        )));
+        assert!(expectations.contains(&(
+            "flag = true, status ∈ {on_hold}".to_owned(),
+            Expectation::Reject
+        )));
+    }
+
+    #[test]
+    fn test_generate_boundary_value() {
+        let feature = feature("var x: int\nif(x < 10)");
+
+        let result = generate(&feature, GenConfig::default());
+
+        assert_eq!(result, generate(&feature, GenConfig::default()));
+        assert!(result.contains(&create_ntuple_input(vec![(
+            "x",
+            precise_interval_input("[9, 9]", 1.0, true)
+        )])));
+        assert!(result.contains(&create_ntuple_input(vec![(
+            "x",
+            precise_interval_input("[10, 10]", 1.0, true)
+        )])));
+    }
 }
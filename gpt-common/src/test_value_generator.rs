@@ -1,17 +1,11 @@
-use crate::{dto::Output, interval::Interval};
+use crate::{
+    dto::{Input, IntervalDTO, Output, Value},
+    interval::{Interval, MultiInterval},
+};
 
-fn test_value_for_interval(interval: &Interval) -> Option<f32> {
-    if interval.is_empty() {
-        None
-    } else {
-        Some(match (interval.lo, interval.hi) {
-            (f32::NEG_INFINITY, f32::INFINITY) => 0.0,
-            (f32::NEG_INFINITY, x) => x,
-            (x, f32::INFINITY) => x,
-            (x, _) => x,
-        })
-    }
-}
+/// The intervals of the test cases were already stepped by the precision of the variable,
+/// so their values are picked without snapping them to a grid again
+const TEST_VALUE_PRECISION: f32 = 0.0;
 
 // TODO: There should be a value which returns the whole test case table
 pub fn generate_test_value(output: &Output<Interval>, show_interval_values: bool) -> String {
@@ -21,16 +15,47 @@ pub fn generate_test_value(output: &Output<Interval>, show_interval_values: bool
             true => "true".to_owned(),
             false => "false".to_owned(),
         },
+        Output::Enum(value) => value.clone(),
         Output::Interval(interval) => {
             if show_interval_values {
                 format!("{interval:?}")
             } else {
-                format!(
-                    "{:?}",
-                    test_value_for_interval(interval)
-                        .expect("NTupleSingleInterval should not be empty, it was checked before")
-                )
+                let input = Input::Interval(IntervalDTO {
+                    interval: MultiInterval::from_interval(*interval),
+                    precision: TEST_VALUE_PRECISION,
+                    is_constant: false,
+                    is_integer: false,
+                });
+                let value = input
+                    .pick_value(TEST_VALUE_PRECISION)
+                    .as_ref()
+                    .and_then(Value::as_number)
+                    .expect("NTupleSingleInterval should not be empty, it was checked before");
+
+                format!("{value:?}")
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use super::generate_test_value;
+    use crate::{dto::Output, interval::Interval};
+
+    #[rstest]
+    #[case("[5, 5]", "5.0")]
+    #[case("[0, 10]", "0.0")]
+    #[case("(0, 10)", "5.0")]
+    #[case("(-Inf, 10]", "10.0")]
+    #[case("(-Inf, Inf)", "0.0")]
+    fn test_generate_test_value(#[case] interval: Interval, #[case] expected: &str) {
+        assert_eq!(
+            generate_test_value(&Output::Interval(interval), false),
+            expected
+        );
+    }
+}
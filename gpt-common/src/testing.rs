@@ -0,0 +1,259 @@
+//! Helpers for writing tests on top of the interval types, enabled by the `testing` feature.
+//!
+//! ```
+//! use gpt_common::{assert_interval_eq, testing::{interval, multi_interval}};
+//!
+//! assert_interval_eq!(interval("[0, 10)"), interval("[0,10)"));
+//! assert_interval_eq!(multi_interval("(-Inf, 0) [5, 5]"), multi_interval("[5, 5] (-Inf, 0)"));
+//! ```
+//!
+//! The random generators are for fuzzing the code consuming the interval types, with any generator implementing [`Rng`].
+//! The seedable [`SplitMix64`] makes them reproducible:
+//!
+//! ```
+//! use gpt_common::{interval::MultiInterval, testing::{random_multi_interval, SplitMix64}};
+//!
+//! let mut rng = SplitMix64::new(42);
+//! let multi_interval = random_multi_interval(&mut rng, 4);
+//! assert_eq!(multi_interval.to_string().parse::<MultiInterval>().unwrap(), multi_interval);
+//! ```
+
+use nom::{
+    combinator::{all_consuming, complete},
+    multi::many0,
+};
+
+pub use crate::util::SplitMix64;
+use crate::{
+    interval::{Boundary, Interval, MultiInterval},
+    parser::interval as interval_parser,
+};
+
+/// Parses a single interval in the GPT syntax, like `[0, 10)` or `(-Inf, 5]`.
+///
+/// Panics if the input is not exactly one valid interval.
+pub fn interval(input: &str) -> Interval {
+    let intervals = multi_interval(input);
+
+    match intervals.intervals.as_slice() {
+        [interval] => *interval,
+        _ => panic!("{input:?} should be a single interval, but it is {intervals}"),
+    }
+}
+
+/// Parses a list of intervals in the GPT syntax, like `(-Inf, 0) [5, 10]`, an empty input is the empty set.
+/// The intervals are simplified, so overlapping ones are merged.
+///
+/// Panics if any of the intervals is invalid.
+pub fn multi_interval(input: &str) -> MultiInterval {
+    let (_, intervals) = all_consuming(many0(complete(interval_parser)))(input.trim())
+        .unwrap_or_else(|err| panic!("{input:?} should be a list of valid intervals: {err}"));
+
+    MultiInterval::from_intervals(
+        intervals
+            .into_iter()
+            .flat_map(|interval| interval.intervals)
+            .collect(),
+    )
+}
+
+/// A source of random numbers, so the random generators can be used with any generator, not just [`SplitMix64`].
+pub trait Rng {
+    /// The next random number, uniformly distributed in the whole range of `u64`
+    fn next_u64(&mut self) -> u64;
+}
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        Self::next_u64(self)
+    }
+}
+
+/// A multiple of `0.5` in `[-10, 10]`, so the random intervals often share or touch their endpoints.
+fn random_grid_value(rng: &mut impl Rng) -> f32 {
+    (rng.next_u64() % 41) as f32 / 2.0 - 10.0
+}
+
+/// The `infinity` in every 8th case, otherwise a grid value
+fn random_endpoint(rng: &mut impl Rng, infinity: f32) -> f32 {
+    match rng.next_u64() % 8 {
+        0 => infinity,
+        _ => random_grid_value(rng),
+    }
+}
+
+fn random_boundary(rng: &mut impl Rng) -> Boundary {
+    match rng.next_u64() % 2 {
+        0 => Boundary::Open,
+        _ => Boundary::Closed,
+    }
+}
+
+/// A random non-empty interval, with the endpoints on the grid of `0.5` steps in `[-10, 10]`.
+///
+/// Every 8th endpoint is infinite, and every 8th interval is a closed point, so the edge cases come up often.
+pub fn random_interval(rng: &mut impl Rng) -> Interval {
+    if rng.next_u64().is_multiple_of(8) {
+        return Interval::new_closed_point(random_grid_value(rng));
+    }
+
+    let (a, b) = (
+        random_endpoint(rng, f32::NEG_INFINITY),
+        random_endpoint(rng, f32::INFINITY),
+    );
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+
+    if lo == hi {
+        Interval::new_closed_point(lo)
+    } else {
+        Interval::new(random_boundary(rng), lo, hi, random_boundary(rng))
+            .expect("The endpoints are ordered and not NaN, so it should be a valid interval")
+    }
+}
+
+/// A random multi-interval of at most `max_pieces` intervals from [`random_interval`], it can also be empty.
+///
+/// The intervals are simplified like in `MultiInterval::from_intervals`, so overlapping ones are merged.
+pub fn random_multi_interval(rng: &mut impl Rng, max_pieces: usize) -> MultiInterval {
+    let pieces = rng.next_u64() % (max_pieces as u64 + 1);
+
+    MultiInterval::from_intervals((0..pieces).map(|_| random_interval(rng)).collect())
+}
+
+/// Asserts that two intervals (or multi-intervals) are equal, printing them in the GPT syntax on failure.
+///
+/// Like [`assert_eq!`], it takes an optional format string and arguments for a custom message.
+#[macro_export]
+macro_rules! assert_interval_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if *left != *right {
+                    panic!("assertion `left == right` failed\n  left: {left}\n right: {right}");
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if *left != *right {
+                    panic!(
+                        "assertion `left == right` failed: {}\n  left: {left}\n right: {right}",
+                        format_args!($($arg)+)
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::{
+        interval, multi_interval, random_interval, random_multi_interval, Rng, SplitMix64,
+    };
+    use crate::interval::{test::int, Interval, MultiInterval};
+
+    #[rstest]
+    #[case("[0, 10)", int("[0, 10)"))]
+    #[case("  (-Inf, 5] ", int("(-Inf, 5]"))]
+    #[case("[1.5,1.5]", Interval::new_closed_point(1.5))]
+    fn test_interval(#[case] input: &str, #[case] expected: Interval) {
+        assert_interval_eq!(interval(input), expected);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "should be a single interval")]
+    #[case("[0, 1] [2, 3]")]
+    #[should_panic(expected = "should be a single interval")]
+    #[case("")]
+    #[should_panic(expected = "should be a list of valid intervals")]
+    #[case("[10, 0]")]
+    #[should_panic(expected = "should be a list of valid intervals")]
+    #[case("[0, 1")]
+    fn test_interval_invalid(#[case] input: &str) {
+        interval(input);
+    }
+
+    #[test]
+    fn test_multi_interval() {
+        assert_interval_eq!(multi_interval(""), MultiInterval::new_empty());
+        assert_interval_eq!(
+            multi_interval("[5, 10] (-Inf, 0) [8, 20)"),
+            MultiInterval::from_intervals(vec![int("(-Inf, 0)"), int("[5, 20)")]),
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "assertion `left == right` failed: x\n  left: [0, 1]\n right: (0, 1]"
+    )]
+    fn test_assert_interval_eq_message() {
+        assert_interval_eq!(interval("[0, 1]"), interval("(0, 1]"), "{}", "x");
+    }
+
+    #[test]
+    fn test_random_interval() {
+        let mut rng = SplitMix64::new(0);
+        let intervals = (0..1000)
+            .map(|_| random_interval(&mut rng))
+            .collect::<Vec<_>>();
+
+        assert!(intervals.iter().all(|interval| !interval.is_empty()));
+        assert!(intervals
+            .iter()
+            .any(|interval| interval.lo == f32::NEG_INFINITY));
+        assert!(intervals
+            .iter()
+            .any(|interval| interval.hi == f32::INFINITY));
+        assert!(intervals.iter().any(|interval| interval.lo == interval.hi));
+        assert!(intervals
+            .iter()
+            .all(|interval| interval.to_string().parse::<Interval>() == Ok(*interval)));
+    }
+
+    /// Always returns the same number
+    struct ConstantRng(u64);
+
+    impl Rng for ConstantRng {
+        fn next_u64(&mut self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_random_interval_with_any_rng() {
+        // `0` picks the closed point, and the smallest grid value
+        assert_interval_eq!(
+            random_interval(&mut ConstantRng(0)),
+            Interval::new_closed_point(-10.0)
+        );
+        assert_interval_eq!(
+            random_multi_interval(&mut ConstantRng(0), 4),
+            MultiInterval::new_empty()
+        );
+    }
+
+    #[test]
+    fn test_random_multi_interval() {
+        let mut rng = SplitMix64::new(0);
+
+        for _ in 0..1000 {
+            let multi_interval = random_multi_interval(&mut rng, 4);
+
+            assert!(multi_interval.intervals.len() <= 4);
+            assert_interval_eq!(
+                MultiInterval::from_intervals(multi_interval.intervals.clone()),
+                multi_interval
+            );
+            assert_interval_eq!(
+                multi_interval.complement().complement(),
+                multi_interval,
+                "the complement of the complement should be the original"
+            );
+        }
+    }
+}
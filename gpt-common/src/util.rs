@@ -32,6 +32,54 @@ impl<T: PartialEq + Clone> ContinousSublistsFromFirst<T> for Vec<T> {
     }
 }
 
+/// Rounds the value to the closest multiple of the precision.
+///
+/// Example: `0.123` with the precision of `0.01` will be `0.12`, `6.5` with the precision of `1` will be `7`.
+/// Infinite values and non-positive precisions leave the value as it is.
+pub fn snap_to_precision(value: f32, precision: f32) -> f32 {
+    if !value.is_finite() || precision <= 0.0 {
+        return value;
+    }
+
+    grid_value((value / precision).round(), precision)
+}
+
+/// The value `steps` multiples of the precision away from `0`.
+pub fn grid_value(steps: f32, precision: f32) -> f32 {
+    // Dividing by the reciprocal when it is a whole number, so 3 * 0.1 becomes 0.3 and not 0.30000001
+    let reciprocal = precision.recip();
+    if reciprocal.fract() == 0.0 {
+        steps / reciprocal
+    } else {
+        steps * precision
+    }
+}
+
+/// A small seedable pseudo-random number generator (SplitMix64), the same seed always results in the same numbers.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub const fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `[0, 1)`, made from the upper 24 bits, because that's how many fit into the mantissa of an `f32`.
+    pub const fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
@@ -59,4 +107,30 @@ mod test {
     fn test_continous_sublists_from_first(#[case] input: Vec<u8>, #[case] expected: Vec<Vec<u8>>) {
         assert_eq!(input.continous_sublists_from_first(), expected);
     }
+
+    #[rstest]
+    #[case(0.123, 0.01, 0.12)]
+    #[case(0.3 + f32::EPSILON, 0.1, 0.3)]
+    #[case(6.5, 1.0, 7.0)]
+    #[case(-6.4, 1.0, -6.0)]
+    #[case(7.3, 0.5, 7.5)]
+    #[case(12.0, 5.0, 10.0)]
+    #[case(f32::INFINITY, 1.0, f32::INFINITY)]
+    #[case(f32::NEG_INFINITY, 0.01, f32::NEG_INFINITY)]
+    #[case(0.123, 0.0, 0.123)]
+    fn test_snap_to_precision(#[case] value: f32, #[case] precision: f32, #[case] expected: f32) {
+        assert_eq!(snap_to_precision(value, precision), expected);
+    }
+
+    #[test]
+    fn test_split_mix_64() {
+        let numbers = |seed| {
+            let mut rng = SplitMix64::new(seed);
+            (0..100).map(|_| rng.next_f32()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers(42), numbers(42));
+        assert_ne!(numbers(42), numbers(43));
+        assert!(numbers(42).iter().all(|x| (0.0..1.0).contains(x)));
+    }
 }
@@ -0,0 +1,26 @@
+//! WASM bindings for the whole pipeline. Everything crosses the boundary as JSON strings,
+//! because the internal types and the nom errors can't be passed to JS.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    generate_tests_json_for_gpt_input, ntuples_json_for_gpt_input, warnings_json_for_gpt_input,
+};
+
+/// Parses the GPT source and returns the n-tuples of every feature as JSON.
+#[wasm_bindgen]
+pub fn feature_ntuples(source: &str) -> Result<String, JsValue> {
+    ntuples_json_for_gpt_input(source).map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Parses the GPT source and returns the warning messages of every feature as JSON.
+#[wasm_bindgen]
+pub fn feature_warnings(source: &str) -> Result<String, JsValue> {
+    warnings_json_for_gpt_input(source).map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Parses the GPT source and returns the generated test cases as JSON.
+#[wasm_bindgen]
+pub fn generate_tests(source: &str) -> Result<String, JsValue> {
+    generate_tests_json_for_gpt_input(source).map_err(|err| JsValue::from_str(&err.to_string()))
+}
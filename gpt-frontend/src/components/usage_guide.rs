@@ -24,7 +24,7 @@ pub fn usage_guide(props: &Props) -> Html {
         </p>
         <p>
           {"You can leave lines empty, or create a commented line beginning with "}
-          <code>{"//"}</code>
+          <code>{"//"}</code>{" or "}<code>{"#"}</code>
         </p>
         <p>
           {"The variables have the following structure:"}